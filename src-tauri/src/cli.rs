@@ -0,0 +1,63 @@
+use crate::services::reassembler::reassemble;
+
+const USAGE: &str = "Usage: video-partitioner reassemble <manifest.json> [output]";
+
+/// Runs a command-line subcommand if one was given.
+///
+/// Returns the process exit code when a subcommand ran, or `None` to start the GUI.
+/// Release builds use the Windows GUI subsystem, so redirect output to a file to see it.
+pub fn run(args: &[String]) -> Option<i32> {
+    let subcommand = args.get(1)?;
+
+    match subcommand.as_str() {
+        "reassemble" => Some(run_reassemble(&args[2..])),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Some(0)
+        }
+        _ => None,
+    }
+}
+
+fn run_reassemble(args: &[String]) -> i32 {
+    let Some(manifest_path) = args.first() else {
+        eprintln!("{USAGE}");
+        return 2;
+    };
+    let output_path = args.get(1).map(String::as_str);
+
+    match tauri::async_runtime::block_on(reassemble(manifest_path, output_path)) {
+        Ok(report) => {
+            println!("Reassembled: {}", report.output_path);
+            println!("Verified partitions: {}", report.verified_partitions);
+            println!(
+                "Duration: {:.3}s (expected {:.3}s) {}",
+                report.actual_duration_secs,
+                report.expected_duration_secs,
+                if report.duration_matches {
+                    "OK"
+                } else {
+                    "MISMATCH"
+                }
+            );
+            println!(
+                "Streams: {} (expected {}) {}",
+                report.actual_streams.join(", "),
+                report.expected_streams.join(", "),
+                if report.streams_match {
+                    "OK"
+                } else {
+                    "MISMATCH"
+                }
+            );
+            i32::from(!(report.duration_matches && report.streams_match))
+        }
+        Err(e) => {
+            eprintln!("Reassembly failed: {e}");
+            if let Some(stderr) = e.stderr() {
+                eprintln!("{stderr}");
+            }
+            1
+        }
+    }
+}
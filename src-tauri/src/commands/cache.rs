@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::models::thumbnail::ThumbnailCacheInfo;
+use crate::services::thumbnail_cache;
+
+/// Resolves the app cache directory where generated thumbnails are kept.
+pub fn cache_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app.path_resolver()
+        .app_cache_dir()
+        .ok_or_else(|| AppError::internal("Could not resolve the app cache directory"))
+}
+
+/// Describes the thumbnail cache: its location, size, limit and entries.
+#[tauri::command]
+pub fn get_thumbnail_cache_info(app: AppHandle) -> Result<ThumbnailCacheInfo, AppError> {
+    Ok(thumbnail_cache::inspect(
+        &cache_dir(&app)?,
+        thumbnail_cache::CACHE_LIMIT_BYTES,
+    ))
+}
+
+/// Deletes all cached thumbnails and returns the number of bytes freed.
+#[tauri::command]
+pub fn clear_thumbnail_cache(app: AppHandle) -> Result<u64, AppError> {
+    let freed = thumbnail_cache::clear(&cache_dir(&app)?)?;
+
+    // Thumbnails were kept in the temp directory before the cache existed
+    let legacy = std::env::temp_dir().join("video-partitioner-thumbs");
+    let legacy_bytes = thumbnail_cache::dir_size(&legacy);
+    if std::fs::remove_dir_all(&legacy).is_ok() {
+        return Ok(freed + legacy_bytes);
+    }
+    Ok(freed)
+}
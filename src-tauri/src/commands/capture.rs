@@ -0,0 +1,18 @@
+use tauri::AppHandle;
+
+use crate::commands::presets::config_dir;
+use crate::error::AppError;
+use crate::models::capture::CaptureSettings;
+use crate::services::capture;
+
+/// Returns the saved screenshot settings, or the defaults.
+#[tauri::command]
+pub fn get_capture_settings(app: AppHandle) -> Result<CaptureSettings, AppError> {
+    capture::load_settings(&config_dir(&app)?)
+}
+
+/// Saves the screenshot settings used by `capture_frame`.
+#[tauri::command]
+pub fn save_capture_settings(app: AppHandle, settings: CaptureSettings) -> Result<(), AppError> {
+    capture::save_settings(&config_dir(&app)?, &settings)
+}
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::models::partition::cut_list::{parse_cut_list, write_cut_list, CutListFormat};
+use crate::models::partition::TimeInterval;
+
+/// Detects the cut list format from the file extension.
+fn format_for(path: &Path) -> Result<CutListFormat, AppError> {
+    path.extension()
+        .and_then(|e| CutListFormat::from_extension(&e.to_string_lossy()))
+        .ok_or_else(|| {
+            AppError::invalid(format!(
+                "Unsupported cut list {}: expected .edl, .csv, .ffmeta, .ffmetadata or .cue",
+                path.display()
+            ))
+        })
+}
+
+/// Reads exclusions from an EDL, CSV, `FFmpeg` metadata chapter or CUE file.
+///
+/// `frame_rate` is the video's frame rate, used for SMPTE timecodes.
+#[tauri::command]
+pub fn import_cut_list(file_path: String, frame_rate: f64) -> Result<Vec<TimeInterval>, AppError> {
+    let path = Path::new(&file_path);
+    let format = format_for(path)?;
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::io("Failed to read cut list", path, &e))?;
+    parse_cut_list(&content, format, frame_rate).map_err(AppError::invalid)
+}
+
+/// Writes exclusions to an EDL, CSV, `FFmpeg` metadata chapter or CUE file.
+#[tauri::command]
+pub fn export_cut_list(
+    file_path: String,
+    intervals: Vec<TimeInterval>,
+    frame_rate: f64,
+) -> Result<(), AppError> {
+    let path = Path::new(&file_path);
+    let content =
+        write_cut_list(&intervals, format_for(path)?, frame_rate).map_err(AppError::invalid)?;
+    std::fs::write(path, content).map_err(|e| AppError::io("Failed to write cut list", path, &e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejected_extension_lists_supported_ones() {
+        assert_eq!(
+            format_for(Path::new("cuts.cue")).unwrap(),
+            CutListFormat::Cue
+        );
+
+        let error = format_for(Path::new("cuts.txt")).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .ends_with("expected .edl, .csv, .ffmeta, .ffmetadata or .cue"),
+            "{error}"
+        );
+    }
+}
@@ -1,200 +1,337 @@
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
 
-use crate::models::partition::SplitRequest;
-use crate::services::splitter::split_video;
-use crate::utils::ffmpeg_wrapper::{format_ffmpeg_time, run_ffmpeg};
+use tauri::{AppHandle, State, Window};
 
-/// Executes an `FFmpeg` split operation using stream copy mode.
-///
-/// Uses `-c copy` to avoid re-encoding, preserving original quality.
-#[tauri::command]
-pub async fn execute_split(request: SplitRequest) -> Result<String, String> {
-    let output_files = split_video(&request).await?;
-    let count = output_files.len();
-    Ok(format!("Split complete: {count} partition(s) created"))
-}
+use crate::commands::cache::cache_dir;
+use crate::commands::file::list_directory;
+use crate::commands::metadata::get_video_metadata;
+use crate::commands::presets::config_dir;
+use crate::error::AppError;
+use crate::models::animation::{AnimationExport, AnimationRequest};
+use crate::models::audio::AudioExportRequest;
+use crate::models::capture::{Burst, CapturedFrame};
+use crate::models::contact_sheet::{
+    ContactSheet, ContactSheetBatch, ContactSheetFailure, ContactSheetOptions, ContactSheetProgress,
+};
+use crate::models::manifest::ReassemblyReport;
+use crate::models::partition::{
+    ByteSplitRequest, PreflightReport, SplitPlan, SplitProgress, SplitRequest,
+};
+use crate::models::thumbnail::{ThumbnailLayout, ThumbnailStrip};
+use crate::models::video::PreviewSource;
+use crate::services::byte_splitter::split_file_bytes;
+use crate::services::preview::PreviewFrames;
+use crate::services::reassembler;
+use crate::services::splitter::{self, preflight_split, split_video};
+use crate::services::{animation, audio, capture, contact_sheet, thumbnails, transcode};
 
-/// Generates evenly-spaced thumbnail images from a video using `FFmpeg`.
+/// Event emitted to the window with a [`SplitProgress`] payload while a split runs.
+pub const SPLIT_PROGRESS_EVENT: &str = "split-progress";
+
+/// Event emitted to the window with a [`SplitProgress`] payload after each file of an
+/// audio export.
+pub const AUDIO_EXPORT_PROGRESS_EVENT: &str = "audio-export-progress";
+
+/// Event emitted to the window with a [`ContactSheetProgress`] payload before each video
+/// of a contact sheet batch.
+pub const CONTACT_SHEET_PROGRESS_EVENT: &str = "contact-sheet-progress";
+
+/// Event emitted to the window with a [`ThumbnailReady`] payload for each new thumbnail.
 ///
-/// Uses per-frame input-level seeking (`-ss` before `-i`) for each thumbnail,
-/// which is fast regardless of video length (keyframe seek, no full decode).
-#[tauri::command]
-pub async fn generate_thumbnails(
-    video_path: String,
-    count: u32,
-    height: u32,
-) -> Result<Vec<String>, String> {
-    if count == 0 {
-        return Err("Thumbnail count must be greater than 0".to_string());
-    }
+/// [`ThumbnailReady`]: crate::models::thumbnail::ThumbnailReady
+pub const THUMBNAIL_READY_EVENT: &str = "thumbnail-ready";
+
+/// Cancel flags of running jobs of one kind.
+#[derive(Default)]
+struct CancelFlags(Mutex<Vec<Weak<AtomicBool>>>);
 
-    let input = Path::new(&video_path);
-    if !input.exists() {
-        return Err(format!("Video file not found: {video_path}"));
+impl CancelFlags {
+    /// Returns a new flag for a job. Every job has its own, so starting one never
+    /// clears a cancellation sent to another.
+    fn begin(&self) -> Arc<AtomicBool> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut running = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        running.retain(|flag| flag.strong_count() > 0);
+        running.push(Arc::downgrade(&cancel));
+        drop(running);
+        cancel
     }
 
-    // Create a deterministic subdirectory based on the video path
-    let mut hasher = DefaultHasher::new();
-    video_path.hash(&mut hasher);
-    let hash = hasher.finish();
-
-    let thumb_dir = std::env::temp_dir()
-        .join("video-partitioner-thumbs")
-        .join(format!("{hash:x}"));
-
-    // Return cached thumbnails if they already exist for this video
-    if thumb_dir.exists() {
-        let mut cached: Vec<String> = Vec::new();
-        for i in 1..=count {
-            let p = thumb_dir.join(format!("thumb_{i:04}.jpg"));
-            if p.exists() {
-                cached.push(p.to_string_lossy().to_string());
-            }
+    /// Sets the flag of every running job.
+    fn cancel_all(&self) {
+        let running: Vec<Arc<AtomicBool>> = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+        for flag in running {
+            flag.store(true, Ordering::Relaxed);
         }
-        if cached.len() == count as usize {
-            return Ok(cached);
-        }
-        let _ = std::fs::remove_dir_all(&thumb_dir);
     }
+}
 
-    std::fs::create_dir_all(&thumb_dir)
-        .map_err(|e| format!("Failed to create thumbnail directory: {e}"))?;
+/// Cancel flags of the running splits, each set by [`cancel_split`].
+#[derive(Default)]
+pub struct SplitCancellation(CancelFlags);
 
-    let duration = get_duration(&video_path).await?;
-    if duration <= 0.0 {
-        return Err("Video has no duration".to_string());
-    }
+/// Cancel flags of the running audio exports, each set by [`cancel_audio_export`].
+#[derive(Default)]
+pub struct AudioExportCancellation(CancelFlags);
 
-    let interval = duration / f64::from(count);
-    let scale_filter = format!("scale=-1:{height}");
-
-    // Generate each thumbnail with fast input-level seeking
-    for i in 0..count {
-        let timestamp = interval * (f64::from(i) + 0.5);
-        let ss_arg = format_ffmpeg_time(timestamp);
-        let output_path = thumb_dir.join(format!("thumb_{:04}.jpg", i + 1));
-        let output_str = output_path.to_string_lossy().to_string();
-
-        let (_, stderr, exit_code) = run_ffmpeg(&[
-            "-ss",
-            &ss_arg,
-            "-i",
-            &video_path,
-            "-vframes",
-            "1",
-            "-vf",
-            &scale_filter,
-            "-q:v",
-            "5",
-            "-y",
-            &output_str,
-        ])
-        .await?;
-
-        match exit_code {
-            Some(0) | None => {}
-            Some(code) => {
-                return Err(format!(
-                    "FFmpeg thumbnail {i} exited with code {code}: {stderr}"
-                ));
-            }
-        }
-    }
+/// Cancellation flag of the running preview conversion; starting another one cancels it.
+#[derive(Default)]
+pub struct PreviewTranscode(Mutex<Arc<AtomicBool>>);
 
-    // Collect generated thumbnail paths in order
-    let mut paths = Vec::new();
-    for i in 1..=count {
-        let thumb_path = thumb_dir.join(format!("thumb_{i:04}.jpg"));
-        if thumb_path.exists() {
-            paths.push(thumb_path.to_string_lossy().to_string());
-        }
+impl PreviewTranscode {
+    /// Cancels the previous conversion and returns the flag for a new one.
+    fn begin(&self) -> Arc<AtomicBool> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut current = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let superseded = std::mem::replace(&mut *current, Arc::clone(&cancel));
+        drop(current);
+        superseded.store(true, Ordering::Relaxed);
+        cancel
     }
+}
 
-    if paths.is_empty() {
-        return Err("No thumbnails were generated".to_string());
-    }
+/// Executes an `FFmpeg` split operation.
+///
+/// The request's encode profile chooses between stream copy, which keeps the original
+/// quality, and re-encoding every partition to H.264 or HEVC.
+#[tauri::command]
+pub async fn execute_split(
+    window: Window,
+    cancellation: State<'_, SplitCancellation>,
+    request: SplitRequest,
+) -> Result<String, AppError> {
+    let cancel = cancellation.0.begin();
+    let output_files = split_video(&request, &cancel, &|progress| {
+        let _ = window.emit(SPLIT_PROGRESS_EVENT, progress);
+    })
+    .await?;
+    let count = output_files.len();
+    Ok(format!("Split complete: {count} partition(s) created"))
+}
 
-    Ok(paths)
+/// Cancels the running splits. Their temporary files are cleaned up as they stop.
+#[tauri::command]
+pub fn cancel_split(cancellation: State<'_, SplitCancellation>) {
+    cancellation.0.cancel_all();
 }
 
-/// Captures a single video frame as a JPEG image.
+/// Checks whether the output volume has room for a split before starting it.
 ///
-/// Saves the frame to the same directory as the source video, named
-/// `{stem}_frame_{HH_MM_SS_mmm}.jpg`.
+/// Returns the exact byte counts so the UI can warn about insufficient space.
 #[tauri::command]
-pub async fn capture_frame(video_path: String, timestamp_secs: f64) -> Result<String, String> {
-    let input = Path::new(&video_path);
-    if !input.exists() {
-        return Err(format!("Video file not found: {video_path}"));
-    }
+pub async fn check_split_space(request: SplitRequest) -> Result<PreflightReport, AppError> {
+    preflight_split(&request).await
+}
+
+/// Describes what [`execute_split`] would do without writing anything.
+///
+/// Returns every partition's segments, keyframe-snapped start times, estimated size,
+/// output path and the exact `FFmpeg` arguments.
+#[tauri::command]
+pub async fn plan_split(request: SplitRequest) -> Result<SplitPlan, AppError> {
+    splitter::plan_split(&request).await
+}
 
-    let stem = input
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("video");
-
-    let parent = input
-        .parent()
-        .ok_or_else(|| "Cannot determine parent directory".to_string())?;
-
-    let time_str = format_ffmpeg_time(timestamp_secs).replace(':', "_");
-    let output_name = format!("{stem}_frame_{time_str}.jpg");
-    let output_path = parent.join(&output_name);
-    let output_str = output_path.to_string_lossy().to_string();
-
-    let ss_arg = format_ffmpeg_time(timestamp_secs);
-
-    let (_, stderr, exit_code) = run_ffmpeg(&[
-        "-ss",
-        &ss_arg,
-        "-i",
-        &video_path,
-        "-vframes",
-        "1",
-        "-q:v",
-        "2",
-        "-y",
-        &output_str,
-    ])
+/// Exports the audio of a file as one file or as parts matching a split, in m4a, mp3,
+/// flac or wav.
+///
+/// Emits [`AUDIO_EXPORT_PROGRESS_EVENT`] after each file and stops on
+/// [`cancel_audio_export`], independently of splits.
+#[tauri::command]
+pub async fn execute_audio_export(
+    window: Window,
+    cancellation: State<'_, AudioExportCancellation>,
+    request: AudioExportRequest,
+) -> Result<String, AppError> {
+    let cancel = cancellation.0.begin();
+    let output_files = audio::export_audio(&request, &cancel, &|progress| {
+        let _ = window.emit(AUDIO_EXPORT_PROGRESS_EVENT, progress);
+    })
     .await?;
+    let count = output_files.len();
+    Ok(format!("Audio export complete: {count} file(s) created"))
+}
+
+/// Cancels the running audio exports; partially written files are removed.
+#[tauri::command]
+pub fn cancel_audio_export(cancellation: State<'_, AudioExportCancellation>) {
+    cancellation.0.cancel_all();
+}
+
+/// Splits any file into fixed-size byte chunks (`.001`, `.002`, ...).
+///
+/// Runs on the blocking thread pool and reports progress like [`execute_split`].
+#[tauri::command]
+pub async fn execute_byte_split(
+    window: Window,
+    cancellation: State<'_, SplitCancellation>,
+    request: ByteSplitRequest,
+) -> Result<String, AppError> {
+    let cancel = cancellation.0.begin();
+    let output_files = tauri::async_runtime::spawn_blocking(move || {
+        split_file_bytes(&request, &cancel, &|progress: SplitProgress| {
+            let _ = window.emit(SPLIT_PROGRESS_EVENT, progress);
+        })
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Split task failed: {e}")))??;
+    let count = output_files.len();
+    Ok(format!("Split complete: {count} chunk(s) created"))
+}
+
+/// Rebuilds a single file from the partitions listed in a split manifest.
+///
+/// Verifies partition checksums, concatenates with stream copy, and reports whether
+/// the result matches the expected duration and stream layout.
+#[tauri::command]
+pub async fn reassemble(
+    manifest_path: String,
+    output_path: Option<String>,
+) -> Result<ReassemblyReport, AppError> {
+    reassembler::reassemble(&manifest_path, output_path.as_deref()).await
+}
 
-    match exit_code {
-        Some(0) | None => {}
-        Some(code) => {
-            return Err(format!(
-                "FFmpeg frame capture exited with code {code}: {stderr}"
-            ));
+/// Generates evenly-spaced thumbnail images from a video using `FFmpeg`.
+///
+/// With the default `files` layout, thumbnails are extracted in parallel and each one is
+/// announced with a [`THUMBNAIL_READY_EVENT`] as soon as it exists, so the timeline fills
+/// in progressively. The `sprite` layout renders one tiled image plus a `WebVTT` track in a
+/// single pass instead. Results are reused from the thumbnail cache.
+#[tauri::command]
+pub async fn generate_thumbnails(
+    app: AppHandle,
+    window: Window,
+    video_path: String,
+    count: u32,
+    height: u32,
+    layout: Option<ThumbnailLayout>,
+) -> Result<ThumbnailStrip, AppError> {
+    let cache_dir = cache_dir(&app)?;
+    match layout.unwrap_or_default() {
+        ThumbnailLayout::Files => {
+            let paths =
+                thumbnails::generate_thumbnails(&cache_dir, &video_path, count, height, &|ready| {
+                    let _ = window.emit(THUMBNAIL_READY_EVENT, ready);
+                })
+                .await?;
+            Ok(ThumbnailStrip::Files { paths })
+        }
+        ThumbnailLayout::Sprite => {
+            thumbnails::generate_sprite_sheet(&cache_dir, &video_path, count, height)
+                .await
+                .map(ThumbnailStrip::Sprite)
         }
     }
+}
 
-    if !output_path.exists() {
-        return Err(format!(
-            "Frame capture failed: output file was not created. FFmpeg output: {stderr}"
-        ));
-    }
+/// Returns a small frame of the video at `timestamp_secs` as a base64 JPEG data URL,
+/// for hover previews while placing exclusion edges.
+///
+/// Recent frames are served from memory; a newer request cancels an older one still
+/// decoding, which then fails with the `cancelled` error code.
+#[tauri::command]
+pub async fn preview_frame(
+    previews: State<'_, PreviewFrames>,
+    video_path: String,
+    timestamp_secs: f64,
+    height: u32,
+) -> Result<String, AppError> {
+    previews.frame(&video_path, timestamp_secs, height).await
+}
 
-    Ok(output_str)
+/// Returns a file the webview can display for an image or video.
+///
+/// Formats it can't decode are converted to PNG/JPEG, or to a low-resolution H.264 proxy
+/// for videos, and cached. Opening another file cancels a conversion still running.
+#[tauri::command]
+pub async fn prepare_preview(
+    app: AppHandle,
+    transcode: State<'_, PreviewTranscode>,
+    file_path: String,
+) -> Result<PreviewSource, AppError> {
+    let cancel = transcode.begin();
+    transcode::prepare_preview(&cache_dir(&app)?, &file_path, &cancel).await
 }
 
-/// Gets video duration by parsing `FFmpeg` stderr output.
-async fn get_duration(file_path: &str) -> Result<f64, String> {
-    let (_, stderr, _) = run_ffmpeg(&["-i", file_path, "-hide_banner"]).await?;
+/// Captures the exact frame the player shows at `timestamp_secs`, or a `burst` of frames
+/// starting there, using the saved capture settings.
+///
+/// `frame_rate` is the video's frame rate if known. Images are named
+/// `{stem}_frame_{HH_MM_SS_mmm}.{ext}` after each frame's actual time, which is also
+/// returned.
+#[tauri::command]
+pub async fn capture_frame(
+    app: AppHandle,
+    video_path: String,
+    timestamp_secs: f64,
+    frame_rate: Option<f64>,
+    burst: Option<Burst>,
+) -> Result<Vec<CapturedFrame>, AppError> {
+    let settings = capture::load_settings(&config_dir(&app)?)?;
+    let frame_rate = frame_rate.filter(|fps| fps.is_finite()).unwrap_or(0.0);
+    capture::capture_frames(&video_path, timestamp_secs, frame_rate, &settings, burst).await
+}
 
-    for line in stderr.lines() {
-        if let Some(idx) = line.find("Duration:") {
-            let after = &line[idx + 9..];
-            let time_str = after.trim().split(',').next().unwrap_or("").trim();
-            let parts: Vec<&str> = time_str.split(':').collect();
-            if parts.len() == 3 {
-                let hours: f64 = parts[0].parse().unwrap_or(0.0);
-                let minutes: f64 = parts[1].parse().unwrap_or(0.0);
-                let seconds: f64 = parts[2].parse().unwrap_or(0.0);
-                return Ok(hours.mul_add(3600.0, minutes.mul_add(60.0, seconds)));
-            }
+/// Writes a contact sheet for one video: a grid of frames with timecodes under a header
+/// with its file name, size, duration, resolution and codecs.
+#[tauri::command]
+pub async fn generate_contact_sheet(
+    video_path: String,
+    options: Option<ContactSheetOptions>,
+) -> Result<ContactSheet, AppError> {
+    let metadata = get_video_metadata(video_path).await?;
+    contact_sheet::generate_contact_sheet(&metadata, &options.unwrap_or_default()).await
+}
+
+/// Writes a contact sheet for every video in a directory, one after the other.
+///
+/// Each video is announced with a [`CONTACT_SHEET_PROGRESS_EVENT`]. A video that fails
+/// is reported in the result and the batch moves on.
+#[tauri::command]
+pub async fn generate_contact_sheets(
+    window: Window,
+    dir_path: String,
+    options: Option<ContactSheetOptions>,
+) -> Result<ContactSheetBatch, AppError> {
+    let options = options.unwrap_or_default();
+    let videos: Vec<String> = list_directory(dir_path)?
+        .into_iter()
+        .filter(|entry| entry.file_type == "video")
+        .map(|entry| entry.path)
+        .collect();
+    let total = u32::try_from(videos.len()).unwrap_or(u32::MAX);
+
+    let mut batch = ContactSheetBatch::default();
+    for (index, video_path) in videos.into_iter().enumerate() {
+        let progress = ContactSheetProgress {
+            index: u32::try_from(index).unwrap_or(u32::MAX),
+            total,
+            video_path: video_path.clone(),
+        };
+        let _ = window.emit(CONTACT_SHEET_PROGRESS_EVENT, progress);
+
+        let result = match get_video_metadata(video_path.clone()).await {
+            Ok(metadata) => contact_sheet::generate_contact_sheet(&metadata, &options).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(sheet) => batch.sheets.push(sheet),
+            Err(error) => batch.failures.push(ContactSheetFailure { video_path, error }),
         }
     }
+    Ok(batch)
+}
 
-    Err("Could not determine video duration".to_string())
+/// Exports a short range of a video as a looping GIF or WebP, optionally shrunk to fit
+/// a maximum file size.
+#[tauri::command]
+pub async fn export_animation(request: AnimationRequest) -> Result<AnimationExport, AppError> {
+    animation::export_animation(&request).await
 }
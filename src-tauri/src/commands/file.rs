@@ -2,6 +2,8 @@ use serde::Serialize;
 use std::fs;
 use std::path::Path;
 
+use crate::error::AppError;
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DirectoryEntry {
@@ -31,13 +33,14 @@ fn classify_extension(ext: &str) -> Option<&'static str> {
 
 /// Lists all video and image files in a directory.
 #[tauri::command]
-pub fn list_directory(dir_path: String) -> Result<Vec<DirectoryEntry>, String> {
+pub fn list_directory(dir_path: String) -> Result<Vec<DirectoryEntry>, AppError> {
     let path = Path::new(&dir_path);
     if !path.is_dir() {
-        return Err(format!("Not a directory: {dir_path}"));
+        return Err(AppError::NotFound { path: dir_path });
     }
 
-    let entries = fs::read_dir(path).map_err(|e| format!("Failed to read directory: {e}"))?;
+    let entries =
+        fs::read_dir(path).map_err(|e| AppError::io("Failed to read directory", path, &e))?;
 
     let mut files: Vec<DirectoryEntry> = Vec::new();
 
@@ -78,12 +81,17 @@ pub fn list_directory(dir_path: String) -> Result<Vec<DirectoryEntry>, String> {
 
 /// Permanently deletes a file from the filesystem.
 #[tauri::command]
-pub fn delete_file(file_path: String) -> Result<(), String> {
-    fs::remove_file(&file_path).map_err(|e| format!("Failed to delete file: {e}"))
+pub fn delete_file(file_path: String) -> Result<(), AppError> {
+    fs::remove_file(&file_path)
+        .map_err(|e| AppError::io("Failed to delete", Path::new(&file_path), &e))
 }
 
 /// Moves a file to the system recycle bin / trash.
 #[tauri::command]
-pub fn move_to_trash(file_path: String) -> Result<(), String> {
-    trash::delete(&file_path).map_err(|e| format!("Failed to move to trash: {e}"))
+pub fn move_to_trash(file_path: String) -> Result<(), AppError> {
+    trash::delete(&file_path).map_err(|e| AppError::Io {
+        context: "Failed to move to trash".to_string(),
+        path: Some(file_path.clone()),
+        message: e.to_string(),
+    })
 }
@@ -1,20 +1,22 @@
 use std::path::Path;
 
+use crate::error::AppError;
 use crate::models::video::VideoMetadata;
-use crate::utils::ffmpeg_wrapper::run_ffmpeg;
+use crate::utils::ffmpeg_wrapper::{parse_resolution, run_ffmpeg};
 
 /// Retrieves metadata from a video file using the filesystem and `FFmpeg`.
 ///
 /// Returns duration, file size, resolution, and codec information.
 #[tauri::command]
 #[allow(clippy::cast_possible_truncation)]
-pub async fn get_video_metadata(file_path: String) -> Result<VideoMetadata, String> {
+pub async fn get_video_metadata(file_path: String) -> Result<VideoMetadata, AppError> {
     let path = Path::new(&file_path);
     if !path.exists() {
-        return Err(format!("File not found: {file_path}"));
+        return Err(AppError::NotFound { path: file_path });
     }
 
-    let fs_meta = std::fs::metadata(path).map_err(|e| format!("Failed to read file metadata: {e}"))?;
+    let fs_meta = std::fs::metadata(path)
+        .map_err(|e| AppError::io("Failed to read file metadata of", path, &e))?;
     let file_size = fs_meta.len();
 
     let file_name = path
@@ -38,6 +40,7 @@ pub async fn get_video_metadata(file_path: String) -> Result<VideoMetadata, Stri
     let video_codec = parse_video_codec(&stderr);
     let audio_codec = parse_audio_codec(&stderr);
     let bitrate = parse_bitrate(&stderr);
+    let frame_rate = parse_frame_rate(&stderr);
 
     Ok(VideoMetadata {
         file_path,
@@ -50,6 +53,7 @@ pub async fn get_video_metadata(file_path: String) -> Result<VideoMetadata, Stri
         audio_codec,
         bitrate,
         format,
+        frame_rate,
     })
 }
 
@@ -85,26 +89,6 @@ fn parse_time_to_secs(time_str: &str) -> f64 {
     }
 }
 
-/// Parses video resolution from `FFmpeg` output like `1920x1080`.
-fn parse_resolution(output: &str) -> (u32, u32) {
-    for line in output.lines() {
-        if line.contains("Video:") {
-            // Look for patterns like "1920x1080" or "1280x720"
-            for part in line.split([' ', ',']) {
-                let part = part.trim();
-                if let Some((w, h)) = part.split_once('x') {
-                    if let (Ok(width), Ok(height)) = (w.parse::<u32>(), h.parse::<u32>()) {
-                        if width >= 16 && height >= 16 && width <= 15360 && height <= 8640 {
-                            return (width, height);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    (0, 0)
-}
-
 /// Parses video codec from `FFmpeg` output like `Stream #0:0: Video: h264`
 fn parse_video_codec(output: &str) -> String {
     for line in output.lines() {
@@ -150,3 +134,44 @@ fn parse_bitrate(output: &str) -> u64 {
     }
     0
 }
+
+/// Parses the video frame rate from `FFmpeg` output like `29.97 fps` or, failing that, `25 tbr`.
+fn parse_frame_rate(output: &str) -> f64 {
+    for unit in ["fps", "tbr"] {
+        for line in output.lines().filter(|l| l.contains("Video:")) {
+            for part in line.split(',') {
+                if let Some(rate) = part.trim().strip_suffix(unit).and_then(parse_rate) {
+                    if rate > 0.0 {
+                        return rate;
+                    }
+                }
+            }
+        }
+    }
+    0.0
+}
+
+/// Parses a rate as `FFmpeg` prints it, where `1k` stands for 1000.
+fn parse_rate(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let (digits, scale) = value
+        .strip_suffix('k')
+        .map_or((value, 1.0), |thousands| (thousands, 1000.0));
+    digits.parse::<f64>().ok().map(|rate| rate * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frame_rate() {
+        let video = |rates: &str| {
+            parse_frame_rate(&format!("Stream #0:0: Video: h264, 1920x1080, {rates}"))
+        };
+        assert!((video("29.97 fps, 29.97 tbr, 90k tbn") - 29.97).abs() < 1e-9);
+        assert!((video("25 tbr, 1k tbn") - 25.0).abs() < 1e-9);
+        assert!((video("1k tbr, 1k tbn") - 1000.0).abs() < 1e-9);
+        assert!(parse_frame_rate("  Stream #0:1: Audio: aac, 48000 Hz").abs() < f64::EPSILON);
+    }
+}
@@ -1,3 +1,9 @@
+pub mod cache;
+pub mod capture;
+pub mod cut_list;
 pub mod ffmpeg;
 pub mod file;
 pub mod metadata;
+pub mod partition;
+pub mod presets;
+pub mod projects;
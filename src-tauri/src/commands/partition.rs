@@ -0,0 +1,47 @@
+use crate::error::AppError;
+use crate::models::analysis::{AnalysisOptions, ExclusionSuggestion};
+use crate::models::partition::{
+    NormalizedExclusions, PartitionPoint, PartitionTarget, TimeInterval,
+};
+use crate::models::video::VideoMetadata;
+use crate::services::{analysis, exclusions, splitter};
+
+/// Calculates where a split would cut the video, with times on the original timeline.
+///
+/// Runs the same calculator as the split itself, so the timeline preview always matches.
+/// `target_duration_secs` replaces the size target when set, as in a split request.
+#[tauri::command]
+pub fn calculate_partition_points(
+    metadata: VideoMetadata,
+    target_size_bytes: u64,
+    target_duration_secs: Option<f64>,
+    exclusions: Vec<TimeInterval>,
+) -> Result<Vec<PartitionPoint>, AppError> {
+    let target = target_duration_secs.map_or(
+        PartitionTarget::Size(target_size_bytes),
+        PartitionTarget::Duration,
+    );
+    splitter::preview_partition_points(&metadata, target, &exclusions)
+}
+
+/// Clamps and merges exclusions, describing every adjustment so the UI can show it.
+///
+/// Fails if an interval ends before it starts.
+#[tauri::command]
+pub fn normalize_exclusions(
+    exclusions: Vec<TimeInterval>,
+    duration_secs: f64,
+) -> Result<NormalizedExclusions, AppError> {
+    exclusions::normalize_exclusions(&exclusions, duration_secs)
+}
+
+/// Scans a video for black, frozen and silent sections and proposes them as exclusions.
+///
+/// Decodes the whole file, so it takes roughly as long as a fast transcode.
+#[tauri::command]
+pub async fn suggest_exclusions(
+    file_path: String,
+    options: Option<AnalysisOptions>,
+) -> Result<Vec<ExclusionSuggestion>, AppError> {
+    analysis::suggest_exclusions(&file_path, &options.unwrap_or_default()).await
+}
@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::models::preset::SplitPreset;
+use crate::services::presets;
+
+/// Resolves the app config directory where presets and settings are stored.
+pub fn config_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app.path_resolver()
+        .app_config_dir()
+        .ok_or_else(|| AppError::internal("Could not resolve the app config directory"))
+}
+
+/// Lists saved presets, or the built-in ones if none have been saved.
+#[tauri::command]
+pub fn list_presets(app: AppHandle) -> Result<Vec<SplitPreset>, AppError> {
+    presets::load_presets(&config_dir(&app)?)
+}
+
+/// Saves a new preset and returns the updated list.
+#[tauri::command]
+pub fn create_preset(app: AppHandle, preset: SplitPreset) -> Result<Vec<SplitPreset>, AppError> {
+    presets::create_preset(&config_dir(&app)?, preset)
+}
+
+/// Replaces the preset called `name` and returns the updated list.
+#[tauri::command]
+pub fn update_preset(
+    app: AppHandle,
+    name: String,
+    preset: SplitPreset,
+) -> Result<Vec<SplitPreset>, AppError> {
+    presets::update_preset(&config_dir(&app)?, &name, preset)
+}
+
+/// Deletes the preset called `name` and returns the updated list.
+#[tauri::command]
+pub fn delete_preset(app: AppHandle, name: String) -> Result<Vec<SplitPreset>, AppError> {
+    presets::delete_preset(&config_dir(&app)?, &name)
+}
+
+/// Imports presets from a JSON file and returns the updated list.
+#[tauri::command]
+pub fn import_presets(app: AppHandle, file_path: String) -> Result<Vec<SplitPreset>, AppError> {
+    presets::import_presets(&config_dir(&app)?, Path::new(&file_path))
+}
+
+/// Exports the named presets, or all of them if `names` is empty, to a JSON file.
+#[tauri::command]
+pub fn export_presets(
+    app: AppHandle,
+    file_path: String,
+    names: Vec<String>,
+) -> Result<(), AppError> {
+    presets::export_presets(&config_dir(&app)?, Path::new(&file_path), &names)
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::models::project::ProjectState;
+use crate::services::projects;
+
+/// Resolves the app data directory where project state is stored.
+fn data_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app.path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| AppError::internal("Could not resolve the app data directory"))
+}
+
+/// Loads the saved editing state of a video, if it is still valid for the file on disk.
+#[tauri::command]
+pub fn load_project_state(
+    app: AppHandle,
+    file_path: String,
+) -> Result<Option<ProjectState>, AppError> {
+    projects::load_project_state(&data_dir(&app)?, &file_path)
+}
+
+/// Saves the editing state of a video so reopening it restores the session.
+#[tauri::command]
+pub fn save_project_state(
+    app: AppHandle,
+    file_path: String,
+    state: ProjectState,
+) -> Result<(), AppError> {
+    projects::save_project_state(&data_dir(&app)?, &file_path, state)
+}
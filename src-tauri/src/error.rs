@@ -0,0 +1,359 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::{Serialize, Serializer};
+
+/// Lines of `FFmpeg` stderr kept in an error; the end of the log names the failure.
+const STDERR_EXCERPT_LINES: usize = 12;
+
+/// Upper bound on the stderr excerpt in bytes.
+const STDERR_EXCERPT_BYTES: usize = 2000;
+
+/// `FFmpeg` log lines that mean a stream or codec can't be read or written.
+const UNSUPPORTED_MARKERS: [&str; 7] = [
+    "Invalid data found when processing input",
+    "Unknown decoder",
+    "Decoder not found",
+    "Could not find codec parameters",
+    "Could not find tag for codec",
+    "not currently supported in container",
+    "Unsupported codec",
+];
+
+/// Machine-readable category of an [`AppError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    SidecarMissing,
+    NotFound,
+    PermissionDenied,
+    DiskFull,
+    UnsupportedMedia,
+    FfmpegFailed,
+    Io,
+    InvalidInput,
+    Cancelled,
+    Internal,
+}
+
+/// Error returned by commands, services and the `FFmpeg` wrapper.
+///
+/// Serializes to `{ code, message, path, exitCode, stderr, retryable }` so the
+/// frontend can tell failures apart and decide whether to offer a retry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    /// The bundled `FFmpeg` sidecar could not be found or started
+    SidecarMissing { message: String },
+    /// A file or directory does not exist
+    NotFound { path: String },
+    /// The OS refused access to a path
+    PermissionDenied { path: String },
+    /// The volume holding `path` has no room, with byte counts when known in advance
+    DiskFull {
+        path: String,
+        required_bytes: Option<u64>,
+        available_bytes: Option<u64>,
+    },
+    /// `FFmpeg` can't read a stream or codec in the input, or write it to the output
+    UnsupportedMedia {
+        path: Option<String>,
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+    /// `FFmpeg` failed for any other reason
+    Ffmpeg {
+        context: String,
+        path: Option<String>,
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+    /// Any other I/O failure
+    Io {
+        context: String,
+        path: Option<String>,
+        message: String,
+    },
+    /// The request or a file's contents are invalid
+    InvalidInput { message: String },
+    /// The user cancelled the operation
+    Cancelled,
+    /// A background task panicked or data could not be serialized
+    Internal { message: String },
+}
+
+impl AppError {
+    /// Wraps an I/O error on `path`, recognising missing files, denied access and full disks.
+    pub fn io(context: &str, path: &Path, error: &io::Error) -> Self {
+        let path = path.display().to_string();
+        match error.kind() {
+            io::ErrorKind::NotFound => Self::NotFound { path },
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied { path },
+            io::ErrorKind::StorageFull => Self::DiskFull {
+                path,
+                required_bytes: None,
+                available_bytes: None,
+            },
+            _ => Self::Io {
+                context: context.to_string(),
+                path: Some(path),
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// Classifies a failed `FFmpeg` run by what its log says went wrong.
+    ///
+    /// `path` is the file the run was working on; only a trimmed excerpt of `stderr` is kept.
+    pub fn ffmpeg(context: &str, path: Option<&str>, exit_code: Option<i32>, stderr: &str) -> Self {
+        let path = path.map(str::to_string);
+        if stderr.contains("No space left on device") {
+            Self::DiskFull {
+                path: path.unwrap_or_default(),
+                required_bytes: None,
+                available_bytes: None,
+            }
+        } else if stderr.contains("Permission denied") {
+            Self::PermissionDenied {
+                path: path.unwrap_or_default(),
+            }
+        } else if UNSUPPORTED_MARKERS.iter().any(|m| stderr.contains(m)) {
+            Self::UnsupportedMedia {
+                path,
+                exit_code,
+                stderr: stderr_excerpt(stderr),
+            }
+        } else {
+            Self::Ffmpeg {
+                context: context.to_string(),
+                path,
+                exit_code,
+                stderr: stderr_excerpt(stderr),
+            }
+        }
+    }
+
+    /// An invalid request or file content.
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::InvalidInput {
+            message: message.into(),
+        }
+    }
+
+    /// An unexpected failure that isn't the user's to fix.
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal {
+            message: message.into(),
+        }
+    }
+
+    pub const fn code(&self) -> ErrorCode {
+        match self {
+            Self::SidecarMissing { .. } => ErrorCode::SidecarMissing,
+            Self::NotFound { .. } => ErrorCode::NotFound,
+            Self::PermissionDenied { .. } => ErrorCode::PermissionDenied,
+            Self::DiskFull { .. } => ErrorCode::DiskFull,
+            Self::UnsupportedMedia { .. } => ErrorCode::UnsupportedMedia,
+            Self::Ffmpeg { .. } => ErrorCode::FfmpegFailed,
+            Self::Io { .. } => ErrorCode::Io,
+            Self::InvalidInput { .. } => ErrorCode::InvalidInput,
+            Self::Cancelled => ErrorCode::Cancelled,
+            Self::Internal { .. } => ErrorCode::Internal,
+        }
+    }
+
+    /// The file or directory the error is about, if any.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Self::NotFound { path }
+            | Self::PermissionDenied { path }
+            | Self::DiskFull { path, .. } => {
+                Some(path).filter(|p| !p.is_empty()).map(String::as_str)
+            }
+            Self::UnsupportedMedia { path, .. }
+            | Self::Ffmpeg { path, .. }
+            | Self::Io { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub const fn exit_code(&self) -> Option<i32> {
+        match self {
+            Self::UnsupportedMedia { exit_code, .. } | Self::Ffmpeg { exit_code, .. } => *exit_code,
+            _ => None,
+        }
+    }
+
+    /// The end of `FFmpeg`'s log for failed runs.
+    pub fn stderr(&self) -> Option<&str> {
+        match self {
+            Self::UnsupportedMedia { stderr, .. } | Self::Ffmpeg { stderr, .. } => Some(stderr),
+            _ => None,
+        }
+    }
+
+    /// Whether running the same operation again could succeed, possibly after the user
+    /// frees space or closes another program.
+    pub const fn retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::DiskFull { .. }
+                | Self::PermissionDenied { .. }
+                | Self::Ffmpeg { .. }
+                | Self::Io { .. }
+                | Self::Cancelled
+                | Self::Internal { .. }
+        )
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SidecarMissing { message } => write!(f, "FFmpeg is not available: {message}"),
+            Self::NotFound { path } => write!(f, "Not found: {path}"),
+            Self::PermissionDenied { path } => write!(f, "Permission denied: {path}"),
+            Self::DiskFull {
+                path,
+                required_bytes: Some(required),
+                available_bytes: Some(available),
+            } => write!(
+                f,
+                "Not enough disk space in {path}: {required} bytes required, {available} bytes available"
+            ),
+            Self::DiskFull { path, .. } => write!(f, "Disk full while writing {path}"),
+            Self::UnsupportedMedia { path: Some(path), .. } => {
+                write!(f, "Unsupported or unreadable media: {path}")
+            }
+            Self::UnsupportedMedia { path: None, .. } => write!(f, "Unsupported or unreadable media"),
+            Self::Ffmpeg {
+                context, exit_code, ..
+            } => match exit_code {
+                Some(code) => write!(f, "{context} (FFmpeg exited with code {code})"),
+                None => write!(f, "{context} (FFmpeg terminated unexpectedly)"),
+            },
+            Self::Io {
+                context,
+                path: Some(path),
+                message,
+            } => write!(f, "{context} {path}: {message}"),
+            Self::Io {
+                context, message, ..
+            } => write!(f, "{context}: {message}"),
+            Self::InvalidInput { message } | Self::Internal { message } => f.write_str(message),
+            Self::Cancelled => f.write_str("Operation cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Wire format of an [`AppError`].
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorPayload<'a> {
+    code: ErrorCode,
+    message: String,
+    path: Option<&'a str>,
+    exit_code: Option<i32>,
+    stderr: Option<&'a str>,
+    retryable: bool,
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ErrorPayload {
+            code: self.code(),
+            message: self.to_string(),
+            path: self.path(),
+            exit_code: self.exit_code(),
+            stderr: self.stderr(),
+            retryable: self.retryable(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Keeps the last lines of an `FFmpeg` log, where the cause of a failure is reported,
+/// skipping blank lines and progress updates.
+fn stderr_excerpt(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty() && !line.starts_with("frame="))
+        .collect();
+    let excerpt = lines[lines.len().saturating_sub(STDERR_EXCERPT_LINES)..].join("\n");
+
+    if excerpt.len() <= STDERR_EXCERPT_BYTES {
+        return excerpt;
+    }
+    let mut start = excerpt.len() - STDERR_EXCERPT_BYTES;
+    while !excerpt.is_char_boundary(start) {
+        start += 1;
+    }
+    excerpt[start..].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffmpeg_failures_are_classified() {
+        let unsupported = AppError::ffmpeg(
+            "Split failed",
+            Some("/in.mkv"),
+            Some(1),
+            "[matroska @ 0x1] Could not find codec parameters for stream 2\n",
+        );
+        assert_eq!(unsupported.code(), ErrorCode::UnsupportedMedia);
+        assert_eq!(unsupported.path(), Some("/in.mkv"));
+        assert!(!unsupported.retryable());
+
+        let full = AppError::ffmpeg(
+            "Split failed",
+            Some("/out/a.mp4"),
+            Some(1),
+            "av_interleaved_write_frame(): No space left on device\n",
+        );
+        assert_eq!(full.code(), ErrorCode::DiskFull);
+
+        let other = AppError::ffmpeg("Split failed", None, Some(69), "Conversion failed!\n");
+        assert_eq!(other.code(), ErrorCode::FfmpegFailed);
+        assert_eq!(
+            other.to_string(),
+            "Split failed (FFmpeg exited with code 69)"
+        );
+    }
+
+    #[test]
+    fn test_stderr_excerpt_keeps_the_end() {
+        let mut log = String::from("ffmpeg version 6.1\n\n");
+        log.extend((0..30).map(|i| format!("line {i}\n")));
+        log.push_str("frame=  100 fps=0.0 q=-1.0 size=1kB\n");
+
+        let excerpt = stderr_excerpt(&log);
+        assert_eq!(excerpt.lines().count(), STDERR_EXCERPT_LINES);
+        assert!(excerpt.starts_with("line 18"));
+        assert!(excerpt.ends_with("line 29"));
+
+        let oversized = "x".repeat(STDERR_EXCERPT_BYTES * 2);
+        assert_eq!(stderr_excerpt(&oversized).len(), STDERR_EXCERPT_BYTES);
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        let error = AppError::io(
+            "Failed to open",
+            Path::new("/missing.mp4"),
+            &io::Error::from(io::ErrorKind::NotFound),
+        );
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(json["code"], "not_found");
+        assert_eq!(json["message"], "Not found: /missing.mp4");
+        assert_eq!(json["path"], "/missing.mp4");
+        assert!(json["exitCode"].is_null());
+        assert_eq!(json["retryable"], false);
+    }
+}
@@ -1,21 +1,61 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod cli;
 mod commands;
+mod error;
 mod models;
 mod services;
 mod utils;
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(exit_code) = cli::run(&args) {
+        std::process::exit(exit_code);
+    }
+
     tauri::Builder::default()
+        .manage(commands::ffmpeg::SplitCancellation::default())
+        .manage(commands::ffmpeg::AudioExportCancellation::default())
+        .manage(commands::ffmpeg::PreviewTranscode::default())
+        .manage(services::preview::PreviewFrames::default())
         .invoke_handler(tauri::generate_handler![
             commands::metadata::get_video_metadata,
             commands::ffmpeg::execute_split,
+            commands::ffmpeg::check_split_space,
+            commands::ffmpeg::plan_split,
+            commands::ffmpeg::cancel_split,
+            commands::ffmpeg::execute_byte_split,
+            commands::ffmpeg::execute_audio_export,
+            commands::ffmpeg::cancel_audio_export,
+            commands::ffmpeg::reassemble,
             commands::ffmpeg::generate_thumbnails,
+            commands::ffmpeg::preview_frame,
+            commands::ffmpeg::prepare_preview,
             commands::ffmpeg::capture_frame,
+            commands::ffmpeg::generate_contact_sheet,
+            commands::ffmpeg::generate_contact_sheets,
+            commands::ffmpeg::export_animation,
+            commands::cache::get_thumbnail_cache_info,
+            commands::cache::clear_thumbnail_cache,
+            commands::capture::get_capture_settings,
+            commands::capture::save_capture_settings,
             commands::file::list_directory,
             commands::file::delete_file,
             commands::file::move_to_trash,
+            commands::presets::list_presets,
+            commands::presets::create_preset,
+            commands::presets::update_preset,
+            commands::presets::delete_preset,
+            commands::presets::import_presets,
+            commands::presets::export_presets,
+            commands::projects::load_project_state,
+            commands::projects::save_project_state,
+            commands::cut_list::import_cut_list,
+            commands::cut_list::export_cut_list,
+            commands::partition::calculate_partition_points,
+            commands::partition::normalize_exclusions,
+            commands::partition::suggest_exclusions,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::partition::TimeInterval;
+
+/// What an `FFmpeg` detection filter found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DetectionKind {
+    /// Black frames (`blackdetect`)
+    Black,
+    /// Frozen picture (`freezedetect`)
+    Freeze,
+    /// Silent audio (`silencedetect`)
+    Silence,
+}
+
+/// Thresholds for exclusion analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AnalysisOptions {
+    /// Minimum length of a black section in seconds
+    pub black_min_secs: f64,
+    /// Ratio of dark pixels above which a frame counts as black (0-1)
+    pub black_picture_threshold: f64,
+    /// Minimum length of a frozen section in seconds
+    pub freeze_min_secs: f64,
+    /// Noise tolerance for frozen frame comparison in dB
+    pub freeze_noise_db: f64,
+    /// Minimum length of a silent section in seconds
+    pub silence_min_secs: f64,
+    /// Volume below which audio counts as silent, in dB
+    pub silence_noise_db: f64,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            black_min_secs: 2.0,
+            black_picture_threshold: 0.98,
+            freeze_min_secs: 5.0,
+            freeze_noise_db: -60.0,
+            silence_min_secs: 5.0,
+            silence_noise_db: -50.0,
+        }
+    }
+}
+
+/// A proposed exclusion the user can accept or reject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExclusionSuggestion {
+    /// The proposed interval
+    pub interval: TimeInterval,
+    /// How likely the interval is unwanted content (0-1)
+    pub confidence: f64,
+    /// Human-readable explanation, e.g. "Black screen for 12.5 s at the start"
+    pub reason: String,
+    /// Detectors that flagged the interval
+    pub kinds: Vec<DetectionKind>,
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// Format of an exported animation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationFormat {
+    /// GIF with a palette generated from the clip
+    #[default]
+    Gif,
+    /// Lossy animated WebP
+    Webp,
+}
+
+impl AnimationFormat {
+    /// File extension of the format.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// A short range of a video to export as a looping animation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationRequest {
+    /// Source video
+    pub video_path: String,
+    /// Start of the range in seconds
+    pub start_secs: f64,
+    /// End of the range in seconds
+    pub end_secs: f64,
+    /// File to write
+    pub output_path: String,
+    /// Output format
+    #[serde(default)]
+    pub format: AnimationFormat,
+    /// Frames per second
+    #[serde(default = "default_fps")]
+    pub fps: u32,
+    /// Width in pixels; the height keeps the aspect ratio
+    #[serde(default = "default_width")]
+    pub width: u32,
+    /// Largest acceptable file size; frame rate, width and quality are lowered until the
+    /// animation fits
+    #[serde(default)]
+    pub max_size_bytes: Option<u64>,
+}
+
+const fn default_fps() -> u32 {
+    12
+}
+
+const fn default_width() -> u32 {
+    480
+}
+
+/// An animation written by `export_animation`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationExport {
+    /// Path of the animation
+    pub path: String,
+    /// Size of the file in bytes
+    pub size_bytes: u64,
+    /// Frame rate actually used
+    pub fps: u32,
+    /// Width actually used
+    pub width: u32,
+    /// Number of encodes needed to approach the size target
+    pub attempts: u32,
+    /// Whether the file fits in `max_size_bytes`; always true without a target
+    pub within_target: bool,
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::partition::{PartitionTarget, TimeInterval};
+
+/// Container and codec of exported audio.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// AAC in an MP4 container; the only format holding several tracks
+    #[default]
+    M4a,
+    /// MPEG layer 3
+    Mp3,
+    /// Lossless FLAC
+    Flac,
+    /// Uncompressed 16-bit PCM
+    Wav,
+}
+
+impl AudioFormat {
+    /// File extension of the format.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::M4a => "m4a",
+            Self::Mp3 => "mp3",
+            Self::Flac => "flac",
+            Self::Wav => "wav",
+        }
+    }
+
+    /// Whether audio in `codec` can be stream-copied into this format unchanged.
+    pub fn can_copy(self, codec: &str) -> bool {
+        match self {
+            Self::M4a => matches!(codec, "aac" | "alac"),
+            Self::Mp3 => codec == "mp3",
+            Self::Flac => codec == "flac",
+            Self::Wav => codec.starts_with("pcm_"),
+        }
+    }
+
+    /// Whether the format is compressed lossily, so a bitrate applies when encoding.
+    pub const fn is_lossy(self) -> bool {
+        matches!(self, Self::M4a | Self::Mp3)
+    }
+}
+
+/// How the audio is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioMode {
+    /// Re-encode to the format's codec
+    #[default]
+    Encode,
+    /// Stream-copy the source audio; only possible when its codec fits the format
+    Copy,
+}
+
+/// Request to export the audio of a file, whole or in parts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioExportRequest {
+    /// Path to the source file
+    pub input_path: String,
+    /// Directory where the audio files will be saved
+    pub output_dir: String,
+    /// Output format
+    #[serde(default)]
+    pub format: AudioFormat,
+    /// Whether to copy or re-encode the audio
+    #[serde(default)]
+    pub mode: AudioMode,
+    /// Bitrate of lossy encodes in kbit/s; 192 when absent
+    #[serde(default)]
+    pub bitrate_kbps: Option<u32>,
+    /// Audio track to export (0-based); all tracks for m4a, the first for other formats
+    /// when absent
+    #[serde(default)]
+    pub track: Option<u32>,
+    /// Time intervals to leave out, as for a split
+    #[serde(default)]
+    pub exclusions: Vec<TimeInterval>,
+    /// Target partition size of the matching video split. Parts then cover the same
+    /// time ranges as the split's partitions; one file is written when absent.
+    #[serde(default)]
+    pub target_size_bytes: Option<u64>,
+    /// Partition length of the matching video split in seconds; replaces
+    /// `target_size_bytes` when set
+    #[serde(default)]
+    pub target_duration_secs: Option<f64>,
+    /// Naming template of the matching video split, with `{stem}`, `{n}` and `{ext}`;
+    /// `{stem}_part{n}.{ext}` when absent. Only applies to parts.
+    #[serde(default)]
+    pub naming_template: Option<String>,
+    /// Directory for intermediate files; defaults to the system temp directory
+    #[serde(default)]
+    pub scratch_dir: Option<String>,
+}
+
+impl AudioExportRequest {
+    /// What the parts are sized by, or `None` for a single file.
+    pub const fn target(&self) -> Option<PartitionTarget> {
+        match (self.target_duration_secs, self.target_size_bytes) {
+            (Some(secs), _) => Some(PartitionTarget::Duration(secs)),
+            (None, Some(bytes)) => Some(PartitionTarget::Size(bytes)),
+            (None, None) => None,
+        }
+    }
+}
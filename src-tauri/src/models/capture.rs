@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the capture settings file format.
+pub const CAPTURE_SETTINGS_VERSION: u32 = 1;
+
+/// Image format of captured frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureFormat {
+    /// High-quality JPEG
+    #[default]
+    Jpeg,
+    /// Lossless PNG
+    Png,
+    /// WebP, lossy unless [`CaptureSettings::lossless`] is set
+    Webp,
+}
+
+impl CaptureFormat {
+    /// File extension of the format.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// How screenshots are written, saved in the app config directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureSettings {
+    /// Format version, currently [`CAPTURE_SETTINGS_VERSION`]
+    #[serde(default = "default_version")]
+    pub version: u32,
+    /// Image format
+    #[serde(default)]
+    pub format: CaptureFormat,
+    /// Encode WebP losslessly; PNG is always lossless and JPEG never is
+    #[serde(default)]
+    pub lossless: bool,
+    /// Output width in pixels; the aspect ratio is kept when only one side is given
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Output height in pixels
+    #[serde(default)]
+    pub height: Option<u32>,
+    /// Screenshots folder; next to the source video when absent
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    /// Write the source file and timecode into the image's metadata
+    #[serde(default = "default_embed_metadata")]
+    pub embed_metadata: bool,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            version: CAPTURE_SETTINGS_VERSION,
+            format: CaptureFormat::default(),
+            lossless: false,
+            width: None,
+            height: None,
+            output_dir: None,
+            embed_metadata: true,
+        }
+    }
+}
+
+const fn default_version() -> u32 {
+    CAPTURE_SETTINGS_VERSION
+}
+
+const fn default_embed_metadata() -> bool {
+    true
+}
+
+/// Several frames captured in one go, starting at the requested timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum Burst {
+    /// `count` frames, `interval_secs` apart
+    #[serde(rename_all = "camelCase")]
+    Interval { count: u32, interval_secs: f64 },
+    /// Every frame of the next `duration_secs`
+    #[serde(rename_all = "camelCase")]
+    Range { duration_secs: f64 },
+}
+
+/// One image written by `capture_frame`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedFrame {
+    /// Path of the image
+    pub path: String,
+    /// Presentation time of the captured frame in seconds
+    pub timestamp_secs: f64,
+}
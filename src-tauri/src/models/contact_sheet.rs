@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::models::capture::CaptureFormat;
+
+/// Layout and destination of a contact sheet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSheetOptions {
+    /// Frames per row
+    #[serde(default = "default_columns")]
+    pub columns: u32,
+    /// Number of rows
+    #[serde(default = "default_rows")]
+    pub rows: u32,
+    /// Width of the whole sheet in pixels
+    #[serde(default = "default_width")]
+    pub width: u32,
+    /// Image format of the sheet
+    #[serde(default)]
+    pub format: CaptureFormat,
+    /// Directory to write to; next to each video when absent
+    #[serde(default)]
+    pub output_dir: Option<String>,
+}
+
+impl Default for ContactSheetOptions {
+    fn default() -> Self {
+        Self {
+            columns: default_columns(),
+            rows: default_rows(),
+            width: default_width(),
+            format: CaptureFormat::default(),
+            output_dir: None,
+        }
+    }
+}
+
+const fn default_columns() -> u32 {
+    4
+}
+
+const fn default_rows() -> u32 {
+    6
+}
+
+const fn default_width() -> u32 {
+    1600
+}
+
+/// A contact sheet written for a video.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSheet {
+    /// Video the sheet summarises
+    pub video_path: String,
+    /// Path of the sheet image
+    pub path: String,
+}
+
+/// A video of a batch whose contact sheet could not be written.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSheetFailure {
+    /// Video that failed
+    pub video_path: String,
+    /// Why it failed
+    pub error: AppError,
+}
+
+/// Outcome of generating contact sheets for every video in a directory.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSheetBatch {
+    /// Sheets written, in directory order
+    pub sheets: Vec<ContactSheet>,
+    /// Videos skipped because of an error; the batch continues past them
+    pub failures: Vec<ContactSheetFailure>,
+}
+
+/// Emitted before each video of a contact sheet batch is processed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSheetProgress {
+    /// Position of the video in the batch, starting at 0
+    pub index: u32,
+    /// Number of videos in the batch
+    pub total: u32,
+    /// Video being processed
+    pub video_path: String,
+}
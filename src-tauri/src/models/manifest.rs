@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::partition::TimeInterval;
+use crate::models::preset::EncodeProfile;
+
+/// Current version of the split manifest format.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// A hash algorithm that can be computed over split sources and partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// SHA-256, compatible with `sha256sum`
+    Sha256,
+    /// BLAKE3
+    Blake3,
+}
+
+/// How the partitions listed in a manifest were produced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestKind {
+    /// Media partitions cut on the timeline with `FFmpeg` stream copy
+    #[default]
+    Video,
+    /// Raw fixed-size byte chunks
+    Bytes,
+}
+
+/// Hex-encoded digests of a single file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHashes {
+    /// SHA-256 digest, if computed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// BLAKE3 digest, if computed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blake3: Option<String>,
+}
+
+impl FileHashes {
+    /// Lists the algorithms that have a digest recorded.
+    pub fn algorithms(&self) -> Vec<HashAlgorithm> {
+        let mut algorithms = Vec::new();
+        if self.sha256.is_some() {
+            algorithms.push(HashAlgorithm::Sha256);
+        }
+        if self.blake3.is_some() {
+            algorithms.push(HashAlgorithm::Blake3);
+        }
+        algorithms
+    }
+}
+
+/// The file a set of partitions was produced from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestSource {
+    /// File name of the source, without path
+    pub file_name: String,
+    /// Source size in bytes
+    pub size_bytes: u64,
+    /// Source duration in seconds
+    pub duration_secs: f64,
+    /// Source streams in file order, formatted as `kind:codec` (e.g. `video:h264`)
+    #[serde(default)]
+    pub streams: Vec<String>,
+    /// Digests of the source file
+    pub hashes: FileHashes,
+}
+
+/// A single partition listed in a manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestPartition {
+    /// Partition index (0-based)
+    pub index: u32,
+    /// File name of the partition, relative to the manifest
+    pub file_name: String,
+    /// Partition size in bytes
+    pub size_bytes: u64,
+    /// Ranges of the source timeline contained in this partition, in order
+    pub segments: Vec<TimeInterval>,
+    /// Digests of the partition file
+    pub hashes: FileHashes,
+}
+
+/// Describes the result of a split so partitions can be verified and reassembled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitManifest {
+    /// Manifest format version
+    pub version: u32,
+    /// How the partitions were produced
+    #[serde(default)]
+    pub kind: ManifestKind,
+    /// Encoding applied to video partitions; stream copy in manifests that predate it
+    #[serde(default)]
+    pub encode_profile: EncodeProfile,
+    /// The source file
+    pub source: ManifestSource,
+    /// Produced partitions, ordered by index
+    pub partitions: Vec<ManifestPartition>,
+}
+
+/// Outcome of reassembling partitions listed in a manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReassemblyReport {
+    /// Path of the reassembled file
+    pub output_path: String,
+    /// Number of partitions whose digests were checked
+    pub verified_partitions: u32,
+    /// Duration the output should have, from the manifest segments
+    pub expected_duration_secs: f64,
+    /// Duration of the reassembled output
+    pub actual_duration_secs: f64,
+    /// Whether the durations agree within tolerance
+    pub duration_matches: bool,
+    /// Stream layout recorded for the source
+    pub expected_streams: Vec<String>,
+    /// Stream layout of the reassembled output
+    pub actual_streams: Vec<String>,
+    /// Whether the stream layouts are identical
+    pub streams_match: bool,
+    /// Whether the output matches the source digests, for byte chunks only
+    pub source_hash_matches: Option<bool>,
+}
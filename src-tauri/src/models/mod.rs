@@ -1,2 +1,11 @@
+pub mod analysis;
+pub mod animation;
+pub mod audio;
+pub mod capture;
+pub mod contact_sheet;
+pub mod manifest;
 pub mod partition;
+pub mod preset;
+pub mod project;
+pub mod thumbnail;
 pub mod video;
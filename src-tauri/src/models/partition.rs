@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::manifest::HashAlgorithm;
+use crate::models::preset::EncodeProfile;
+
+pub mod cut_list;
+
 /// Request to split a video file into partitions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,13 +15,80 @@ pub struct SplitRequest {
     pub output_dir: String,
     /// Target size per partition in bytes
     pub target_size_bytes: u64,
+    /// Length of each partition in seconds; replaces `target_size_bytes` when set
+    #[serde(default)]
+    pub target_duration_secs: Option<f64>,
     /// Time intervals to exclude from the output
     pub exclusions: Vec<TimeInterval>,
+    /// Encoding applied to partitions; stream copy by default
+    #[serde(default)]
+    pub encode_profile: EncodeProfile,
+    /// Output file name template with `{stem}`, `{n}` and `{ext}` placeholders;
+    /// `{stem}_part{n}.{ext}` when absent
+    #[serde(default)]
+    pub naming_template: Option<String>,
+    /// Hashes to compute for the source and every partition.
+    /// A manifest is written next to the partitions when non-empty.
+    #[serde(default)]
+    pub checksums: Vec<HashAlgorithm>,
+    /// Directory for intermediate files; defaults to the system temp directory
+    #[serde(default)]
+    pub scratch_dir: Option<String>,
+    /// Number of partitions written concurrently; defaults to one
+    #[serde(default)]
+    pub worker_count: Option<u32>,
 }
 
-/// A time interval defined by start and end timestamps.
+impl SplitRequest {
+    /// What each partition is sized by.
+    pub fn target(&self) -> PartitionTarget {
+        self.target_duration_secs.map_or(
+            PartitionTarget::Size(self.target_size_bytes),
+            PartitionTarget::Duration,
+        )
+    }
+}
+
+/// What the partitions of a split are sized by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PartitionTarget {
+    /// Roughly this many bytes per partition
+    Size(u64),
+    /// This many seconds per partition
+    Duration(f64),
+}
+
+/// Request to split an arbitrary file into fixed-size byte chunks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct ByteSplitRequest {
+    /// Path to the source file
+    pub input_path: String,
+    /// Directory where chunks will be saved
+    pub output_dir: String,
+    /// Size of every chunk except the last, in bytes
+    pub target_size_bytes: u64,
+    /// Hashes to compute for the source and every chunk.
+    /// A manifest is written next to the chunks when non-empty.
+    #[serde(default)]
+    pub checksums: Vec<HashAlgorithm>,
+}
+
+/// Progress of a running split, emitted to the frontend after each step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitProgress {
+    /// Number of partitions fully written
+    pub completed_partitions: u32,
+    /// Total number of partitions
+    pub total_partitions: u32,
+    /// Overall completion from 0 to 100
+    pub percent: f64,
+}
+
+/// A time interval defined by start and end timestamps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TimeInterval {
     /// Start time in seconds
     pub start_secs: f64,
@@ -37,3 +109,68 @@ pub struct PartitionPoint {
     /// Estimated size in bytes
     pub estimated_size_bytes: u64,
 }
+
+/// Disk space needed by a split compared with what the output volume has free.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    /// Total bytes needed on the output volume, including the safety margin
+    pub required_bytes: u64,
+    /// Sum of the estimated partition sizes
+    pub partition_bytes: u64,
+    /// Free bytes on the output volume
+    pub available_bytes: u64,
+    /// Whether the split fits in the available space
+    pub sufficient: bool,
+}
+
+/// Exclusions after clamping to the video and merging overlaps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedExclusions {
+    /// Sorted, non-overlapping intervals within the video
+    pub intervals: Vec<TimeInterval>,
+    /// Human-readable descriptions of every adjustment made
+    pub warnings: Vec<String>,
+}
+
+/// What a split would do, computed without writing anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitPlan {
+    /// Partitions in output order
+    pub partitions: Vec<PartitionPlan>,
+    /// Disk space the split needs on the output volume
+    pub space: PreflightReport,
+    /// Adjustments made to the exclusions before planning
+    pub warnings: Vec<String>,
+}
+
+/// One planned partition and the `FFmpeg` invocation that would write it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionPlan {
+    /// Partition index (0-based)
+    pub index: u32,
+    /// Full path of the output file
+    pub output_path: String,
+    /// Estimated size in bytes
+    pub estimated_size_bytes: u64,
+    /// Original-timeline ranges copied into the partition
+    pub segments: Vec<PlannedSegment>,
+    /// Arguments passed to `ffmpeg`, excluding the program name. A concat list path
+    /// points into a `job-<id>` placeholder directory, as the split creates its own.
+    pub ffmpeg_args: Vec<String>,
+    /// Contents of the concat list for partitions spanning exclusions
+    pub concat_list: Option<String>,
+}
+
+/// A range of the original timeline and where stream copy will actually start it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedSegment {
+    /// Requested range
+    pub interval: TimeInterval,
+    /// Last video keyframe at or before the start, where stream copy begins
+    pub keyframe_start_secs: Option<f64>,
+}
@@ -0,0 +1,674 @@
+//! Conversion between [`TimeInterval`] lists and cut lists exchanged with editors.
+//!
+//! Supported formats are CMX3600 EDL, simple `start,end` CSV, `FFmpeg` metadata
+//! chapter files and CUE sheets. Timecodes may be written as `HH:MM:SS.mmm` (hours and
+//! minutes optional) or as SMPTE `HH:MM:SS:FF`, with `;` before the frames for
+//! drop-frame; CUE sheets use their own `MM:SS:FF` at 75 frames per second.
+
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use super::TimeInterval;
+
+/// File format of a cut list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CutListFormat {
+    /// CMX3600 edit decision list; source in/out of every event
+    Edl,
+    /// One `start,end` pair per line, with an optional header
+    Csv,
+    /// `FFmpeg` metadata file (`;FFMETADATA1`) with one `[CHAPTER]` per interval
+    FfMetadata,
+    /// CUE sheet with one `TRACK` per interval, starting at its `INDEX 01`
+    Cue,
+}
+
+impl CutListFormat {
+    /// Picks the format from a file extension (`edl`, `csv`, `ffmeta`, `ffmetadata`,
+    /// `cue`).
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "edl" => Some(Self::Edl),
+            "csv" => Some(Self::Csv),
+            "ffmeta" | "ffmetadata" => Some(Self::FfMetadata),
+            "cue" => Some(Self::Cue),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a cut list into time intervals in seconds.
+///
+/// `frame_rate` is the video's frame rate, needed for SMPTE timecodes; pass `0.0`
+/// if it is unknown.
+pub fn parse_cut_list(
+    content: &str,
+    format: CutListFormat,
+    frame_rate: f64,
+) -> Result<Vec<TimeInterval>, String> {
+    match format {
+        CutListFormat::Edl => parse_edl(content, frame_rate),
+        CutListFormat::Csv => parse_csv(content, frame_rate),
+        CutListFormat::FfMetadata => parse_chapters(content),
+        CutListFormat::Cue => parse_cue(content),
+    }
+}
+
+/// Renders time intervals as a cut list.
+///
+/// EDL timecodes are written non-drop-frame at `frame_rate`, which must be known.
+pub fn write_cut_list(
+    intervals: &[TimeInterval],
+    format: CutListFormat,
+    frame_rate: f64,
+) -> Result<String, String> {
+    match format {
+        CutListFormat::Edl => write_edl(intervals, frame_rate),
+        CutListFormat::Csv => Ok(write_csv(intervals)),
+        CutListFormat::FfMetadata => Ok(write_chapters(intervals)),
+        CutListFormat::Cue => Ok(write_cue(intervals)),
+    }
+}
+
+/// Parses `HH:MM:SS.mmm`, `MM:SS.mmm`, `SS.mmm` or SMPTE `HH:MM:SS:FF` / `HH:MM:SS;FF`.
+pub fn parse_timecode(text: &str, frame_rate: f64) -> Result<f64, String> {
+    let text = text.trim();
+    let drop_frame = text.contains(';');
+    let fields: Vec<&str> = text.split([':', ';']).collect();
+
+    if fields.len() == 4 {
+        return parse_smpte(&fields, frame_rate, drop_frame, text);
+    }
+    if drop_frame || fields.len() > 3 {
+        return Err(format!("Invalid timecode: {text}"));
+    }
+
+    let invalid = || format!("Invalid timecode: {text}");
+    let (whole, last) = fields.split_at(fields.len() - 1);
+    let seconds: f64 = last[0].replace(',', ".").parse().map_err(|_| invalid())?;
+    if !seconds.is_finite() || seconds < 0.0 || (!whole.is_empty() && seconds >= 60.0) {
+        return Err(invalid());
+    }
+
+    let mut total = 0.0;
+    for (i, field) in whole.iter().enumerate() {
+        let value: u32 = field.parse().map_err(|_| invalid())?;
+        // Minutes are bounded unless they are the leading field
+        if i > 0 && value >= 60 {
+            return Err(invalid());
+        }
+        total = total * 60.0 + f64::from(value);
+    }
+
+    Ok(total.mul_add(60.0, seconds))
+}
+
+/// Formats seconds as `HH:MM:SS.mmm`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_timecode(seconds: f64) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (hours, rest) = (total_ms / 3_600_000, total_ms % 3_600_000);
+    let (minutes, rest) = (rest / 60_000, rest % 60_000);
+    format!(
+        "{hours:02}:{minutes:02}:{:02}.{:03}",
+        rest / 1000,
+        rest % 1000
+    )
+}
+
+/// Formats seconds as a non-drop-frame SMPTE timecode `HH:MM:SS:FF`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_smpte(seconds: f64, frame_rate: f64) -> Result<String, String> {
+    let nominal = nominal_frame_rate(frame_rate)?;
+    let frames = (seconds.max(0.0) * frame_rate).round() as u64;
+    let (total_secs, ff) = (frames / nominal, frames % nominal);
+    Ok(format!(
+        "{:02}:{:02}:{:02}:{ff:02}",
+        total_secs / 3600,
+        total_secs % 3600 / 60,
+        total_secs % 60
+    ))
+}
+
+/// Frames per timecode second, e.g. 30 for 29.97 fps material.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nominal_frame_rate(frame_rate: f64) -> Result<u64, String> {
+    if !frame_rate.is_finite() || frame_rate <= 0.0 {
+        return Err("SMPTE timecodes need the video's frame rate, which is unknown".to_string());
+    }
+    Ok((frame_rate.round() as u64).max(1))
+}
+
+/// Converts SMPTE fields to seconds. Frames are counted at the nominal rate and
+/// converted using the actual rate, so 29.97 fps timecodes map to real time.
+#[allow(clippy::cast_precision_loss)]
+fn parse_smpte(
+    fields: &[&str],
+    frame_rate: f64,
+    drop_frame: bool,
+    text: &str,
+) -> Result<f64, String> {
+    let invalid = || format!("Invalid timecode: {text}");
+    let nominal = nominal_frame_rate(frame_rate)?;
+
+    let mut values = [0u64; 4];
+    for (value, field) in values.iter_mut().zip(fields) {
+        *value = field.parse().map_err(|_| invalid())?;
+    }
+    let [hours, minutes, seconds, frames] = values;
+    if minutes >= 60 || seconds >= 60 || frames >= nominal {
+        return Err(invalid());
+    }
+
+    let mut frame_number = ((hours * 60 + minutes) * 60 + seconds) * nominal + frames;
+    if drop_frame {
+        // Drop-frame skips 2 (30 fps) or 4 (60 fps) frame numbers every minute,
+        // except every tenth minute
+        let dropped = match nominal {
+            30 => 2,
+            60 => 4,
+            _ => return Err(format!("Drop-frame timecode at {frame_rate} fps: {text}")),
+        };
+        if seconds == 0 && frames < dropped && minutes % 10 != 0 {
+            return Err(invalid());
+        }
+        let total_minutes = hours * 60 + minutes;
+        frame_number -= dropped * (total_minutes - total_minutes / 10);
+    }
+
+    Ok(frame_number as f64 / frame_rate)
+}
+
+/// Builds an interval, rejecting empty or inverted ranges.
+fn make_interval(start_secs: f64, end_secs: f64, line: usize) -> Result<TimeInterval, String> {
+    if end_secs <= start_secs {
+        return Err(format!(
+            "Line {line}: end {} is not after start {}",
+            format_timecode(end_secs),
+            format_timecode(start_secs)
+        ));
+    }
+    Ok(TimeInterval {
+        start_secs,
+        end_secs,
+    })
+}
+
+fn parse_edl(content: &str, frame_rate: f64) -> Result<Vec<TimeInterval>, String> {
+    let mut drop_frame = false;
+    let mut intervals: Vec<TimeInterval> = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if let Some(mode) = line.strip_prefix("FCM:") {
+            drop_frame = mode.trim().eq_ignore_ascii_case("DROP FRAME");
+            continue;
+        }
+
+        // Events: `001  AX  V  C  <src in> <src out> <rec in> <rec out>`
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let is_event = tokens.len() >= 8 && tokens[0].parse::<u32>().is_ok();
+        if !is_event {
+            continue;
+        }
+
+        let timecode = |token: &str| {
+            // Under `FCM: DROP FRAME` the frame separator may still be written as `:`
+            let token = if drop_frame && !token.contains(';') {
+                replace_last_colon(token)
+            } else {
+                token.to_string()
+            };
+            parse_timecode(&token, frame_rate).map_err(|e| format!("Line {}: {e}", i + 1))
+        };
+        let n = tokens.len();
+        let interval = make_interval(timecode(tokens[n - 4])?, timecode(tokens[n - 3])?, i + 1)?;
+
+        // Audio and video events of the same edit share source times
+        if !intervals.contains(&interval) {
+            intervals.push(interval);
+        }
+    }
+
+    Ok(intervals)
+}
+
+fn replace_last_colon(token: &str) -> String {
+    token.rfind(':').map_or_else(
+        || token.to_string(),
+        |idx| format!("{};{}", &token[..idx], &token[idx + 1..]),
+    )
+}
+
+fn write_edl(intervals: &[TimeInterval], frame_rate: f64) -> Result<String, String> {
+    let mut content = String::from("TITLE: Exclusions\nFCM: NON-DROP FRAME\n\n");
+    let mut record_secs = 0.0;
+
+    for (i, interval) in intervals.iter().enumerate() {
+        let duration = interval.end_secs - interval.start_secs;
+        let _ = writeln!(
+            content,
+            "{:03}  AX       V     C        {} {} {} {}",
+            i + 1,
+            format_smpte(interval.start_secs, frame_rate)?,
+            format_smpte(interval.end_secs, frame_rate)?,
+            format_smpte(record_secs, frame_rate)?,
+            format_smpte(record_secs + duration, frame_rate)?,
+        );
+        record_secs += duration;
+    }
+
+    Ok(content)
+}
+
+fn parse_csv(content: &str, frame_rate: f64) -> Result<Vec<TimeInterval>, String> {
+    let mut intervals = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split([',', '\t']).map(|f| f.trim().trim_matches('"'));
+        let (Some(start), Some(end)) = (fields.next(), fields.next()) else {
+            return Err(format!("Line {}: expected start,end", i + 1));
+        };
+
+        match (
+            parse_timecode(start, frame_rate),
+            parse_timecode(end, frame_rate),
+        ) {
+            (Ok(start), Ok(end)) => intervals.push(make_interval(start, end, i + 1)?),
+            _ if i == 0 && is_csv_header(start) => {}
+            (Err(e), _) | (_, Err(e)) => return Err(format!("Line {}: {e}", i + 1)),
+        }
+    }
+
+    Ok(intervals)
+}
+
+/// Whether the first field of line 1 names a column rather than holding a malformed
+/// timecode: it has no digits or starts with a usual column name.
+fn is_csv_header(first_field: &str) -> bool {
+    const HEADER_WORDS: &[&str] = &["start", "begin", "from", "in", "timecode", "time"];
+
+    let field = first_field.to_lowercase();
+    !field.chars().any(|c| c.is_ascii_digit())
+        || HEADER_WORDS.iter().any(|word| field.starts_with(word))
+}
+
+fn write_csv(intervals: &[TimeInterval]) -> String {
+    let mut content = String::from("start,end\n");
+    for interval in intervals {
+        let _ = writeln!(
+            content,
+            "{},{}",
+            format_timecode(interval.start_secs),
+            format_timecode(interval.end_secs)
+        );
+    }
+    content
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn parse_chapters(content: &str) -> Result<Vec<TimeInterval>, String> {
+    /// Fields of the chapter being read.
+    struct Chapter {
+        line: usize,
+        timebase: (u64, u64),
+        start: Option<u64>,
+        end: Option<u64>,
+    }
+
+    let finish = |chapter: Chapter| -> Result<TimeInterval, String> {
+        let (num, den) = chapter.timebase;
+        let (Some(start), Some(end)) = (chapter.start, chapter.end) else {
+            return Err(format!(
+                "Line {}: chapter needs START and END",
+                chapter.line
+            ));
+        };
+        let to_secs = |ticks: u64| ticks as f64 * num as f64 / den as f64;
+        make_interval(to_secs(start), to_secs(end), chapter.line)
+    };
+
+    let mut intervals = Vec::new();
+    let mut current: Option<Chapter> = None;
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            if let Some(chapter) = current.take() {
+                intervals.push(finish(chapter)?);
+            }
+            if line.eq_ignore_ascii_case("[CHAPTER]") {
+                current = Some(Chapter {
+                    line: i + 1,
+                    timebase: (1, 1_000_000_000),
+                    start: None,
+                    end: None,
+                });
+            }
+            continue;
+        }
+
+        let (Some(chapter), Some((key, value))) = (current.as_mut(), line.split_once('=')) else {
+            continue;
+        };
+        let invalid = || format!("Line {}: invalid {key}", i + 1);
+        match key.trim().to_uppercase().as_str() {
+            "TIMEBASE" => {
+                let (num, den) = value.split_once('/').ok_or_else(invalid)?;
+                let num: u64 = num.trim().parse().map_err(|_| invalid())?;
+                let den: u64 = den.trim().parse().map_err(|_| invalid())?;
+                if num == 0 || den == 0 {
+                    return Err(invalid());
+                }
+                chapter.timebase = (num, den);
+            }
+            "START" => chapter.start = Some(value.trim().parse().map_err(|_| invalid())?),
+            "END" => chapter.end = Some(value.trim().parse().map_err(|_| invalid())?),
+            _ => {}
+        }
+    }
+
+    if let Some(chapter) = current {
+        intervals.push(finish(chapter)?);
+    }
+
+    Ok(intervals)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn write_chapters(intervals: &[TimeInterval]) -> String {
+    let mut content = String::from(";FFMETADATA1\n");
+    for (i, interval) in intervals.iter().enumerate() {
+        let _ = write!(
+            content,
+            "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle=Excluded {}\n",
+            (interval.start_secs * 1000.0).round() as u64,
+            (interval.end_secs * 1000.0).round() as u64,
+            i + 1
+        );
+    }
+    content
+}
+
+/// Frames per second of CUE sheet `MM:SS:FF` times.
+const CUE_FRAME_RATE: u64 = 75;
+
+/// Parses a CUE `MM:SS:FF` time; minutes may exceed 99.
+#[allow(clippy::cast_precision_loss)]
+fn parse_cue_time(text: &str) -> Option<f64> {
+    let mut fields = text.split(':').map(|f| f.parse::<u64>().ok());
+    let (Some(Some(minutes)), Some(Some(seconds)), Some(Some(frames)), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return None;
+    };
+    if seconds >= 60 || frames >= CUE_FRAME_RATE {
+        return None;
+    }
+    let frame_number = (minutes * 60 + seconds) * CUE_FRAME_RATE + frames;
+    Some(frame_number as f64 / CUE_FRAME_RATE as f64)
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn format_cue_time(seconds: f64) -> String {
+    let frames = (seconds.max(0.0) * CUE_FRAME_RATE as f64).round() as u64;
+    let (total_secs, ff) = (frames / CUE_FRAME_RATE, frames % CUE_FRAME_RATE);
+    format!("{:02}:{:02}:{ff:02}", total_secs / 60, total_secs % 60)
+}
+
+/// Reads one interval per `TRACK`: from its `INDEX 01` to the next track's `INDEX 00`
+/// (its pregap) or `INDEX 01`. The last track ends at a `REM END` time inside it,
+/// since a CUE sheet otherwise runs it to the end of the media.
+fn parse_cue(content: &str) -> Result<Vec<TimeInterval>, String> {
+    /// Times of the track being read.
+    struct Track {
+        line: usize,
+        start: Option<f64>,
+        pregap: Option<f64>,
+        end: Option<f64>,
+    }
+
+    let mut tracks: Vec<Track> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let invalid = || format!("Line {}: invalid time {}", i + 1, tokens[tokens.len() - 1]);
+        match tokens.as_slice() {
+            [keyword, ..] if keyword.eq_ignore_ascii_case("TRACK") => tracks.push(Track {
+                line: i + 1,
+                start: None,
+                pregap: None,
+                end: None,
+            }),
+            [keyword, number, time] if keyword.eq_ignore_ascii_case("INDEX") => {
+                let Some(track) = tracks.last_mut() else {
+                    continue;
+                };
+                let time = parse_cue_time(time).ok_or_else(invalid)?;
+                match *number {
+                    "00" | "0" => track.pregap = Some(time),
+                    "01" | "1" => track.start = Some(time),
+                    _ => {}
+                }
+            }
+            [rem, key, time]
+                if rem.eq_ignore_ascii_case("REM") && key.eq_ignore_ascii_case("END") =>
+            {
+                if let Some(track) = tracks.last_mut() {
+                    track.end = Some(parse_cue_time(time).ok_or_else(invalid)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut intervals = Vec::with_capacity(tracks.len());
+    for (i, track) in tracks.iter().enumerate() {
+        let start = track
+            .start
+            .ok_or_else(|| format!("Line {}: track has no INDEX 01", track.line))?;
+        let end = track
+            .end
+            .or_else(|| {
+                tracks
+                    .get(i + 1)
+                    .and_then(|next| next.pregap.or(next.start))
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Line {}: the last track needs a REM END time to mark where it ends",
+                    track.line
+                )
+            })?;
+        intervals.push(make_interval(start, end, track.line)?);
+    }
+
+    Ok(intervals)
+}
+
+/// Writes one audio track per interval. The gap to the next interval becomes that
+/// track's pregap and every track records its end in `REM END`.
+fn write_cue(intervals: &[TimeInterval]) -> String {
+    let mut content = String::from("TITLE \"Exclusions\"\nFILE \"source\" MP4\n");
+    let mut previous_end: Option<f64> = None;
+    for (i, interval) in intervals.iter().enumerate() {
+        let _ = writeln!(
+            content,
+            "  TRACK {:02} AUDIO\n    TITLE \"Excluded {}\"",
+            i + 1,
+            i + 1
+        );
+        if let Some(end) = previous_end.filter(|&end| end < interval.start_secs) {
+            let _ = writeln!(content, "    INDEX 00 {}", format_cue_time(end));
+        }
+        let _ = writeln!(
+            content,
+            "    INDEX 01 {}\n    REM END {}",
+            format_cue_time(interval.start_secs),
+            format_cue_time(interval.end_secs)
+        );
+        previous_end = Some(interval.end_secs);
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start_secs: f64, end_secs: f64) -> TimeInterval {
+        TimeInterval {
+            start_secs,
+            end_secs,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_parse_clock_timecodes() {
+        assert_close(parse_timecode("01:02:03.250", 0.0).unwrap(), 3723.25);
+        assert_close(parse_timecode("02:03,5", 0.0).unwrap(), 123.5);
+        assert_close(parse_timecode("42.125", 0.0).unwrap(), 42.125);
+        assert!(parse_timecode("00:61:00", 0.0).is_err());
+        assert!(parse_timecode("abc", 0.0).is_err());
+    }
+
+    #[test]
+    fn test_parse_smpte_timecodes() {
+        assert_close(parse_timecode("00:00:10:12", 25.0).unwrap(), 10.48);
+        // Frame 1800 at 29.97 fps drop-frame is labelled 00:01:00;02
+        assert_close(
+            parse_timecode("00:01:00;02", 29.97).unwrap(),
+            1800.0 / 29.97,
+        );
+        assert!(parse_timecode("00:00:10:25", 25.0).is_err());
+        assert!(parse_timecode("00:00:10:12", 0.0).is_err());
+    }
+
+    #[test]
+    fn test_format_timecodes() {
+        assert_eq!(format_timecode(3723.25), "01:02:03.250");
+        assert_eq!(format_timecode(59.9999), "00:01:00.000");
+        assert_eq!(format_smpte(10.48, 25.0).unwrap(), "00:00:10:12");
+    }
+
+    #[test]
+    fn test_edl_roundtrip() {
+        let intervals = vec![interval(10.0, 20.0), interval(65.0, 70.48)];
+        let edl = write_cut_list(&intervals, CutListFormat::Edl, 25.0).unwrap();
+
+        assert!(edl.contains(
+            "002  AX       V     C        00:01:05:00 00:01:10:12 00:00:10:00 00:00:15:12"
+        ));
+        assert_eq!(
+            parse_cut_list(&edl, CutListFormat::Edl, 25.0).unwrap(),
+            intervals
+        );
+    }
+
+    #[test]
+    fn test_edl_drop_frame_and_audio_events() {
+        let edl = "TITLE: Cuts\nFCM: DROP FRAME\n\n\
+                   001  AX       V     C        00:01:00:02 00:01:00:04 00:00:00:00 00:00:00:02\n\
+                   001  AX       A     C        00:01:00:02 00:01:00:04 00:00:00:00 00:00:00:02\n";
+        let intervals = parse_cut_list(edl, CutListFormat::Edl, 29.97).unwrap();
+
+        assert_eq!(intervals.len(), 1);
+        assert_close(intervals[0].start_secs, 1800.0 / 29.97);
+    }
+
+    #[test]
+    fn test_csv_with_header_and_mixed_timecodes() {
+        let csv = "start,end\n10.5,20\n00:01:00.000,\"00:01:30.250\"\n";
+        let intervals = parse_cut_list(csv, CutListFormat::Csv, 0.0).unwrap();
+
+        assert_eq!(intervals, vec![interval(10.5, 20.0), interval(60.0, 90.25)]);
+        assert_eq!(
+            write_cut_list(&intervals, CutListFormat::Csv, 0.0).unwrap(),
+            "start,end\n00:00:10.500,00:00:20.000\n00:01:00.000,00:01:30.250\n"
+        );
+    }
+
+    #[test]
+    fn test_inverted_interval_is_rejected() {
+        assert!(parse_cut_list("20,10\n", CutListFormat::Csv, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_malformed_first_csv_line_is_not_a_header() {
+        let error = parse_cut_list("1O.5,20\n30,40\n", CutListFormat::Csv, 0.0).unwrap_err();
+        assert!(error.starts_with("Line 1:"), "{error}");
+
+        let header = "Start 1,End 1\n30,40\n";
+        assert_eq!(
+            parse_cut_list(header, CutListFormat::Csv, 0.0).unwrap(),
+            vec![interval(30.0, 40.0)]
+        );
+    }
+
+    #[test]
+    fn test_ffmetadata_roundtrip() {
+        let intervals = vec![interval(1.5, 3.0), interval(10.0, 12.25)];
+        let content = write_cut_list(&intervals, CutListFormat::FfMetadata, 0.0).unwrap();
+
+        assert_eq!(
+            parse_cut_list(&content, CutListFormat::FfMetadata, 0.0).unwrap(),
+            intervals
+        );
+
+        let custom =
+            ";FFMETADATA1\ntitle=Movie\n[CHAPTER]\nTIMEBASE=1/90000\nSTART=90000\nEND=180000\n";
+        assert_eq!(
+            parse_cut_list(custom, CutListFormat::FfMetadata, 0.0).unwrap(),
+            vec![interval(1.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn test_cue_roundtrip() {
+        let intervals = vec![interval(10.0, 20.0), interval(65.0, 70.48)];
+        let content = write_cut_list(&intervals, CutListFormat::Cue, 0.0).unwrap();
+
+        assert!(
+            content.contains("TRACK 02 AUDIO\n    TITLE \"Excluded 2\"\n    INDEX 00 00:20:00\n")
+        );
+        assert!(content.contains("    INDEX 01 01:05:00\n    REM END 01:10:36\n"));
+
+        let parsed = parse_cut_list(&content, CutListFormat::Cue, 0.0).unwrap();
+        assert_eq!(parsed.len(), intervals.len());
+        for (parsed, expected) in parsed.iter().zip(&intervals) {
+            assert_close(parsed.start_secs, expected.start_secs);
+            assert_close(parsed.end_secs, expected.end_secs);
+        }
+    }
+
+    #[test]
+    fn test_cue_tracks_end_at_next_track() {
+        let cue = "FILE \"talk.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:01:00\n\
+                   \x20 TRACK 02 AUDIO\n    INDEX 01 00:02:37\n    REM END 100:00:00\n";
+        let intervals = parse_cut_list(cue, CutListFormat::Cue, 0.0).unwrap();
+
+        assert_eq!(intervals.len(), 2);
+        assert_close(intervals[0].end_secs, 2.0 + 37.0 / 75.0);
+        assert_close(intervals[1].start_secs, intervals[0].end_secs);
+        assert_close(intervals[1].end_secs, 6000.0);
+
+        let open_ended = "TRACK 01 AUDIO\nINDEX 01 00:01:00\n";
+        assert!(parse_cut_list(open_ended, CutListFormat::Cue, 0.0).is_err());
+        let bad_frame = "TRACK 01 AUDIO\nINDEX 01 00:01:75\nREM END 00:02:00\n";
+        assert!(parse_cut_list(bad_frame, CutListFormat::Cue, 0.0).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the presets file format.
+pub const PRESETS_VERSION: u32 = 1;
+
+/// Naming template used when a preset doesn't specify one.
+pub const DEFAULT_NAMING_TEMPLATE: &str = "{stem}_part{n}.{ext}";
+
+/// How a file is divided into partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitStrategy {
+    /// Partitions of roughly `target` bytes each
+    Size,
+    /// Partitions of roughly `target` seconds each
+    Duration,
+}
+
+/// How partitions are encoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncodeProfile {
+    /// Stream copy — no re-encoding, no quality loss
+    #[default]
+    Copy,
+    /// Re-encode to H.264/AAC for maximum compatibility
+    H264,
+    /// Re-encode to HEVC/AAC for smaller files
+    Hevc,
+}
+
+/// A named, reusable split configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitPreset {
+    /// Unique display name
+    pub name: String,
+    /// Whether `target` is a size or a duration
+    pub strategy: SplitStrategy,
+    /// Target per partition: bytes for [`SplitStrategy::Size`], seconds for [`SplitStrategy::Duration`]
+    pub target: f64,
+    /// Encoding applied to partitions
+    #[serde(default)]
+    pub encode_profile: EncodeProfile,
+    /// Output file name template with `{stem}`, `{n}` and `{ext}` placeholders
+    #[serde(default = "default_naming_template")]
+    pub naming_template: String,
+    /// Output directory; asks for one on every split when absent
+    #[serde(default)]
+    pub output_dir: Option<String>,
+}
+
+/// On-disk layout of the presets file, also used for import and export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetFile {
+    /// Format version, currently [`PRESETS_VERSION`]
+    pub version: u32,
+    /// Presets in display order
+    pub presets: Vec<SplitPreset>,
+}
+
+fn default_naming_template() -> String {
+    DEFAULT_NAMING_TEMPLATE.to_string()
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::partition::TimeInterval;
+
+/// Version of the project state file format.
+pub const PROJECT_VERSION: u32 = 1;
+
+/// A named position in the video.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bookmark {
+    /// Position in seconds
+    pub time_secs: f64,
+    /// User-provided label
+    #[serde(default)]
+    pub label: String,
+}
+
+/// Editing state of a single video, restored when the file is reopened.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectState {
+    /// Time intervals excluded from the output
+    #[serde(default)]
+    pub exclusions: Vec<TimeInterval>,
+    /// Manually placed split points in seconds
+    #[serde(default)]
+    pub split_points: Vec<f64>,
+    /// Name of the preset chosen for this file
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// Bookmarked positions
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl ProjectState {
+    /// Whether there is nothing worth persisting.
+    pub const fn is_empty(&self) -> bool {
+        self.exclusions.is_empty()
+            && self.split_points.is_empty()
+            && self.preset.is_none()
+            && self.bookmarks.is_empty()
+    }
+}
+
+/// Identifies a particular version of a file so edits to it invalidate saved state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFingerprint {
+    /// File size in bytes
+    pub size_bytes: u64,
+    /// Modification time in milliseconds since the Unix epoch
+    pub modified_millis: u64,
+}
+
+/// On-disk record of a video's project state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFile {
+    /// Format version, currently [`PROJECT_VERSION`]
+    pub version: u32,
+    /// Path of the video the state belongs to
+    pub file_path: String,
+    /// Fingerprint of the video when the state was saved
+    pub fingerprint: FileFingerprint,
+    /// The saved editing state
+    pub state: ProjectState,
+}
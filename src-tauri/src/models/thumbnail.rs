@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::project::FileFingerprint;
+
+/// Version of the thumbnail cache index format.
+pub const CACHE_INDEX_VERSION: u32 = 1;
+
+/// Emitted as soon as one thumbnail of a strip has been written.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailReady {
+    /// Video the thumbnail belongs to
+    pub video_path: String,
+    /// Position in the strip, starting at 0
+    pub index: u32,
+    /// Number of thumbnails in the strip
+    pub total: u32,
+    /// Path of the thumbnail image
+    pub path: String,
+}
+
+/// One set of images generated from a video and kept in the thumbnail cache.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry {
+    /// Digest of the source identity and variant; also the entry's directory name
+    pub key: String,
+    /// Video the images were generated from
+    pub source_path: String,
+    /// Fingerprint of the video when the images were generated
+    pub fingerprint: FileFingerprint,
+    /// What was generated, e.g. `strip-20x60` for 20 thumbnails 60 pixels high
+    pub variant: String,
+    /// Total size of the entry's files in bytes
+    pub size_bytes: u64,
+    /// Last time the entry was generated or read, in milliseconds since the Unix epoch
+    pub last_used_millis: u64,
+}
+
+/// On-disk index of the thumbnail cache.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheIndex {
+    /// Format version, currently [`CACHE_INDEX_VERSION`]
+    pub version: u32,
+    /// Cached entries in no particular order
+    pub entries: Vec<CacheEntry>,
+}
+
+/// Summary of the thumbnail cache for display.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailCacheInfo {
+    /// Directory holding the cache
+    pub directory: String,
+    /// Combined size of all entries in bytes
+    pub total_bytes: u64,
+    /// Size above which least recently used entries are evicted
+    pub limit_bytes: u64,
+    /// Cached entries, most recently used first
+    pub entries: Vec<CacheEntry>,
+}
+
+/// How `generate_thumbnails` writes a strip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailLayout {
+    /// One JPEG per thumbnail, announced one by one as they are extracted
+    #[default]
+    Files,
+    /// A single tiled JPEG plus a `WebVTT` track, written in one `FFmpeg` pass
+    Sprite,
+}
+
+/// A tiled thumbnail image and the `WebVTT` track describing it.
+///
+/// Tile `i` shows the video from `i * interval_secs` and sits in row `i / columns`,
+/// column `i % columns`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteSheet {
+    /// Path of the sprite sheet JPEG
+    pub image_path: String,
+    /// Path of the `WebVTT` file mapping time ranges to `#xywh=` tile coordinates
+    pub vtt_path: String,
+    /// Number of tiles
+    pub count: u32,
+    /// Tiles per row
+    pub columns: u32,
+    /// Number of rows
+    pub rows: u32,
+    /// Width of one tile in pixels
+    pub tile_width: u32,
+    /// Height of one tile in pixels
+    pub tile_height: u32,
+    /// Video time covered by each tile in seconds
+    pub interval_secs: f64,
+}
+
+/// Thumbnails generated for the timeline, in the requested layout.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "layout", rename_all = "lowercase")]
+pub enum ThumbnailStrip {
+    /// Thumbnail paths in timeline order
+    Files { paths: Vec<String> },
+    /// A single sprite sheet
+    Sprite(SpriteSheet),
+}
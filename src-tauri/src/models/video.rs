@@ -24,4 +24,18 @@ pub struct VideoMetadata {
     pub bitrate: u64,
     /// Container format (e.g., "mp4", "mkv")
     pub format: String,
+    /// Video frame rate in frames per second, or 0 if unknown
+    #[serde(default)]
+    pub frame_rate: f64,
+}
+
+/// A file the webview can display in place of a media file, served through the
+/// asset protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewSource {
+    /// Path to load: the original file, or a converted copy in the cache
+    pub path: String,
+    /// Whether `path` is a converted copy rather than the original
+    pub transcoded: bool,
 }
@@ -0,0 +1,327 @@
+use crate::error::AppError;
+use crate::models::analysis::{AnalysisOptions, DetectionKind, ExclusionSuggestion};
+use crate::models::partition::TimeInterval;
+use crate::utils::ffmpeg_wrapper::{probe_media, run_ffmpeg};
+
+/// Sections closer than this to either end of the file count as lead-in or tail.
+const EDGE_TOLERANCE_SECS: f64 = 1.0;
+
+/// Sections at least this long are more likely to be unwanted.
+const LONG_SECTION_SECS: f64 = 30.0;
+
+/// A section reported by one detection filter.
+#[derive(Debug, Clone, PartialEq)]
+struct Detection {
+    kind: DetectionKind,
+    start: f64,
+    end: f64,
+}
+
+/// Runs `blackdetect`, `freezedetect` and `silencedetect` over a file in one decoding
+/// pass and turns what they find into exclusion suggestions.
+///
+/// Detections that overlap are combined into one suggestion with a higher confidence.
+/// Video filters are skipped for audio-only files and vice versa.
+pub async fn suggest_exclusions(
+    file_path: &str,
+    options: &AnalysisOptions,
+) -> Result<Vec<ExclusionSuggestion>, AppError> {
+    let probe = probe_media(file_path).await?;
+    let has_video = probe.streams.iter().any(|s| s.starts_with("video:"));
+    let has_audio = probe.streams.iter().any(|s| s.starts_with("audio:"));
+    if !has_video && !has_audio {
+        return Ok(Vec::new());
+    }
+
+    let video_filters = format!(
+        "blackdetect=d={}:pic_th={},freezedetect=n={}dB:d={}",
+        options.black_min_secs,
+        options.black_picture_threshold,
+        options.freeze_noise_db,
+        options.freeze_min_secs
+    );
+    let audio_filters = format!(
+        "silencedetect=n={}dB:d={}",
+        options.silence_noise_db, options.silence_min_secs
+    );
+
+    let mut args = vec!["-hide_banner", "-nostats", "-i", file_path];
+    if has_video {
+        args.extend(["-map", "0:v:0", "-vf", &video_filters]);
+    }
+    if has_audio {
+        args.extend(["-map", "0:a:0", "-af", &audio_filters]);
+    }
+    args.extend(["-f", "null", "-"]);
+
+    let (_, stderr, exit_code) = run_ffmpeg(&args).await?;
+    if exit_code != Some(0) {
+        return Err(AppError::ffmpeg(
+            "Analysis failed",
+            Some(file_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+
+    let detections = parse_detections(&stderr, probe.duration_secs);
+    Ok(build_suggestions(&detections, probe.duration_secs))
+}
+
+/// Extracts detected sections from the filters' log output.
+///
+/// Freeze and silence sections still open at the end of the file end at `duration`.
+fn parse_detections(output: &str, duration: f64) -> Vec<Detection> {
+    let mut detections = Vec::new();
+    let mut freeze_start = None;
+    let mut silence_start = None;
+
+    for line in output.lines() {
+        if line.contains("[blackdetect") {
+            if let (Some(start), Some(end)) = (
+                value_after(line, "black_start:"),
+                value_after(line, "black_end:"),
+            ) {
+                detections.push(Detection {
+                    kind: DetectionKind::Black,
+                    start,
+                    end,
+                });
+            }
+        } else if line.contains("[freezedetect") {
+            if let Some(start) = value_after(line, "freeze_start:") {
+                freeze_start = Some(start);
+            } else if let Some(end) = value_after(line, "freeze_end:") {
+                if let Some(start) = freeze_start.take() {
+                    detections.push(Detection {
+                        kind: DetectionKind::Freeze,
+                        start,
+                        end,
+                    });
+                }
+            }
+        } else if line.contains("[silencedetect") {
+            if let Some(start) = value_after(line, "silence_start:") {
+                silence_start = Some(start);
+            } else if let Some(end) = value_after(line, "silence_end:") {
+                if let Some(start) = silence_start.take() {
+                    detections.push(Detection {
+                        kind: DetectionKind::Silence,
+                        start,
+                        end,
+                    });
+                }
+            }
+        }
+    }
+
+    for (kind, start) in [
+        (DetectionKind::Freeze, freeze_start),
+        (DetectionKind::Silence, silence_start),
+    ] {
+        if let Some(start) = start.filter(|&s| s < duration) {
+            detections.push(Detection {
+                kind,
+                start,
+                end: duration,
+            });
+        }
+    }
+
+    detections
+}
+
+/// Parses the number following `key`, e.g. `12.5` in `black_start:12.5 black_end:20`.
+fn value_after(line: &str, key: &str) -> Option<f64> {
+    let idx = line.find(key)?;
+    line[idx + key.len()..]
+        .split_whitespace()
+        .next()?
+        .trim_end_matches('|')
+        .parse()
+        .ok()
+}
+
+/// Groups overlapping detections and scores each group.
+fn build_suggestions(detections: &[Detection], duration: f64) -> Vec<ExclusionSuggestion> {
+    let mut sorted: Vec<&Detection> = detections.iter().filter(|d| d.end > d.start).collect();
+    sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut groups: Vec<Vec<&Detection>> = Vec::new();
+    let mut group_end = f64::NEG_INFINITY;
+    for detection in sorted {
+        match groups.last_mut() {
+            Some(group) if detection.start <= group_end => {
+                group.push(detection);
+                group_end = group_end.max(detection.end);
+            }
+            _ => {
+                groups.push(vec![detection]);
+                group_end = detection.end;
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|group| {
+            let start = group.iter().map(|d| d.start).fold(f64::INFINITY, f64::min);
+            let end = group
+                .iter()
+                .map(|d| d.end)
+                .fold(f64::NEG_INFINITY, f64::max);
+
+            // Independent detectors agreeing make a section more likely to be unwanted
+            let miss = group
+                .iter()
+                .map(|d| 1.0 - detection_confidence(d, duration))
+                .product::<f64>();
+            let confidence = ((1.0 - miss) * 100.0).round() / 100.0;
+
+            let mut kinds: Vec<DetectionKind> = Vec::new();
+            for detection in &group {
+                if !kinds.contains(&detection.kind) {
+                    kinds.push(detection.kind);
+                }
+            }
+
+            let reason = group
+                .iter()
+                .map(|d| describe_detection(d, duration))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            ExclusionSuggestion {
+                interval: TimeInterval {
+                    start_secs: start,
+                    end_secs: end,
+                },
+                confidence,
+                reason: capitalize(&reason),
+                kinds,
+            }
+        })
+        .collect()
+}
+
+/// Scores a single detection; lead-ins and tails are the usual unwanted sections.
+fn detection_confidence(detection: &Detection, duration: f64) -> f64 {
+    let base = match detection.kind {
+        DetectionKind::Black => 0.6,
+        DetectionKind::Freeze => 0.5,
+        DetectionKind::Silence => 0.35,
+    };
+    let edge_bonus = if edge_label(detection, duration).is_some() {
+        0.25
+    } else {
+        0.0
+    };
+    let length_bonus = if detection.end - detection.start >= LONG_SECTION_SECS {
+        0.1
+    } else {
+        0.0
+    };
+    f64::min(base + edge_bonus + length_bonus, 0.95)
+}
+
+fn edge_label(detection: &Detection, duration: f64) -> Option<&'static str> {
+    if detection.start <= EDGE_TOLERANCE_SECS {
+        Some("at the start")
+    } else if detection.end >= duration - EDGE_TOLERANCE_SECS {
+        Some("at the end")
+    } else {
+        None
+    }
+}
+
+fn describe_detection(detection: &Detection, duration: f64) -> String {
+    let what = match detection.kind {
+        DetectionKind::Black => "black screen",
+        DetectionKind::Freeze => "frozen picture",
+        DetectionKind::Silence => "silence",
+    };
+    let length = detection.end - detection.start;
+    edge_label(detection, duration).map_or_else(
+        || format!("{what} for {length:.1} s"),
+        |edge| format!("{what} for {length:.1} s {edge}"),
+    )
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "\
+[blackdetect @ 0x55d] black_start:0 black_end:12.5 black_duration:12.5
+[silencedetect @ 0x55e] silence_start: 0
+[silencedetect @ 0x55e] silence_end: 15.2 | silence_duration: 15.2
+[freezedetect @ 0x55f] lavfi.freezedetect.freeze_start: 300.04
+[freezedetect @ 0x55f] lavfi.freezedetect.freeze_duration: 10.0
+[freezedetect @ 0x55f] lavfi.freezedetect.freeze_end: 310.04
+[freezedetect @ 0x55f] lavfi.freezedetect.freeze_start: 560
+";
+
+    #[test]
+    fn test_parse_detections() {
+        let detections = parse_detections(OUTPUT, 600.0);
+
+        assert_eq!(
+            detections,
+            vec![
+                Detection {
+                    kind: DetectionKind::Black,
+                    start: 0.0,
+                    end: 12.5
+                },
+                Detection {
+                    kind: DetectionKind::Silence,
+                    start: 0.0,
+                    end: 15.2
+                },
+                Detection {
+                    kind: DetectionKind::Freeze,
+                    start: 300.04,
+                    end: 310.04
+                },
+                // Still frozen when the file ends
+                Detection {
+                    kind: DetectionKind::Freeze,
+                    start: 560.0,
+                    end: 600.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_detections_are_combined() {
+        let suggestions = build_suggestions(&parse_detections(OUTPUT, 600.0), 600.0);
+
+        assert_eq!(suggestions.len(), 3);
+
+        let intro = &suggestions[0];
+        assert!((intro.interval.end_secs - 15.2).abs() < 1e-9);
+        assert_eq!(
+            intro.kinds,
+            vec![DetectionKind::Black, DetectionKind::Silence]
+        );
+        assert_eq!(
+            intro.reason,
+            "Black screen for 12.5 s at the start, silence for 15.2 s at the start"
+        );
+
+        // Lead-in and tail outrank a freeze in the middle
+        let middle = &suggestions[1];
+        let tail = &suggestions[2];
+        assert!(intro.confidence > middle.confidence);
+        assert!(tail.confidence > middle.confidence);
+        assert_eq!(tail.reason, "Frozen picture for 40.0 s at the end");
+    }
+}
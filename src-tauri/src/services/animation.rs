@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+use crate::models::animation::{AnimationExport, AnimationFormat, AnimationRequest};
+use crate::utils::ffmpeg_wrapper::{format_ffmpeg_time, run_ffmpeg};
+
+/// Longest range that can be exported; animations are meant for short clips.
+const MAX_DURATION_SECS: f64 = 30.0;
+
+/// Encodes tried at most while approaching a size target.
+const MAX_ATTEMPTS: u32 = 6;
+
+/// Limits below which a size target is no longer chased.
+const MIN_WIDTH: u32 = 120;
+const MIN_FPS: u32 = 5;
+const MIN_GIF_COLORS: u32 = 32;
+const MIN_WEBP_QUALITY: u32 = 30;
+
+/// Settings of one encode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Encoding {
+    fps: u32,
+    width: u32,
+    /// Palette size for GIF, quality for WebP
+    quality: u32,
+}
+
+/// Exports the requested range as a looping GIF or WebP.
+///
+/// GIFs use a palette generated from the clip itself. With a size target the clip is
+/// encoded again with a lower width, then frame rate, then palette size or quality until
+/// it fits or nothing more can be lowered; the last result is kept either way.
+pub async fn export_animation(request: &AnimationRequest) -> Result<AnimationExport, AppError> {
+    validate_request(request)?;
+
+    let output = Path::new(&request.output_path);
+    let partial = partial_path(output);
+    let mut encoding = Encoding {
+        fps: request.fps,
+        width: request.width & !1,
+        quality: match request.format {
+            AnimationFormat::Gif => 256,
+            AnimationFormat::Webp => 75,
+        },
+    };
+
+    let mut attempts = 0;
+    let size_bytes = loop {
+        attempts += 1;
+        if let Err(e) = encode(request, encoding, &partial).await {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e);
+        }
+        let size = std::fs::metadata(&partial)
+            .map_err(|e| AppError::io("Failed to read animation size", &partial, &e))?
+            .len();
+
+        let Some(target) = request.max_size_bytes else {
+            break size;
+        };
+        if size <= target || attempts >= MAX_ATTEMPTS {
+            break size;
+        }
+        match shrink(encoding, request.format, size, target) {
+            Some(smaller) => encoding = smaller,
+            None => break size,
+        }
+    };
+
+    std::fs::rename(&partial, output)
+        .map_err(|e| AppError::io("Failed to save animation", output, &e))?;
+
+    Ok(AnimationExport {
+        path: request.output_path.clone(),
+        size_bytes,
+        fps: encoding.fps,
+        width: encoding.width,
+        attempts,
+        within_target: size_bytes <= request.max_size_bytes.unwrap_or(u64::MAX),
+    })
+}
+
+async fn encode(
+    request: &AnimationRequest,
+    encoding: Encoding,
+    output: &Path,
+) -> Result<(), AppError> {
+    let ss_arg = format_ffmpeg_time(request.start_secs);
+    let duration_arg = format!("{:.3}", request.end_secs - request.start_secs);
+    let filter = animation_filter(request.format, encoding);
+    let quality = encoding.quality.to_string();
+    let output_str = output.to_string_lossy().to_string();
+
+    let mut args = vec![
+        "-hide_banner",
+        "-ss",
+        &ss_arg,
+        "-t",
+        &duration_arg,
+        "-i",
+        &request.video_path,
+        "-an",
+        "-sn",
+        "-vf",
+        &filter,
+        "-loop",
+        "0",
+    ];
+    match request.format {
+        AnimationFormat::Gif => args.extend(["-f", "gif"]),
+        AnimationFormat::Webp => {
+            args.extend(["-c:v", "libwebp", "-quality", &quality, "-f", "webp"]);
+        }
+    }
+    args.extend(["-y", &output_str]);
+
+    let (_, stderr, exit_code) = run_ffmpeg(&args).await?;
+    if exit_code != Some(0) || !output.exists() {
+        return Err(AppError::ffmpeg(
+            "Animation export failed",
+            Some(&request.video_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Filter chain resampling the clip; GIFs get a palette built from the clip's own
+/// colours, with only changed rectangles re-dithered between frames.
+fn animation_filter(format: AnimationFormat, encoding: Encoding) -> String {
+    let Encoding {
+        fps,
+        width,
+        quality,
+    } = encoding;
+    let resample = format!("fps={fps},scale={width}:-2:flags=lanczos");
+    match format {
+        AnimationFormat::Gif => format!(
+            "{resample},split[a][b];[a]palettegen=max_colors={quality}:stats_mode=diff[p];\
+             [b][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle"
+        ),
+        AnimationFormat::Webp => resample,
+    }
+}
+
+/// Settings expected to bring a `size`-byte encode under `target`, or `None` if every
+/// setting is already at its minimum.
+///
+/// Size grows roughly with the pixel count and the frame rate, so the width shrinks by
+/// the square root of the needed factor first; what the width can't absorb comes off the
+/// frame rate, and only then off the palette or quality.
+fn shrink(current: Encoding, format: AnimationFormat, size: u64, target: u64) -> Option<Encoding> {
+    #[allow(clippy::cast_precision_loss)]
+    let factor = (target as f64 / size as f64) * 0.9;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let width = ((f64::from(current.width) * factor.sqrt()) as u32).max(MIN_WIDTH) & !1;
+    let width = width.min(current.width);
+    let width_gain = (f64::from(width) / f64::from(current.width)).powi(2);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let fps = if width_gain > factor {
+        ((f64::from(current.fps) * factor / width_gain) as u32).max(MIN_FPS)
+    } else {
+        current.fps
+    }
+    .min(current.fps);
+
+    let quality = if width == current.width && fps == current.fps {
+        match format {
+            AnimationFormat::Gif => (current.quality / 2).max(MIN_GIF_COLORS),
+            AnimationFormat::Webp => current.quality.saturating_sub(15).max(MIN_WEBP_QUALITY),
+        }
+    } else {
+        current.quality
+    };
+
+    let next = Encoding {
+        fps,
+        width,
+        quality,
+    };
+    (next != current).then_some(next)
+}
+
+/// Writes next to the destination under a temporary name so a failed or oversized
+/// attempt never replaces an existing file.
+fn partial_path(output: &Path) -> PathBuf {
+    let name = output
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    output.with_file_name(format!(".{name}.partial"))
+}
+
+fn validate_request(request: &AnimationRequest) -> Result<(), AppError> {
+    if !Path::new(&request.video_path).exists() {
+        return Err(AppError::NotFound {
+            path: request.video_path.clone(),
+        });
+    }
+    let duration = request.end_secs - request.start_secs;
+    if !request.start_secs.is_finite() || !request.end_secs.is_finite() {
+        return Err(AppError::invalid("The range must be finite"));
+    }
+    if request.start_secs < 0.0 || duration <= 0.0 {
+        return Err(AppError::invalid("The range must end after it starts"));
+    }
+    if duration > MAX_DURATION_SECS {
+        return Err(AppError::invalid(format!(
+            "Animations can be at most {MAX_DURATION_SECS} seconds long"
+        )));
+    }
+    if !(1..=50).contains(&request.fps) {
+        return Err(AppError::invalid("Frame rate must be between 1 and 50"));
+    }
+    if !(MIN_WIDTH..=1920).contains(&request.width) {
+        return Err(AppError::invalid(format!(
+            "Width must be between {MIN_WIDTH} and 1920 pixels"
+        )));
+    }
+    if request.max_size_bytes == Some(0) {
+        return Err(AppError::invalid("The size target must be positive"));
+    }
+    let extension = request.format.extension();
+    if !request
+        .output_path
+        .to_lowercase()
+        .ends_with(&format!(".{extension}"))
+    {
+        return Err(AppError::invalid(format!(
+            "The output file must end in .{extension}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: Encoding = Encoding {
+        fps: 15,
+        width: 640,
+        quality: 256,
+    };
+
+    #[test]
+    fn test_gif_filter_uses_palette() {
+        assert_eq!(
+            animation_filter(AnimationFormat::Gif, START),
+            "fps=15,scale=640:-2:flags=lanczos,split[a][b];\
+             [a]palettegen=max_colors=256:stats_mode=diff[p];\
+             [b][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle"
+        );
+        assert_eq!(
+            animation_filter(AnimationFormat::Webp, START),
+            "fps=15,scale=640:-2:flags=lanczos"
+        );
+    }
+
+    #[test]
+    fn test_shrink_lowers_width_then_fps_then_quality() {
+        // Needing half the size only narrows the animation
+        let next = shrink(START, AnimationFormat::Gif, 2000, 1000).unwrap();
+        assert_eq!(next.fps, 15);
+        assert_eq!(next.width, 428);
+        assert_eq!(next.quality, 256);
+
+        // Far too big: the width bottoms out and the frame rate absorbs the rest
+        let next = shrink(START, AnimationFormat::Gif, 100_000, 1000).unwrap();
+        assert_eq!(next.width, MIN_WIDTH);
+        assert_eq!(next.fps, MIN_FPS);
+
+        // With width and frame rate at their minimum the palette shrinks
+        let floor = Encoding {
+            fps: MIN_FPS,
+            width: MIN_WIDTH,
+            quality: 256,
+        };
+        assert_eq!(
+            shrink(floor, AnimationFormat::Gif, 2000, 1000)
+                .unwrap()
+                .quality,
+            128
+        );
+
+        // Nothing left to lower
+        let bottom = Encoding {
+            quality: MIN_GIF_COLORS,
+            ..floor
+        };
+        assert_eq!(shrink(bottom, AnimationFormat::Gif, 2000, 1000), None);
+    }
+}
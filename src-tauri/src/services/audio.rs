@@ -0,0 +1,259 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::AppError;
+use crate::models::audio::{AudioExportRequest, AudioFormat, AudioMode};
+use crate::models::partition::SplitProgress;
+use crate::models::preset::DEFAULT_NAMING_TEMPLATE;
+use crate::models::video::VideoMetadata;
+use crate::services::scratch::ScratchDir;
+use crate::services::splitter::{
+    partition_file_name, partition_intervals, validate_naming_template, write_intervals,
+};
+use crate::utils::ffmpeg_wrapper::probe_media;
+
+/// Bitrate of lossy encodes when the request doesn't set one.
+const DEFAULT_BITRATE_KBPS: u32 = 192;
+
+/// Exports the audio of `request.input_path` as one file, or as parts matching the
+/// partitions `split_video` would write for the same target and exclusions.
+///
+/// Parts are named like the video partitions, from the same naming template
+/// (`meeting_part1.m4a` by default); a single file keeps the source's name. Parts are
+/// written one after another and progress is reported after each. Setting `cancel`
+/// stops the running `FFmpeg` process; a partially written file is removed on failure.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub async fn export_audio(
+    request: &AudioExportRequest,
+    cancel: &AtomicBool,
+    on_progress: &(dyn Fn(SplitProgress) + Send + Sync),
+) -> Result<Vec<String>, AppError> {
+    let input_path = Path::new(&request.input_path);
+    if !input_path.exists() {
+        return Err(AppError::NotFound {
+            path: request.input_path.clone(),
+        });
+    }
+    let output_dir = Path::new(&request.output_dir);
+    if !output_dir.is_dir() {
+        return Err(AppError::NotFound {
+            path: request.output_dir.clone(),
+        });
+    }
+
+    let probe = probe_media(&request.input_path).await?;
+    let audio_codecs: Vec<&str> = probe
+        .streams
+        .iter()
+        .filter_map(|s| s.strip_prefix("audio:"))
+        .collect();
+    let codec_args = codec_args(request, &audio_codecs)?;
+
+    let file_size = std::fs::metadata(input_path)
+        .map_err(|e| AppError::io("Failed to read file metadata of", input_path, &e))?
+        .len();
+    let file_stem = input_path
+        .file_stem()
+        .map_or_else(|| "audio".to_string(), |s| s.to_string_lossy().to_string());
+    let file_name = input_path
+        .file_name()
+        .map_or_else(|| file_stem.clone(), |n| n.to_string_lossy().to_string());
+    let metadata = VideoMetadata {
+        file_path: request.input_path.clone(),
+        file_name,
+        file_size,
+        duration_secs: probe.duration_secs,
+        width: 0,
+        height: 0,
+        video_codec: String::new(),
+        audio_codec: None,
+        bitrate: 0,
+        format: String::new(),
+        frame_rate: 0.0,
+    };
+    let target = request.target();
+    let parts = partition_intervals(&metadata, target, &request.exclusions)?;
+
+    let extension = request.format.extension();
+    let naming_template = request
+        .naming_template
+        .as_deref()
+        .unwrap_or(DEFAULT_NAMING_TEMPLATE);
+    if target.is_some() {
+        validate_naming_template(naming_template, &file_stem, extension)?;
+    }
+    let output_paths: Vec<_> = (0..parts.len())
+        .map(|i| {
+            let name = if target.is_some() {
+                partition_file_name(naming_template, &file_stem, extension, i as u32)
+            } else {
+                format!("{file_stem}.{extension}")
+            };
+            output_dir.join(name)
+        })
+        .collect();
+    if output_paths.iter().any(|path| path == input_path) {
+        return Err(AppError::invalid(
+            "The audio would overwrite the source; choose another folder or format",
+        ));
+    }
+
+    let scratch = ScratchDir::create(request.scratch_dir.as_deref().map(Path::new))?;
+    let args: Vec<&str> = codec_args.iter().map(String::as_str).collect();
+    let total = parts.len();
+    let mut output_files = Vec::with_capacity(total);
+
+    for (i, (intervals, path)) in parts.iter().zip(&output_paths).enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(AppError::Cancelled);
+        }
+        let path_str = path.to_string_lossy().to_string();
+        if let Err(e) = write_intervals(
+            &request.input_path,
+            intervals,
+            &path_str,
+            &args,
+            scratch.path(),
+            cancel,
+        )
+        .await
+        {
+            let _ = std::fs::remove_file(path);
+            return Err(e);
+        }
+        output_files.push(path_str);
+
+        let done = i as u32 + 1;
+        on_progress(SplitProgress {
+            completed_partitions: done,
+            total_partitions: total as u32,
+            percent: f64::from(done) / total as f64 * 100.0,
+        });
+    }
+
+    Ok(output_files)
+}
+
+/// Arguments selecting the requested audio track(s) and how they are written, given
+/// the codecs of the source's audio streams in order.
+fn codec_args(
+    request: &AudioExportRequest,
+    audio_codecs: &[&str],
+) -> Result<Vec<String>, AppError> {
+    if audio_codecs.is_empty() {
+        return Err(AppError::invalid("The file has no audio track"));
+    }
+    let format = request.format;
+
+    let (map, selected) = match request.track {
+        Some(track) => {
+            let codec = audio_codecs.get(track as usize).ok_or_else(|| {
+                AppError::invalid(format!(
+                    "Audio track {} doesn't exist; the file has {}",
+                    track + 1,
+                    audio_codecs.len()
+                ))
+            })?;
+            (format!("0:a:{track}"), vec![*codec])
+        }
+        // Only MP4 holds several audio streams; other formats take the first
+        None if format == AudioFormat::M4a => ("0:a".to_string(), audio_codecs.to_vec()),
+        None => ("0:a:0".to_string(), vec![audio_codecs[0]]),
+    };
+
+    let mut args = vec!["-map".to_string(), map];
+    let codec: &[&str] = match request.mode {
+        AudioMode::Copy => {
+            if let Some(codec) = selected.iter().find(|c| !format.can_copy(c)) {
+                return Err(AppError::invalid(format!(
+                    "{codec} audio can't be copied into .{}; encode it instead",
+                    format.extension()
+                )));
+            }
+            &["-c:a", "copy"]
+        }
+        AudioMode::Encode => match format {
+            AudioFormat::M4a => &["-c:a", "aac"],
+            AudioFormat::Mp3 => &["-c:a", "libmp3lame"],
+            AudioFormat::Flac => &["-c:a", "flac"],
+            AudioFormat::Wav => &["-c:a", "pcm_s16le"],
+        },
+    };
+    args.extend(codec.iter().map(|arg| (*arg).to_string()));
+
+    if request.mode == AudioMode::Encode && format.is_lossy() {
+        let bitrate = request.bitrate_kbps.unwrap_or(DEFAULT_BITRATE_KBPS);
+        if !(32..=320).contains(&bitrate) {
+            return Err(AppError::invalid(
+                "Bitrate must be between 32 and 320 kbit/s",
+            ));
+        }
+        args.extend(["-b:a".to_string(), format!("{bitrate}k")]);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(format: AudioFormat, mode: AudioMode) -> AudioExportRequest {
+        AudioExportRequest {
+            input_path: "/in.mp4".to_string(),
+            output_dir: "/out".to_string(),
+            format,
+            mode,
+            bitrate_kbps: None,
+            track: None,
+            exclusions: Vec::new(),
+            target_size_bytes: None,
+            target_duration_secs: None,
+            naming_template: None,
+            scratch_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_encode_args() {
+        let args = codec_args(&request(AudioFormat::Mp3, AudioMode::Encode), &["aac"]).unwrap();
+        assert_eq!(
+            args,
+            ["-map", "0:a:0", "-c:a", "libmp3lame", "-b:a", "192k"]
+        );
+
+        // Lossless formats take no bitrate; m4a keeps every track
+        let args = codec_args(&request(AudioFormat::Flac, AudioMode::Encode), &["aac"]).unwrap();
+        assert_eq!(args, ["-map", "0:a:0", "-c:a", "flac"]);
+        let args = codec_args(
+            &request(AudioFormat::M4a, AudioMode::Encode),
+            &["aac", "ac3"],
+        )
+        .unwrap();
+        assert_eq!(args[..2], ["-map", "0:a"]);
+
+        let mut bad_bitrate = request(AudioFormat::M4a, AudioMode::Encode);
+        bad_bitrate.bitrate_kbps = Some(1000);
+        assert!(codec_args(&bad_bitrate, &["aac"]).is_err());
+    }
+
+    #[test]
+    fn test_copy_requires_matching_codec() {
+        let copy_m4a = request(AudioFormat::M4a, AudioMode::Copy);
+        assert_eq!(
+            codec_args(&copy_m4a, &["aac"]).unwrap(),
+            ["-map", "0:a", "-c:a", "copy"]
+        );
+        assert!(codec_args(&copy_m4a, &["aac", "ac3"]).is_err());
+
+        let mut second_track = copy_m4a;
+        second_track.track = Some(1);
+        assert!(codec_args(&second_track, &["ac3", "aac"]).is_ok());
+        second_track.track = Some(2);
+        assert!(codec_args(&second_track, &["ac3", "aac"]).is_err());
+
+        let copy_wav = request(AudioFormat::Wav, AudioMode::Copy);
+        assert!(codec_args(&copy_wav, &["pcm_s24le"]).is_ok());
+        assert!(codec_args(&copy_wav, &["aac"]).is_err());
+        assert!(codec_args(&copy_wav, &[]).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::AppError;
+use crate::models::manifest::{
+    FileHashes, HashAlgorithm, ManifestKind, ManifestPartition, ManifestSource, SplitManifest,
+    MANIFEST_VERSION,
+};
+use crate::models::partition::{ByteSplitRequest, SplitProgress};
+use crate::models::preset::EncodeProfile;
+use crate::services::checksum::MultiHasher;
+use crate::services::manifest::write_manifest;
+use crate::services::preflight::{available_space, estimate_disk_space};
+
+/// Buffer size used when copying between the source and chunk files.
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Outcome of joining byte chunks back into a single file.
+pub struct JoinOutcome {
+    /// Number of chunks whose digests were checked
+    pub verified_chunks: u32,
+    /// Whether the output matches the source digests, if the manifest has any
+    pub source_hash_matches: Option<bool>,
+}
+
+/// Splits any file into fixed-size chunks named `{file_name}.001`, `{file_name}.002`, ...
+///
+/// Streams the source once, hashing the source and every chunk as they are written.
+/// Refuses to start if the output volume cannot hold a full copy of the source.
+/// Setting `cancel` stops the split and removes the chunk being written.
+/// When checksums are requested, writes a manifest in the same format as the video splitter.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn split_file_bytes(
+    request: &ByteSplitRequest,
+    cancel: &AtomicBool,
+    on_progress: &dyn Fn(SplitProgress),
+) -> Result<Vec<String>, AppError> {
+    let input_path = Path::new(&request.input_path);
+    if !input_path.is_file() {
+        return Err(AppError::NotFound {
+            path: request.input_path.clone(),
+        });
+    }
+
+    let output_dir = Path::new(&request.output_dir);
+    if !output_dir.is_dir() {
+        return Err(AppError::NotFound {
+            path: request.output_dir.clone(),
+        });
+    }
+
+    if request.target_size_bytes == 0 {
+        return Err(AppError::invalid("Chunk size must be greater than 0"));
+    }
+
+    let total_size = std::fs::metadata(input_path)
+        .map_err(|e| AppError::io("Failed to read file metadata of", input_path, &e))?
+        .len();
+    if total_size == 0 {
+        return Err(AppError::invalid("Input file is empty"));
+    }
+
+    ensure_space_for_copy(output_dir, total_size)?;
+
+    let file_name = input_path
+        .file_name()
+        .map_or_else(|| "output".to_string(), |n| n.to_string_lossy().to_string());
+    let chunk_count = total_size.div_ceil(request.target_size_bytes) as u32;
+
+    let mut reader = File::open(input_path)
+        .map_err(|e| AppError::io("Failed to open input file", input_path, &e))?;
+    let mut source_hasher = MultiHasher::new(&request.checksums);
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut written_total = 0u64;
+    let mut output_files = Vec::with_capacity(chunk_count as usize);
+    let mut manifest_partitions = Vec::with_capacity(chunk_count as usize);
+
+    for index in 0..chunk_count {
+        let chunk_name = chunk_file_name(&file_name, index);
+        let chunk_path = output_dir.join(&chunk_name);
+        let chunk_size = request.target_size_bytes.min(total_size - written_total);
+
+        let hashes = match write_chunk(
+            &mut reader,
+            &chunk_path,
+            chunk_size,
+            &request.checksums,
+            &mut source_hasher,
+            &mut buffer,
+            cancel,
+        ) {
+            Ok(hashes) => hashes,
+            Err(e) => {
+                let _ = std::fs::remove_file(&chunk_path);
+                return Err(e);
+            }
+        };
+        written_total += chunk_size;
+
+        on_progress(SplitProgress {
+            completed_partitions: index + 1,
+            total_partitions: chunk_count,
+            percent: written_total as f64 / total_size as f64 * 100.0,
+        });
+
+        manifest_partitions.push(ManifestPartition {
+            index,
+            file_name: chunk_name,
+            size_bytes: chunk_size,
+            segments: Vec::new(),
+            hashes,
+        });
+        output_files.push(chunk_path.to_string_lossy().to_string());
+    }
+
+    if !request.checksums.is_empty() {
+        let manifest = SplitManifest {
+            version: MANIFEST_VERSION,
+            kind: ManifestKind::Bytes,
+            encode_profile: EncodeProfile::Copy,
+            source: ManifestSource {
+                file_name: file_name.clone(),
+                size_bytes: total_size,
+                duration_secs: 0.0,
+                streams: Vec::new(),
+                hashes: source_hasher.finalize(),
+            },
+            partitions: manifest_partitions,
+        };
+        write_manifest(&manifest, output_dir, &file_name)?;
+    }
+
+    Ok(output_files)
+}
+
+/// Copies `size_bytes` from `reader` into a new chunk file, returning the chunk's digests.
+fn write_chunk(
+    reader: &mut File,
+    chunk_path: &Path,
+    size_bytes: u64,
+    algorithms: &[HashAlgorithm],
+    source_hasher: &mut MultiHasher,
+    buffer: &mut [u8],
+    cancel: &AtomicBool,
+) -> Result<FileHashes, AppError> {
+    let file =
+        File::create(chunk_path).map_err(|e| AppError::io("Failed to create", chunk_path, &e))?;
+    let mut writer = BufWriter::new(file);
+    let mut chunk_hasher = MultiHasher::new(algorithms);
+    let mut remaining = size_bytes;
+
+    while remaining > 0 {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(AppError::Cancelled);
+        }
+
+        let want = usize::try_from(remaining).map_or(buffer.len(), |r| r.min(buffer.len()));
+        let read = reader
+            .read(&mut buffer[..want])
+            .map_err(|e| AppError::Io {
+                context: "Failed to read input file".to_string(),
+                path: None,
+                message: e.to_string(),
+            })?;
+        if read == 0 {
+            return Err(AppError::internal(
+                "Input file ended before all chunks were written",
+            ));
+        }
+
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|e| AppError::io("Failed to write", chunk_path, &e))?;
+        chunk_hasher.update(&buffer[..read]);
+        source_hasher.update(&buffer[..read]);
+
+        remaining -= read as u64;
+    }
+
+    writer
+        .flush()
+        .map_err(|e| AppError::io("Failed to write", chunk_path, &e))?;
+
+    Ok(chunk_hasher.finalize())
+}
+
+/// Joins the chunks listed in a byte manifest back into `output_path`.
+///
+/// Each chunk is hashed while it is copied and the join fails on the first mismatch,
+/// removing the partial output.
+pub fn join_file_chunks(
+    manifest: &SplitManifest,
+    parts_dir: &Path,
+    output_path: &Path,
+) -> Result<JoinOutcome, AppError> {
+    let result = copy_chunks(manifest, parts_dir, output_path);
+    if result.is_err() {
+        let _ = std::fs::remove_file(output_path);
+    }
+    result
+}
+
+fn copy_chunks(
+    manifest: &SplitManifest,
+    parts_dir: &Path,
+    output_path: &Path,
+) -> Result<JoinOutcome, AppError> {
+    let mut partitions: Vec<&ManifestPartition> = manifest.partitions.iter().collect();
+    partitions.sort_by_key(|p| p.index);
+
+    let source_algorithms = manifest.source.hashes.algorithms();
+    let file =
+        File::create(output_path).map_err(|e| AppError::io("Failed to create", output_path, &e))?;
+    let mut writer = BufWriter::new(file);
+    let mut source_hasher = MultiHasher::new(&source_algorithms);
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut verified_chunks = 0u32;
+
+    for partition in partitions {
+        let chunk_path = parts_dir.join(&partition.file_name);
+        let mut reader = File::open(&chunk_path)
+            .map_err(|e| AppError::io("Failed to open chunk", &chunk_path, &e))?;
+        let chunk_algorithms = partition.hashes.algorithms();
+        let mut chunk_hasher = MultiHasher::new(&chunk_algorithms);
+
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .map_err(|e| AppError::io("Failed to read chunk", &chunk_path, &e))?;
+            if read == 0 {
+                break;
+            }
+            writer
+                .write_all(&buffer[..read])
+                .map_err(|e| AppError::io("Failed to write", output_path, &e))?;
+            chunk_hasher.update(&buffer[..read]);
+            source_hasher.update(&buffer[..read]);
+        }
+
+        if !chunk_algorithms.is_empty() {
+            if chunk_hasher.finalize() != partition.hashes {
+                return Err(AppError::invalid(format!(
+                    "Checksum mismatch for chunk: {}",
+                    chunk_path.display()
+                )));
+            }
+            verified_chunks += 1;
+        }
+    }
+
+    writer
+        .flush()
+        .map_err(|e| AppError::io("Failed to write", output_path, &e))?;
+
+    let source_hash_matches =
+        (!source_algorithms.is_empty()).then(|| source_hasher.finalize() == manifest.source.hashes);
+
+    Ok(JoinOutcome {
+        verified_chunks,
+        source_hash_matches,
+    })
+}
+
+/// Fails with exact byte counts if `output_dir` cannot hold `size_bytes` more data.
+fn ensure_space_for_copy(output_dir: &Path, size_bytes: u64) -> Result<(), AppError> {
+    let space = estimate_disk_space(&[size_bytes], available_space(output_dir)?);
+
+    if space.sufficient {
+        Ok(())
+    } else {
+        Err(AppError::DiskFull {
+            path: output_dir.display().to_string(),
+            required_bytes: Some(space.required_bytes),
+            available_bytes: Some(space.available_bytes),
+        })
+    }
+}
+
+/// Builds the chunk file name for a 0-based chunk index (`movie.iso.001` for index 0).
+fn chunk_file_name(file_name: &str, index: u32) -> String {
+    format!("{file_name}.{:03}", index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::manifest::read_manifest;
+
+    #[test]
+    fn test_chunk_file_name() {
+        assert_eq!(chunk_file_name("disk.img", 0), "disk.img.001");
+        assert_eq!(chunk_file_name("disk.img", 41), "disk.img.042");
+        assert_eq!(chunk_file_name("disk.img", 1233), "disk.img.1234");
+    }
+
+    #[test]
+    fn test_split_and_join_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("vp-byte-split-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let input = dir.join("data.bin");
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&input, &content).unwrap();
+
+        let request = ByteSplitRequest {
+            input_path: input.to_string_lossy().to_string(),
+            output_dir: dir.to_string_lossy().to_string(),
+            target_size_bytes: 4096,
+            checksums: vec![HashAlgorithm::Sha256, HashAlgorithm::Blake3],
+        };
+        let chunks = split_file_bytes(&request, &AtomicBool::new(false), &|_| {}).unwrap();
+        assert_eq!(chunks.len(), 3);
+
+        let manifest = read_manifest(&dir.join("data.bin_manifest.json")).unwrap();
+        assert_eq!(manifest.kind, ManifestKind::Bytes);
+        assert_eq!(manifest.partitions[2].size_bytes, 10_000 - 2 * 4096);
+
+        let output = dir.join("joined.bin");
+        let outcome = join_file_chunks(&manifest, &dir, &output).unwrap();
+        assert_eq!(outcome.verified_chunks, 3);
+        assert_eq!(outcome.source_hash_matches, Some(true));
+        assert_eq!(std::fs::read(&output).unwrap(), content);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
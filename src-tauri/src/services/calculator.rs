@@ -1,28 +1,46 @@
-use crate::models::partition::{PartitionPoint, TimeInterval};
+use crate::error::AppError;
+use crate::models::partition::{PartitionPoint, PartitionTarget, TimeInterval};
 use crate::models::video::VideoMetadata;
+use crate::services::exclusions::{normalize_exclusions, union_duration};
 
-/// Calculates partition split points based on video metadata and target size.
+/// Calculates partition split points based on video metadata and a target size or
+/// duration per partition.
 ///
-/// Takes into account excluded intervals when computing where to split.
-#[allow(clippy::cast_precision_loss)]
+/// Takes into account excluded intervals when computing where to split. Exclusions
+/// are normalised first, so inverted intervals are rejected and overlaps count once.
+/// Partitions are equally long, so a duration target is an upper bound.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
 pub fn calculate_partition_points(
     metadata: &VideoMetadata,
-    target_size_bytes: u64,
+    target: PartitionTarget,
     exclusions: &[TimeInterval],
-) -> Vec<PartitionPoint> {
-    let excluded_duration = total_excluded_duration(exclusions);
+) -> Result<Vec<PartitionPoint>, AppError> {
+    let normalized = normalize_exclusions(exclusions, metadata.duration_secs)?;
+    let excluded_duration = total_excluded_duration(&normalized.intervals);
     let effective_duration = metadata.duration_secs - excluded_duration;
 
-    if effective_duration <= 0.0 || metadata.file_size == 0 || target_size_bytes == 0 {
-        return Vec::new();
+    if effective_duration <= 0.0 || metadata.file_size == 0 {
+        return Ok(Vec::new());
     }
 
     let effective_size =
         (effective_duration / metadata.duration_secs) * metadata.file_size as f64;
-    let partition_count = (effective_size / target_size_bytes as f64).ceil() as u32;
+    let partition_count = match target {
+        PartitionTarget::Size(0) => return Ok(Vec::new()),
+        PartitionTarget::Size(bytes) => (effective_size / bytes as f64).ceil() as u32,
+        PartitionTarget::Duration(secs) if secs.is_finite() && secs > 0.0 => {
+            // Tolerate float noise so a 30-minute video in 15-minute parts gives two
+            (effective_duration / secs - 1e-9).ceil() as u32
+        }
+        PartitionTarget::Duration(_) => return Ok(Vec::new()),
+    };
 
     if partition_count == 0 {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
     let time_per_partition = effective_duration / f64::from(partition_count);
@@ -46,12 +64,12 @@ pub fn calculate_partition_points(
         });
     }
 
-    points
+    Ok(points)
 }
 
-/// Calculates the total excluded duration in seconds.
+/// Calculates the total excluded duration in seconds, counting overlapping time once.
 pub fn total_excluded_duration(exclusions: &[TimeInterval]) -> f64 {
-    exclusions.iter().map(|e| e.end_secs - e.start_secs).sum()
+    union_duration(exclusions)
 }
 
 #[cfg(test)]
@@ -70,6 +88,7 @@ mod tests {
             audio_codec: None,
             bitrate: 0,
             format: "mp4".to_string(),
+            frame_rate: 30.0,
         }
     }
 
@@ -77,7 +96,8 @@ mod tests {
     fn test_no_exclusions_even_split() {
         let metadata = make_metadata(10_737_418_240, 100.0); // 10 GB, 100s
         let target = 4_294_967_296; // 4 GB
-        let points = calculate_partition_points(&metadata, target, &[]);
+        let points =
+            calculate_partition_points(&metadata, PartitionTarget::Size(target), &[]).unwrap();
 
         assert_eq!(points.len(), 3);
         assert_eq!(points[0].index, 0);
@@ -92,7 +112,9 @@ mod tests {
             start_secs: 20.0,
             end_secs: 40.0,
         }]; // 20s excluded => 80s effective => ~8GB
-        let points = calculate_partition_points(&metadata, target, &exclusions);
+        let points =
+            calculate_partition_points(&metadata, PartitionTarget::Size(target), &exclusions)
+                .unwrap();
 
         assert_eq!(points.len(), 2);
     }
@@ -112,4 +134,49 @@ mod tests {
         let total = total_excluded_duration(&exclusions);
         assert!((total - 20.0).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn test_overlapping_exclusions_count_once() {
+        let metadata = make_metadata(10_737_418_240, 100.0); // 10 GB, 100s
+        let target = 4_294_967_296; // 4 GB
+        let exclusions = vec![
+            TimeInterval {
+                start_secs: 10.0,
+                end_secs: 40.0,
+            },
+            TimeInterval {
+                start_secs: 20.0,
+                end_secs: 50.0,
+            },
+        ]; // 40s excluded => 60s effective => ~6GB
+        let points =
+            calculate_partition_points(&metadata, PartitionTarget::Size(target), &exclusions)
+                .unwrap();
+
+        assert_eq!(points.len(), 2);
+        assert!((points[1].end_secs - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_duration_target() {
+        let metadata = make_metadata(1_000_000, 1800.0); // 30 minutes
+        let points =
+            calculate_partition_points(&metadata, PartitionTarget::Duration(900.0), &[]).unwrap();
+        assert_eq!(points.len(), 2);
+        assert!((points[0].end_secs - 900.0).abs() < 1e-9);
+        assert_eq!(points[0].estimated_size_bytes, 500_000);
+
+        // 40 s excluded leaves 29:20, so 10-minute parts make three
+        let exclusions = vec![TimeInterval {
+            start_secs: 0.0,
+            end_secs: 40.0,
+        }];
+        let points =
+            calculate_partition_points(&metadata, PartitionTarget::Duration(600.0), &exclusions)
+                .unwrap();
+        assert_eq!(points.len(), 3);
+
+        let none = calculate_partition_points(&metadata, PartitionTarget::Duration(0.0), &[]);
+        assert!(none.unwrap().is_empty());
+    }
 }
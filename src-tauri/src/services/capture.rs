@@ -0,0 +1,452 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::AppError;
+use crate::models::capture::{
+    Burst, CaptureFormat, CaptureSettings, CapturedFrame, CAPTURE_SETTINGS_VERSION,
+};
+use crate::services::image_metadata::{self, FrameOrigin};
+use crate::utils::ffmpeg_wrapper::{format_ffmpeg_time, parse_keyframe_times, run_ffmpeg};
+
+/// File name of the capture settings inside the app config directory.
+const SETTINGS_FILE_NAME: &str = "capture.json";
+
+/// Most frames a single burst may write.
+pub const MAX_BURST_FRAMES: u32 = 300;
+
+/// How far before the requested frame decoding starts, so frames whose timestamps are
+/// slightly early still reach the frame selection.
+const PRE_ROLL_SECS: f64 = 1.0;
+
+/// Distinguishes the temporary files of concurrent captures.
+static NEXT_CAPTURE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Loads the capture settings from `config_dir`, or the defaults if none were saved.
+pub fn load_settings(config_dir: &Path) -> Result<CaptureSettings, AppError> {
+    let path = config_dir.join(SETTINGS_FILE_NAME);
+    if !path.exists() {
+        return Ok(CaptureSettings::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| AppError::io("Failed to read capture settings", &path, &e))?;
+    let settings: CaptureSettings = serde_json::from_str(&content)
+        .map_err(|e| AppError::invalid(format!("Invalid capture settings: {e}")))?;
+    if settings.version > CAPTURE_SETTINGS_VERSION {
+        return Err(AppError::invalid(format!(
+            "Capture settings version {} is newer than supported version {CAPTURE_SETTINGS_VERSION}",
+            settings.version
+        )));
+    }
+    Ok(settings)
+}
+
+/// Validates and saves the capture settings to `config_dir`.
+pub fn save_settings(config_dir: &Path, settings: &CaptureSettings) -> Result<(), AppError> {
+    validate_settings(settings)?;
+    std::fs::create_dir_all(config_dir)
+        .map_err(|e| AppError::io("Failed to create config directory", config_dir, &e))?;
+
+    let settings = CaptureSettings {
+        version: CAPTURE_SETTINGS_VERSION,
+        ..settings.clone()
+    };
+    let json = serde_json::to_string_pretty(&settings)
+        .map_err(|e| AppError::internal(format!("Failed to serialize capture settings: {e}")))?;
+
+    // Write to a temporary file first so a crash can't leave truncated settings
+    let path = config_dir.join(SETTINGS_FILE_NAME);
+    let temp_path = config_dir.join(format!("{SETTINGS_FILE_NAME}.tmp"));
+    std::fs::write(&temp_path, json)
+        .map_err(|e| AppError::io("Failed to write capture settings", &temp_path, &e))?;
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| AppError::io("Failed to save capture settings", &path, &e))
+}
+
+/// Captures the frame a paused player shows at `timestamp_secs`, or a `burst` of frames
+/// starting there.
+///
+/// With the video's `frame_rate` (0 if unknown) the time is snapped to the start of the
+/// displayed frame. Decoding starts at the preceding keyframe and runs up to that exact
+/// frame, so the capture matches the player on any container or codec.
+///
+/// Images are named `{stem}_frame_{HH_MM_SS_mmm}.{ext}` after the actual time of each
+/// frame and written to the screenshots folder, or next to the video if none is set.
+pub async fn capture_frames(
+    video_path: &str,
+    timestamp_secs: f64,
+    frame_rate: f64,
+    settings: &CaptureSettings,
+    burst: Option<Burst>,
+) -> Result<Vec<CapturedFrame>, AppError> {
+    validate_settings(settings)?;
+    if let Some(burst) = burst {
+        validate_burst(burst)?;
+    }
+    if !timestamp_secs.is_finite() || timestamp_secs < 0.0 {
+        return Err(AppError::invalid("Capture timestamp must not be negative"));
+    }
+    let input = Path::new(video_path);
+    if !input.exists() {
+        return Err(AppError::NotFound {
+            path: video_path.to_string(),
+        });
+    }
+
+    let stem = input
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    let output_dir = match &settings.output_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => input
+            .parent()
+            .ok_or_else(|| AppError::invalid("Cannot determine parent directory"))?
+            .to_path_buf(),
+    };
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| AppError::io("Failed to create screenshots folder", &output_dir, &e))?;
+
+    // FFmpeg numbers the frames; they are renamed after their timestamps once known
+    let ext = settings.format.extension();
+    let id = NEXT_CAPTURE_ID.fetch_add(1, Ordering::Relaxed);
+    let prefix = format!(".capture-{}-{id}-", std::process::id());
+    let pattern = output_dir.join(format!("{prefix}%04d.{ext}"));
+
+    let window = frame_window(timestamp_secs, frame_rate);
+    let result = run_capture(video_path, &window, settings, burst, &pattern).await;
+    let numbered = numbered_files(&output_dir, &prefix);
+    let frame_times = match result {
+        Ok(times) if !numbered.is_empty() => times,
+        Ok(_) => {
+            return Err(AppError::invalid(
+                "No frame was captured; the timestamp may be past the end of the video",
+            ))
+        }
+        Err(e) => {
+            for file in numbered {
+                let _ = std::fs::remove_file(file);
+            }
+            return Err(e);
+        }
+    };
+
+    let mut frames = Vec::with_capacity(numbered.len());
+    for (i, file) in numbered.iter().enumerate() {
+        let timestamp_secs = frame_times
+            .get(i)
+            .copied()
+            .unwrap_or_else(|| planned_time(&window, burst, i));
+        let time_str = format_ffmpeg_time(timestamp_secs).replace(':', "_");
+        let output_path = output_dir.join(format!("{stem}_frame_{time_str}.{ext}"));
+
+        std::fs::rename(file, &output_path)
+            .map_err(|e| AppError::io("Failed to save screenshot", &output_path, &e))?;
+        if settings.embed_metadata {
+            let timecode = format_ffmpeg_time(timestamp_secs);
+            let origin = FrameOrigin {
+                source: video_path,
+                timecode: &timecode,
+            };
+            image_metadata::embed(&output_path, settings.format, &origin)?;
+        }
+
+        frames.push(CapturedFrame {
+            path: output_path.to_string_lossy().to_string(),
+            timestamp_secs,
+        });
+    }
+    Ok(frames)
+}
+
+/// Where to seek and which frames to keep for a capture starting at a player time, all
+/// in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FrameWindow {
+    /// Input seek position; decoding starts at the keyframe before it
+    seek_at: f64,
+    /// Start of the frame the player shows
+    frame_start: f64,
+    /// Earliest timestamp accepted as that frame, allowing for timestamp jitter
+    select_from: f64,
+}
+
+/// Works out the frame shown at `timestamp_secs`: the last one starting at or before it.
+///
+/// Without a frame rate the first frame at the time itself is taken.
+fn frame_window(timestamp_secs: f64, frame_rate: f64) -> FrameWindow {
+    let (frame_start, select_from) = if frame_rate > 0.0 {
+        // A small epsilon keeps a time reported exactly at a frame boundary on that frame
+        let frame_start = (timestamp_secs.mul_add(frame_rate, 1e-3).floor() / frame_rate).max(0.0);
+        (frame_start, (frame_start - 0.5 / frame_rate).max(0.0))
+    } else {
+        (timestamp_secs, (timestamp_secs - 0.001).max(0.0))
+    };
+    FrameWindow {
+        seek_at: (select_from - PRE_ROLL_SECS).max(0.0),
+        frame_start,
+        select_from,
+    }
+}
+
+/// Runs `FFmpeg` and returns the timestamps of the written frames.
+async fn run_capture(
+    video_path: &str,
+    window: &FrameWindow,
+    settings: &CaptureSettings,
+    burst: Option<Burst>,
+    pattern: &Path,
+) -> Result<Vec<f64>, AppError> {
+    let ss_arg = format_ffmpeg_time(window.seek_at);
+    let filters = capture_filters(window, settings, burst);
+    let frame_limit = match burst {
+        None => 1,
+        Some(Burst::Interval { count, .. }) => count,
+        Some(Burst::Range { .. }) => MAX_BURST_FRAMES,
+    }
+    .to_string();
+    let pattern_str = pattern.to_string_lossy().to_string();
+
+    // Input seeking decodes from the preceding keyframe and restarts timestamps at the
+    // seek position; the select filter then picks the exact frame
+    let mut args = vec![
+        "-hide_banner",
+        "-nostats",
+        "-ss",
+        &ss_arg,
+        "-i",
+        video_path,
+        "-map",
+        "0:v:0",
+        "-vf",
+        &filters,
+        "-frames:v",
+        &frame_limit,
+    ];
+    // Keep every source frame of a range; `-fps_mode` replaces the deprecated `-vsync`
+    if matches!(burst, Some(Burst::Range { .. })) {
+        args.extend(["-fps_mode", "passthrough"]);
+    }
+    args.extend(encoder_args(settings));
+    args.extend(["-y", &pattern_str]);
+
+    let (_, stderr, exit_code) = run_ffmpeg(&args).await?;
+    if exit_code != Some(0) {
+        return Err(AppError::ffmpeg(
+            "Frame capture failed",
+            Some(video_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+    Ok(parse_keyframe_times(&stderr)
+        .into_iter()
+        .map(|t| window.seek_at + t)
+        .collect())
+}
+
+/// Filter chain selecting the captured frames, scaling them and logging their times.
+///
+/// Timestamps in the chain are relative to [`FrameWindow::seek_at`].
+fn capture_filters(
+    window: &FrameWindow,
+    settings: &CaptureSettings,
+    burst: Option<Burst>,
+) -> String {
+    let from = window.select_from - window.seek_at;
+    let mut filters = match burst {
+        None => vec![format!("select='gte(t,{from:.6})'")],
+        Some(Burst::Interval { interval_secs, .. }) => vec![
+            format!("select='gte(t,{from:.6})'"),
+            format!(
+                "fps=1/{interval_secs}:start_time={:.6}",
+                window.frame_start - window.seek_at
+            ),
+        ],
+        Some(Burst::Range { duration_secs }) => {
+            let to = window.frame_start - window.seek_at + duration_secs;
+            vec![format!("select='between(t,{from:.6},{to:.6})'")]
+        }
+    };
+    match (settings.width, settings.height) {
+        (Some(width), Some(height)) => filters.push(format!("scale={width}:{height}")),
+        (Some(width), None) => filters.push(format!("scale={width}:-2")),
+        (None, Some(height)) => filters.push(format!("scale=-2:{height}")),
+        (None, None) => {}
+    }
+    filters.push("showinfo".to_string());
+    filters.join(",")
+}
+
+fn encoder_args(settings: &CaptureSettings) -> Vec<&'static str> {
+    match (settings.format, settings.lossless) {
+        (CaptureFormat::Jpeg, _) => vec!["-q:v", "2"],
+        (CaptureFormat::Png, _) => vec!["-c:v", "png"],
+        (CaptureFormat::Webp, false) => vec!["-c:v", "libwebp", "-quality", "90"],
+        (CaptureFormat::Webp, true) => vec!["-c:v", "libwebp", "-lossless", "1"],
+    }
+}
+
+/// Time of frame `index` when `FFmpeg` didn't report it.
+fn planned_time(window: &FrameWindow, burst: Option<Burst>, index: usize) -> f64 {
+    match burst {
+        Some(Burst::Interval { interval_secs, .. }) => {
+            let index = f64::from(u32::try_from(index).unwrap_or(u32::MAX));
+            interval_secs.mul_add(index, window.frame_start)
+        }
+        _ => window.frame_start,
+    }
+}
+
+/// Files `FFmpeg` wrote for the capture with `prefix`, in frame order.
+fn numbered_files(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(prefix))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Rejects settings `FFmpeg` can't honour.
+fn validate_settings(settings: &CaptureSettings) -> Result<(), AppError> {
+    if settings.lossless && settings.format == CaptureFormat::Jpeg {
+        return Err(AppError::invalid(
+            "JPEG can't be lossless; choose PNG or WebP",
+        ));
+    }
+    if settings.width == Some(0) || settings.height == Some(0) {
+        return Err(AppError::invalid(
+            "Capture width and height must be positive",
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects bursts that would write no frames or too many.
+fn validate_burst(burst: Burst) -> Result<(), AppError> {
+    let valid = match burst {
+        Burst::Interval {
+            count,
+            interval_secs,
+        } => (1..=MAX_BURST_FRAMES).contains(&count) && interval_secs > 0.0,
+        Burst::Range { duration_secs } => duration_secs > 0.0 && duration_secs <= 60.0,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::invalid(format!(
+            "A burst needs 1 to {MAX_BURST_FRAMES} frames at a positive interval, \
+             or a range of up to 60 seconds"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_window_snaps_to_displayed_frame() {
+        // At 25 fps, 10.07s is inside the frame starting at 10.04s
+        let window = frame_window(10.07, 25.0);
+        assert!((window.frame_start - 10.04).abs() < 1e-9);
+        assert!((window.select_from - 10.02).abs() < 1e-9);
+        assert!((window.seek_at - 9.02).abs() < 1e-9);
+
+        // A time reported exactly at a boundary stays on that frame despite rounding
+        let window = frame_window(0.1 * 3.0, 30.0);
+        assert!((window.frame_start - 0.3).abs() < 1e-9);
+
+        // Near the start nothing is negative
+        let window = frame_window(0.01, 25.0);
+        assert!(window.seek_at.abs() < 1e-9);
+        assert!(window.select_from.abs() < 1e-9);
+
+        // Unknown frame rate keeps the time as is
+        let window = frame_window(5.0, 0.0);
+        assert!((window.frame_start - 5.0).abs() < 1e-9);
+        assert!((window.select_from - 4.999).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_capture_filters() {
+        let window = frame_window(10.07, 25.0);
+        let mut settings = CaptureSettings::default();
+        assert_eq!(
+            capture_filters(&window, &settings, None),
+            "select='gte(t,1.000000)',showinfo"
+        );
+
+        settings.width = Some(1280);
+        let burst = Burst::Interval {
+            count: 5,
+            interval_secs: 2.5,
+        };
+        assert_eq!(
+            capture_filters(&window, &settings, Some(burst)),
+            "select='gte(t,1.000000)',fps=1/2.5:start_time=1.020000,scale=1280:-2,showinfo"
+        );
+
+        settings.height = Some(720);
+        let range = Burst::Range { duration_secs: 1.0 };
+        assert_eq!(
+            capture_filters(&window, &settings, Some(range)),
+            "select='between(t,1.000000,2.020000)',scale=1280:720,showinfo"
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let settings = CaptureSettings {
+            lossless: true,
+            ..CaptureSettings::default()
+        };
+        assert!(validate_settings(&settings).is_err());
+        assert!(validate_settings(&CaptureSettings {
+            format: CaptureFormat::Webp,
+            ..settings
+        })
+        .is_ok());
+
+        assert!(validate_burst(Burst::Interval {
+            count: 0,
+            interval_secs: 1.0
+        })
+        .is_err());
+        assert!(validate_burst(Burst::Range {
+            duration_secs: 120.0
+        })
+        .is_err());
+        assert!(validate_burst(Burst::Range { duration_secs: 2.0 }).is_ok());
+    }
+
+    #[test]
+    fn test_settings_roundtrip() {
+        let dir =
+            std::env::temp_dir().join(format!("video-partitioner-capture-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(load_settings(&dir).unwrap(), CaptureSettings::default());
+        let settings = CaptureSettings {
+            format: CaptureFormat::Png,
+            height: Some(1080),
+            output_dir: Some("/screenshots".to_string()),
+            embed_metadata: false,
+            ..CaptureSettings::default()
+        };
+        save_settings(&dir, &settings).unwrap();
+        assert_eq!(load_settings(&dir).unwrap(), settings);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
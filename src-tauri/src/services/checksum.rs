@@ -0,0 +1,97 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+use crate::models::manifest::{FileHashes, HashAlgorithm};
+
+/// Read buffer size used while hashing.
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Computes the requested digests of a file in a single streaming pass.
+pub fn hash_file(path: &Path, algorithms: &[HashAlgorithm]) -> Result<FileHashes, AppError> {
+    let mut file =
+        File::open(path).map_err(|e| AppError::io("Failed to open for hashing", path, &e))?;
+
+    let mut hasher = MultiHasher::new(algorithms);
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| AppError::io("Failed to read for hashing", path, &e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize())
+}
+
+/// Feeds the same bytes into every requested hash algorithm.
+pub struct MultiHasher {
+    sha256: Option<Sha256>,
+    blake3: Option<blake3::Hasher>,
+}
+
+impl MultiHasher {
+    pub fn new(algorithms: &[HashAlgorithm]) -> Self {
+        Self {
+            sha256: algorithms
+                .contains(&HashAlgorithm::Sha256)
+                .then(Sha256::new),
+            blake3: algorithms
+                .contains(&HashAlgorithm::Blake3)
+                .then(blake3::Hasher::new),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        if let Some(h) = self.sha256.as_mut() {
+            h.update(data);
+        }
+        if let Some(h) = self.blake3.as_mut() {
+            h.update(data);
+        }
+    }
+
+    pub fn finalize(self) -> FileHashes {
+        FileHashes {
+            sha256: self.sha256.map(|h| format!("{:x}", h.finalize())),
+            blake3: self.blake3.map(|h| h.finalize().to_hex().to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests() {
+        let mut hasher = MultiHasher::new(&[HashAlgorithm::Sha256, HashAlgorithm::Blake3]);
+        hasher.update(b"abc");
+        let digests = hasher.finalize();
+
+        assert_eq!(
+            digests.sha256.as_deref(),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            digests.blake3.as_deref(),
+            Some("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85")
+        );
+    }
+
+    #[test]
+    fn test_only_requested_algorithms() {
+        let hasher = MultiHasher::new(&[HashAlgorithm::Blake3]);
+        let digests = hasher.finalize();
+
+        assert!(digests.sha256.is_none());
+        assert!(digests.blake3.is_some());
+    }
+}
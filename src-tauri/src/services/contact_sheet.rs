@@ -0,0 +1,402 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use futures::stream::{self, TryStreamExt};
+
+use crate::error::AppError;
+use crate::models::capture::CaptureFormat;
+use crate::models::contact_sheet::{ContactSheet, ContactSheetOptions};
+use crate::models::partition::cut_list::format_timecode;
+use crate::models::video::VideoMetadata;
+use crate::services::thumbnails::{thumbnail_timestamps, thumbnail_workers};
+use crate::utils::ffmpeg_wrapper::{escape_filter_value, format_ffmpeg_time, run_ffmpeg};
+
+/// Most frames a sheet may hold.
+const MAX_SHEET_FRAMES: u32 = 100;
+
+/// Gap between frames and around the grid, in pixels.
+const SPACING: u32 = 8;
+
+/// Background of the sheet and its header.
+const BACKGROUND: &str = "0x1f2937";
+
+/// Fonts tried in order for the header and timecodes; without one `FFmpeg` falls back to
+/// fontconfig.
+const FONT_CANDIDATES: &[&str] = &[
+    "C:/Windows/Fonts/segoeui.ttf",
+    "C:/Windows/Fonts/arial.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+];
+
+/// Distinguishes the working directories of concurrent sheets.
+static NEXT_SHEET_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Pixel sizes of a sheet's parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SheetLayout {
+    tile_width: u32,
+    tile_height: u32,
+    font_size: u32,
+    header_height: u32,
+}
+
+/// Writes a contact sheet for the video described by `metadata`: a header with its file
+/// name, size, duration, resolution and codecs above a grid of evenly spaced frames,
+/// each labelled with its timecode.
+///
+/// The sheet is named `{stem}_{source ext}_contact.{ext}`, so videos differing only in
+/// extension get separate sheets, and written to the chosen directory or next to the video.
+pub async fn generate_contact_sheet(
+    metadata: &VideoMetadata,
+    options: &ContactSheetOptions,
+) -> Result<ContactSheet, AppError> {
+    validate_options(options)?;
+    if metadata.duration_secs <= 0.0 {
+        return Err(AppError::invalid("Video has no duration"));
+    }
+
+    let input = Path::new(&metadata.file_path);
+    let output_dir = match &options.output_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => input
+            .parent()
+            .ok_or_else(|| AppError::invalid("Cannot determine parent directory"))?
+            .to_path_buf(),
+    };
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| AppError::io("Failed to create output directory", &output_dir, &e))?;
+    let output = output_dir.join(sheet_file_name(input, options.format.extension()));
+
+    let work_dir = std::env::temp_dir().join(format!(
+        "video-partitioner-contact-{}-{}",
+        std::process::id(),
+        NEXT_SHEET_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| AppError::io("Failed to create temporary directory", &work_dir, &e))?;
+
+    let result = render(metadata, options, &work_dir, &output).await;
+    let _ = std::fs::remove_dir_all(&work_dir);
+    result?;
+
+    Ok(ContactSheet {
+        video_path: metadata.file_path.clone(),
+        path: output.to_string_lossy().to_string(),
+    })
+}
+
+/// File name of the sheet for `input`, e.g. `talk_mkv_contact.jpg`.
+fn sheet_file_name(input: &Path, extension: &str) -> String {
+    let stem = input
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video");
+    input.extension().and_then(|e| e.to_str()).map_or_else(
+        || format!("{stem}_contact.{extension}"),
+        |source_ext| format!("{stem}_{source_ext}_contact.{extension}"),
+    )
+}
+
+/// Extracts the labelled frames into `work_dir`, then tiles them under the header.
+async fn render(
+    metadata: &VideoMetadata,
+    options: &ContactSheetOptions,
+    work_dir: &Path,
+    output: &Path,
+) -> Result<(), AppError> {
+    let layout = sheet_layout(options, metadata.width, metadata.height);
+    let font = font_option();
+    let count = options.columns * options.rows;
+    let timestamps = thumbnail_timestamps(metadata.duration_secs, count);
+
+    stream::iter(timestamps.into_iter().enumerate().map(Ok))
+        .try_for_each_concurrent(thumbnail_workers(), |(index, timestamp)| {
+            let frame = work_dir.join(format!("frame_{:04}.png", index + 1));
+            let filter = format!(
+                "scale={}:{},setsar=1,drawtext={font}text={}:expansion=none:fontsize={}:\
+                 fontcolor=white:box=1:boxcolor=black@0.6:boxborderw=4:x=w-tw-8:y=h-th-8",
+                layout.tile_width,
+                layout.tile_height,
+                escape_filter_value(&format_timecode(timestamp)),
+                layout.font_size * 3 / 4,
+            );
+            async move { extract_frame(&metadata.file_path, timestamp, &filter, &frame).await }
+        })
+        .await?;
+
+    let header_file = work_dir.join("header.txt");
+    std::fs::write(&header_file, header_text(metadata))
+        .map_err(|e| AppError::io("Failed to write contact sheet header", &header_file, &e))?;
+
+    let filter = format!(
+        "tile={columns}x{rows}:margin={SPACING}:padding={SPACING}:color={BACKGROUND},\
+         pad=iw:ih+{header}:0:{header}:color={BACKGROUND},\
+         drawtext={font}textfile={text}:expansion=none:fontsize={size}:fontcolor=white:\
+         line_spacing={spacing}:x={SPACING}:y={SPACING}",
+        columns = options.columns,
+        rows = options.rows,
+        header = layout.header_height,
+        text = escape_filter_value(&filter_path(&header_file)),
+        size = layout.font_size,
+        spacing = layout.font_size / 2,
+    );
+    let pattern = work_dir
+        .join("frame_%04d.png")
+        .to_string_lossy()
+        .to_string();
+    let output_str = output.to_string_lossy().to_string();
+
+    let mut args = vec![
+        "-hide_banner",
+        "-framerate",
+        "1",
+        "-i",
+        &pattern,
+        "-vf",
+        &filter,
+        "-frames:v",
+        "1",
+    ];
+    args.extend(encoder_args(options.format));
+    args.extend(["-y", &output_str]);
+
+    let (_, stderr, exit_code) = run_ffmpeg(&args).await?;
+    if exit_code != Some(0) || !output.exists() {
+        return Err(AppError::ffmpeg(
+            "Contact sheet failed",
+            Some(&metadata.file_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Writes the frame at `timestamp`, passed through `filter`, to `output`.
+async fn extract_frame(
+    video_path: &str,
+    timestamp: f64,
+    filter: &str,
+    output: &Path,
+) -> Result<(), AppError> {
+    let ss_arg = format_ffmpeg_time(timestamp);
+    let output_str = output.to_string_lossy().to_string();
+    let (_, stderr, exit_code) = run_ffmpeg(&[
+        "-hide_banner",
+        "-ss",
+        &ss_arg,
+        "-i",
+        video_path,
+        "-frames:v",
+        "1",
+        "-vf",
+        filter,
+        "-y",
+        &output_str,
+    ])
+    .await?;
+
+    if exit_code != Some(0) || !output.exists() {
+        return Err(AppError::ffmpeg(
+            &format!("Contact sheet frame at {ss_arg} failed"),
+            Some(video_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Sizes the tiles to fill `options.width` and the header to fit two lines of text.
+/// Unknown source dimensions are assumed to be 16:9.
+fn sheet_layout(
+    options: &ContactSheetOptions,
+    source_width: u32,
+    source_height: u32,
+) -> SheetLayout {
+    let gaps = SPACING * (options.columns + 1);
+    let tile_width = (options.width.saturating_sub(gaps) / options.columns).max(2) & !1;
+    let (w, h) = if source_width > 0 && source_height > 0 {
+        (u64::from(source_width), u64::from(source_height))
+    } else {
+        (16, 9)
+    };
+    let tile_height = u32::try_from(u64::from(tile_width) * h / w)
+        .unwrap_or(u32::MAX)
+        .max(2)
+        & !1;
+    let font_size = (options.width / 60).max(14);
+    SheetLayout {
+        tile_width,
+        tile_height,
+        font_size,
+        header_height: font_size * 3 + SPACING * 2,
+    }
+}
+
+/// File name on the first line, then size, duration, resolution, codecs and rates.
+fn header_text(metadata: &VideoMetadata) -> String {
+    let mut details = vec![
+        format_size(metadata.file_size),
+        format_timecode(metadata.duration_secs),
+        format!("{}x{}", metadata.width, metadata.height),
+        format!(
+            "{} / {}",
+            metadata.video_codec,
+            metadata.audio_codec.as_deref().unwrap_or("no audio")
+        ),
+    ];
+    if metadata.frame_rate > 0.0 {
+        details.push(format!("{:.3} fps", metadata.frame_rate));
+    }
+    if metadata.bitrate > 0 {
+        details.push(format!("{} kb/s", metadata.bitrate / 1000));
+    }
+    format!("{}\n{}", metadata.file_name, details.join("  |  "))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
+/// `fontfile=...:` for the first installed candidate font, or nothing.
+fn font_option() -> String {
+    FONT_CANDIDATES
+        .iter()
+        .map(Path::new)
+        .find(|path| path.exists())
+        .map(|path| format!("fontfile={}:", escape_filter_value(&filter_path(path))))
+        .unwrap_or_default()
+}
+
+/// `path` with forward slashes, which `FFmpeg` accepts on every platform and which
+/// need no escaping.
+fn filter_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn encoder_args(format: CaptureFormat) -> Vec<&'static str> {
+    match format {
+        CaptureFormat::Jpeg => vec!["-q:v", "3"],
+        CaptureFormat::Png => vec!["-c:v", "png"],
+        CaptureFormat::Webp => vec!["-c:v", "libwebp", "-quality", "85"],
+    }
+}
+
+fn validate_options(options: &ContactSheetOptions) -> Result<(), AppError> {
+    let frames = options.columns.saturating_mul(options.rows);
+    if options.columns == 0 || options.rows == 0 || frames > MAX_SHEET_FRAMES {
+        return Err(AppError::invalid(format!(
+            "A contact sheet needs between 1 and {MAX_SHEET_FRAMES} frames"
+        )));
+    }
+    if options.width < options.columns * 32 {
+        return Err(AppError::invalid(format!(
+            "A sheet with {} columns needs to be at least {} pixels wide",
+            options.columns,
+            options.columns * 32
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> VideoMetadata {
+        VideoMetadata {
+            file_path: "/videos/holiday.mkv".to_string(),
+            file_name: "holiday.mkv".to_string(),
+            file_size: 3 * 1024 * 1024 * 1024 / 2,
+            duration_secs: 3725.5,
+            width: 1920,
+            height: 1080,
+            video_codec: "hevc".to_string(),
+            audio_codec: Some("aac".to_string()),
+            bitrate: 3_500_000,
+            format: "mkv".to_string(),
+            frame_rate: 25.0,
+        }
+    }
+
+    #[test]
+    fn test_sheet_layout() {
+        let layout = sheet_layout(&ContactSheetOptions::default(), 1920, 1080);
+        // 1600 pixels minus five 8-pixel gaps over four columns
+        assert_eq!(layout.tile_width, 390);
+        assert_eq!(layout.tile_height, 218);
+        assert_eq!(layout.font_size, 26);
+        assert_eq!(layout.header_height, 94);
+
+        // Portrait video, unknown dimensions
+        assert_eq!(
+            sheet_layout(&ContactSheetOptions::default(), 1080, 1920).tile_height,
+            692
+        );
+        assert_eq!(
+            sheet_layout(&ContactSheetOptions::default(), 0, 0).tile_height,
+            218
+        );
+    }
+
+    #[test]
+    fn test_header_text() {
+        assert_eq!(
+            header_text(&metadata()),
+            "holiday.mkv\n1.50 GB  |  01:02:05.500  |  1920x1080  |  hevc / aac  |  \
+             25.000 fps  |  3500 kb/s"
+        );
+
+        let silent = VideoMetadata {
+            audio_codec: None,
+            frame_rate: 0.0,
+            bitrate: 0,
+            ..metadata()
+        };
+        assert!(header_text(&silent).ends_with("1920x1080  |  hevc / no audio"));
+    }
+
+    #[test]
+    fn test_sheet_file_name() {
+        assert_eq!(
+            sheet_file_name(Path::new("/v/a.mp4"), "jpg"),
+            "a_mp4_contact.jpg"
+        );
+        assert_eq!(
+            sheet_file_name(Path::new("/v/a.mkv"), "jpg"),
+            "a_mkv_contact.jpg"
+        );
+        assert_eq!(sheet_file_name(Path::new("/v/a"), "png"), "a_contact.png");
+    }
+
+    #[test]
+    fn test_validation() {
+        let options = ContactSheetOptions {
+            columns: 20,
+            rows: 10,
+            ..ContactSheetOptions::default()
+        };
+        assert!(validate_options(&options).is_err());
+        assert!(validate_options(&ContactSheetOptions {
+            width: 100,
+            ..ContactSheetOptions::default()
+        })
+        .is_err());
+        assert!(validate_options(&ContactSheetOptions::default()).is_ok());
+    }
+}
@@ -0,0 +1,178 @@
+use crate::error::AppError;
+use crate::models::partition::cut_list::format_timecode;
+use crate::models::partition::{NormalizedExclusions, TimeInterval};
+
+/// Validates exclusions and brings them into the form the calculator and splitter expect.
+///
+/// Intervals are clamped to `[0, duration_secs]`, empty or out-of-range ones are dropped
+/// and overlapping ones are merged; each adjustment produces a warning. Inverted or
+/// non-finite intervals are rejected. A non-positive `duration_secs` disables clamping
+/// at the end.
+pub fn normalize_exclusions(
+    exclusions: &[TimeInterval],
+    duration_secs: f64,
+) -> Result<NormalizedExclusions, AppError> {
+    let has_duration = duration_secs.is_finite() && duration_secs > 0.0;
+    let mut warnings = Vec::new();
+    // Original 1-based numbers are kept so warnings refer to what the user sees
+    let mut numbered: Vec<(usize, TimeInterval)> = Vec::with_capacity(exclusions.len());
+
+    for (i, excl) in exclusions.iter().enumerate() {
+        let n = i + 1;
+        if !excl.start_secs.is_finite() || !excl.end_secs.is_finite() {
+            return Err(AppError::invalid(format!("Exclusion {n} has an invalid time")));
+        }
+        if excl.end_secs < excl.start_secs {
+            return Err(AppError::invalid(format!(
+                "Exclusion {n} ends before it starts ({} - {})",
+                format_timecode(excl.start_secs),
+                format_timecode(excl.end_secs)
+            )));
+        }
+
+        if excl.end_secs <= excl.start_secs {
+            warnings.push(format!("Exclusion {n} is empty and was ignored"));
+            continue;
+        }
+        if excl.end_secs <= 0.0 || (has_duration && excl.start_secs >= duration_secs) {
+            warnings.push(format!(
+                "Exclusion {n} lies outside the video and was ignored"
+            ));
+            continue;
+        }
+
+        let mut interval = excl.clone();
+        if interval.start_secs < 0.0 {
+            interval.start_secs = 0.0;
+            warnings.push(format!(
+                "Exclusion {n} starts before the video and was clamped to 00:00:00.000"
+            ));
+        }
+        if has_duration && interval.end_secs > duration_secs {
+            interval.end_secs = duration_secs;
+            warnings.push(format!(
+                "Exclusion {n} ends after the video and was clamped to {}",
+                format_timecode(duration_secs)
+            ));
+        }
+
+        numbered.push((n, interval));
+    }
+
+    numbered.sort_by(|a, b| a.1.start_secs.total_cmp(&b.1.start_secs));
+
+    let mut intervals: Vec<TimeInterval> = Vec::with_capacity(numbered.len());
+    let mut last_number = 0;
+    for (n, interval) in numbered {
+        match intervals.last_mut() {
+            Some(last) if interval.start_secs <= last.end_secs => {
+                // Touching intervals are joined silently; only real overlaps are reported
+                if interval.start_secs < last.end_secs {
+                    warnings.push(format!(
+                        "Exclusions {} and {} overlap and were merged",
+                        last_number.min(n),
+                        last_number.max(n)
+                    ));
+                }
+                last.end_secs = last.end_secs.max(interval.end_secs);
+            }
+            _ => {
+                intervals.push(interval);
+                last_number = n;
+            }
+        }
+    }
+
+    Ok(NormalizedExclusions {
+        intervals,
+        warnings,
+    })
+}
+
+/// Total length of the union of `exclusions`, counting overlapping time once.
+pub fn union_duration(exclusions: &[TimeInterval]) -> f64 {
+    let mut sorted: Vec<&TimeInterval> = exclusions
+        .iter()
+        .filter(|e| e.end_secs > e.start_secs)
+        .collect();
+    sorted.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+
+    let mut total = 0.0;
+    let mut covered_until = f64::NEG_INFINITY;
+    for excl in sorted {
+        let start = excl.start_secs.max(covered_until);
+        if excl.end_secs > start {
+            total += excl.end_secs - start;
+            covered_until = excl.end_secs;
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start_secs: f64, end_secs: f64) -> TimeInterval {
+        TimeInterval {
+            start_secs,
+            end_secs,
+        }
+    }
+
+    #[test]
+    fn test_clamps_and_merges() {
+        let normalized = normalize_exclusions(
+            &[
+                interval(50.0, 70.0),
+                interval(-5.0, 10.0),
+                interval(60.0, 80.0),
+                interval(90.0, 120.0),
+            ],
+            100.0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            normalized.intervals,
+            vec![
+                interval(0.0, 10.0),
+                interval(50.0, 80.0),
+                interval(90.0, 100.0)
+            ]
+        );
+        assert_eq!(
+            normalized.warnings,
+            vec![
+                "Exclusion 2 starts before the video and was clamped to 00:00:00.000",
+                "Exclusion 4 ends after the video and was clamped to 00:01:40.000",
+                "Exclusions 1 and 3 overlap and were merged",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drops_empty_and_out_of_range() {
+        let normalized =
+            normalize_exclusions(&[interval(5.0, 5.0), interval(120.0, 130.0)], 100.0).unwrap();
+
+        assert!(normalized.intervals.is_empty());
+        assert_eq!(normalized.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_rejects_inverted() {
+        assert!(normalize_exclusions(&[interval(20.0, 10.0)], 100.0).is_err());
+        assert!(normalize_exclusions(&[interval(f64::NAN, 10.0)], 100.0).is_err());
+    }
+
+    #[test]
+    fn test_union_duration_counts_overlap_once() {
+        let total = union_duration(&[
+            interval(0.0, 20.0),
+            interval(10.0, 30.0),
+            interval(40.0, 50.0),
+        ]);
+        assert!((total - 40.0).abs() < f64::EPSILON);
+    }
+}
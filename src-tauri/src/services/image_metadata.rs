@@ -0,0 +1,300 @@
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::models::capture::CaptureFormat;
+
+/// Name written as the creating software.
+const SOFTWARE: &str = "Video Partitioner";
+
+/// Where a captured frame came from.
+pub struct FrameOrigin<'a> {
+    /// Path of the source video
+    pub source: &'a str,
+    /// Position of the frame as `HH:MM:SS.mmm`
+    pub timecode: &'a str,
+}
+
+/// Writes `origin` into the metadata of the image at `path`: `iTXt` chunks for PNG, a
+/// comment segment for JPEG and an XMP packet for WebP.
+pub fn embed(path: &Path, format: CaptureFormat, origin: &FrameOrigin) -> Result<(), AppError> {
+    let data = std::fs::read(path).map_err(|e| AppError::io("Failed to read image", path, &e))?;
+    let tagged = match format {
+        CaptureFormat::Png => tag_png(&data, origin),
+        CaptureFormat::Jpeg => tag_jpeg(&data, origin),
+        CaptureFormat::Webp => tag_webp(&data, origin),
+    }
+    .ok_or_else(|| {
+        AppError::internal(format!(
+            "Unexpected image layout, cannot embed metadata: {}",
+            path.display()
+        ))
+    })?;
+    std::fs::write(path, tagged).map_err(|e| AppError::io("Failed to write image", path, &e))
+}
+
+/// Inserts one `iTXt` chunk per field right after the `IHDR` chunk.
+fn tag_png(data: &[u8], origin: &FrameOrigin) -> Option<Vec<u8>> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    // Signature, then IHDR: length, type, 13 data bytes and CRC
+    const IHDR_END: usize = 8 + 4 + 4 + 13 + 4;
+    if !data.starts_with(SIGNATURE) || data.get(12..16)? != b"IHDR" || data.len() < IHDR_END {
+        return None;
+    }
+
+    let mut tagged = data[..IHDR_END].to_vec();
+    for (keyword, text) in [
+        ("Source", origin.source),
+        ("Timecode", origin.timecode),
+        ("Software", SOFTWARE),
+    ] {
+        // Keyword, no compression, empty language tag and translated keyword, UTF-8 text
+        let mut body = b"iTXt".to_vec();
+        body.extend_from_slice(keyword.as_bytes());
+        body.extend_from_slice(&[0, 0, 0, 0, 0]);
+        body.extend_from_slice(text.as_bytes());
+
+        tagged.extend_from_slice(&u32::try_from(body.len() - 4).ok()?.to_be_bytes());
+        tagged.extend_from_slice(&body);
+        tagged.extend_from_slice(&crc32(&body).to_be_bytes());
+    }
+    tagged.extend_from_slice(&data[IHDR_END..]);
+    Some(tagged)
+}
+
+/// Inserts a comment segment after the start-of-image marker and any leading
+/// `APP0`/`APP1` segments, so a JFIF `APP0` stays right after the marker.
+fn tag_jpeg(data: &[u8], origin: &FrameOrigin) -> Option<Vec<u8>> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let comment = format!(
+        "Source: {}\nTimecode: {}\nSoftware: {SOFTWARE}",
+        origin.source, origin.timecode
+    );
+    // The segment length includes its own two bytes and can't exceed 16 bits
+    let mut comment = comment.into_bytes();
+    comment.truncate(usize::from(u16::MAX) - 2);
+    let length = u16::try_from(comment.len() + 2).ok()?;
+
+    let mut insert_at = 2;
+    while let [0xFF, 0xE0 | 0xE1, high, low, ..] = data[insert_at..] {
+        insert_at += 2 + usize::from(u16::from_be_bytes([high, low]));
+        if insert_at > data.len() {
+            return None;
+        }
+    }
+
+    let mut tagged = Vec::with_capacity(data.len() + comment.len() + 4);
+    tagged.extend_from_slice(&data[..insert_at]);
+    tagged.extend_from_slice(&[0xFF, 0xFE]);
+    tagged.extend_from_slice(&length.to_be_bytes());
+    tagged.extend_from_slice(&comment);
+    tagged.extend_from_slice(&data[insert_at..]);
+    Some(tagged)
+}
+
+/// Appends an `XMP ` chunk, first converting a simple WebP into the extended format
+/// whose `VP8X` header announces it.
+fn tag_webp(data: &[u8], origin: &FrameOrigin) -> Option<Vec<u8>> {
+    const XMP_FLAG: u8 = 0x04;
+    const ALPHA_FLAG: u8 = 0x10;
+    if data.len() < 20 || &data[..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return None;
+    }
+
+    let first_chunk = &data[12..16];
+    let mut tagged = data[..12].to_vec();
+    if first_chunk == b"VP8X" {
+        tagged.extend_from_slice(&data[12..]);
+        tagged[20] |= XMP_FLAG;
+    } else {
+        let (width, height, alpha) = webp_canvas(first_chunk, &data[20..])?;
+        let mut flags = XMP_FLAG;
+        if alpha {
+            flags |= ALPHA_FLAG;
+        }
+        tagged.extend_from_slice(b"VP8X");
+        tagged.extend_from_slice(&10u32.to_le_bytes());
+        tagged.extend_from_slice(&[flags, 0, 0, 0]);
+        tagged.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+        tagged.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+        tagged.extend_from_slice(&data[12..]);
+    }
+
+    let xmp = xmp_packet(origin).into_bytes();
+    tagged.extend_from_slice(b"XMP ");
+    tagged.extend_from_slice(&u32::try_from(xmp.len()).ok()?.to_le_bytes());
+    tagged.extend_from_slice(&xmp);
+    if xmp.len() % 2 == 1 {
+        tagged.push(0);
+    }
+
+    let riff_size = u32::try_from(tagged.len() - 8).ok()?;
+    tagged[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(tagged)
+}
+
+/// Reads the canvas width, height and alpha use from a `VP8 ` or `VP8L` bitstream.
+/// Truncated bitstreams and zero dimensions give `None`.
+fn webp_canvas(chunk: &[u8], bitstream: &[u8]) -> Option<(u32, u32, bool)> {
+    match chunk {
+        b"VP8 " => {
+            // Frame tag, start code 9d 01 2a, then 14-bit width and height
+            if bitstream.get(3..6)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+            let size = bitstream.get(6..10)?;
+            let width = u16::from_le_bytes([size[0], size[1]]) & 0x3FFF;
+            let height = u16::from_le_bytes([size[2], size[3]]) & 0x3FFF;
+            if width == 0 || height == 0 {
+                return None;
+            }
+            Some((u32::from(width), u32::from(height), false))
+        }
+        b"VP8L" => {
+            // Signature 0x2f, then 14 bits width - 1, 14 bits height - 1 and the alpha bit
+            if *bitstream.first()? != 0x2F {
+                return None;
+            }
+            let bits = u32::from_le_bytes(bitstream.get(1..5)?.try_into().ok()?);
+            let width = (bits & 0x3FFF) + 1;
+            let height = ((bits >> 14) & 0x3FFF) + 1;
+            Some((width, height, bits & (1 << 28) != 0))
+        }
+        _ => None,
+    }
+}
+
+fn xmp_packet(origin: &FrameOrigin) -> String {
+    format!(
+        concat!(
+            "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>",
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">",
+            "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">",
+            "<rdf:Description rdf:about=\"\"",
+            " xmlns:dc=\"http://purl.org/dc/elements/1.1/\"",
+            " xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"",
+            " dc:source=\"{source}\" xmp:CreatorTool=\"{software}\">",
+            "<dc:description><rdf:Alt><rdf:li xml:lang=\"x-default\">Frame at {timecode}",
+            "</rdf:li></rdf:Alt></dc:description>",
+            "</rdf:Description></rdf:RDF></x:xmpmeta><?xpacket end=\"r\"?>"
+        ),
+        source = xml_escape(origin.source),
+        software = SOFTWARE,
+        timecode = xml_escape(origin.timecode),
+    )
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// CRC-32 as used by PNG chunks.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGIN: FrameOrigin = FrameOrigin {
+        source: "C:\\videos\\a & b.mp4",
+        timecode: "00:01:02.500",
+    };
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn test_png_chunks_follow_ihdr() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&[0; 13 + 4]);
+        png.extend_from_slice(b"\0\0\0\0IEND\xae\x42\x60\x82");
+
+        let tagged = tag_png(&png, &ORIGIN).unwrap();
+        assert_eq!(&tagged[37..41], b"iTXt");
+        assert_eq!(&tagged[41..48], b"Source\0");
+        assert!(tagged.ends_with(b"IEND\xae\x42\x60\x82"));
+
+        let first_length = u32::from_be_bytes(tagged[33..37].try_into().unwrap()) as usize;
+        let crc_at = 37 + 4 + first_length;
+        let expected = crc32(&tagged[37..crc_at]);
+        assert_eq!(tagged[crc_at..crc_at + 4], expected.to_be_bytes());
+
+        assert!(tag_png(b"not a png", &ORIGIN).is_none());
+    }
+
+    #[test]
+    fn test_jpeg_comment_after_app_segments() {
+        let tagged = tag_jpeg(&[0xFF, 0xD8, 0xFF, 0xD9], &ORIGIN).unwrap();
+        assert_eq!(tagged[..4], [0xFF, 0xD8, 0xFF, 0xFE]);
+        let length = usize::from(u16::from_be_bytes([tagged[4], tagged[5]]));
+        let comment = std::str::from_utf8(&tagged[6..4 + length]).unwrap();
+        assert!(comment.starts_with("Source: C:\\videos\\a & b.mp4\nTimecode: 00:01:02.500"));
+        assert_eq!(tagged[4 + length..], [0xFF, 0xD9]);
+
+        // A JFIF APP0 stays at offset 2, directly after SOI
+        let app0 = [0xFF, 0xE0, 0x00, 0x07, b'J', b'F', b'I', b'F', 0x00];
+        let jfif = [&[0xFF, 0xD8][..], &app0, &[0xFF, 0xD9]].concat();
+        let tagged = tag_jpeg(&jfif, &ORIGIN).unwrap();
+        assert_eq!(tagged[2..11], app0);
+        assert_eq!(tagged[11..13], [0xFF, 0xFE]);
+        assert_eq!(tagged[tagged.len() - 2..], [0xFF, 0xD9]);
+
+        // A segment length running past the end is rejected
+        assert!(tag_jpeg(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x40], &ORIGIN).is_none());
+    }
+
+    #[test]
+    fn test_simple_webp_becomes_extended() {
+        // Lossless 300x200 bitstream with alpha: width - 1 = 299, height - 1 = 199
+        let bits: u32 = 0x12B | (0xC7 << 14) | (0x1 << 28);
+        let mut vp8l = vec![0x2F];
+        vp8l.extend_from_slice(&bits.to_le_bytes());
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8L".to_vec();
+        webp.extend_from_slice(&5u32.to_le_bytes());
+        webp.extend_from_slice(&vp8l);
+        webp.push(0);
+
+        let tagged = tag_webp(&webp, &ORIGIN).unwrap();
+        assert_eq!(&tagged[12..16], b"VP8X");
+        assert_eq!(tagged[20], 0x04 | 0x10);
+        assert_eq!(tagged[24..27], [43, 1, 0]);
+        assert_eq!(tagged[27..30], [199, 0, 0]);
+        assert_eq!(&tagged[30..34], b"VP8L");
+
+        let riff_size = u32::from_le_bytes(tagged[4..8].try_into().unwrap()) as usize;
+        assert_eq!(riff_size, tagged.len() - 8);
+        let xmp = String::from_utf8_lossy(&tagged[30 + 8 + 6..]);
+        assert!(xmp.starts_with("XMP "));
+        assert!(xmp.contains("dc:source=\"C:\\videos\\a &amp; b.mp4\""));
+    }
+
+    #[test]
+    fn test_vp8_canvas_rejects_truncated_and_empty_frames() {
+        let frame = [0x30, 0x01, 0x00, 0x9D, 0x01, 0x2A, 0x2C, 0x01, 0xC8, 0x00];
+        assert_eq!(webp_canvas(b"VP8 ", &frame), Some((300, 200, false)));
+        assert_eq!(webp_canvas(b"VP8 ", &frame[..7]), None);
+        let mut zero_width = frame;
+        zero_width[6..8].fill(0);
+        assert_eq!(webp_canvas(b"VP8 ", &zero_width), None);
+    }
+}
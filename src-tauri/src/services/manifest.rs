@@ -0,0 +1,139 @@
+use std::fmt::Write as _;
+use std::path::{Component, Path};
+
+use crate::error::AppError;
+use crate::models::manifest::SplitManifest;
+
+/// Writes the JSON manifest and, if SHA-256 digests are present, a `.sha256` sidecar
+/// that can be verified with `sha256sum -c` from inside `output_dir`.
+///
+/// Returns the path of the JSON manifest.
+pub fn write_manifest(
+    manifest: &SplitManifest,
+    output_dir: &Path,
+    file_stem: &str,
+) -> Result<String, AppError> {
+    let json = serde_json::to_string_pretty(manifest)
+        .map_err(|e| AppError::internal(format!("Failed to serialize manifest: {e}")))?;
+
+    let manifest_path = output_dir.join(format!("{file_stem}_manifest.json"));
+    std::fs::write(&manifest_path, json)
+        .map_err(|e| AppError::io("Failed to write manifest", &manifest_path, &e))?;
+
+    if let Some(sidecar) = render_sha256_sidecar(manifest) {
+        let sidecar_path = output_dir.join(format!("{file_stem}_parts.sha256"));
+        std::fs::write(&sidecar_path, sidecar)
+            .map_err(|e| AppError::io("Failed to write checksum file", &sidecar_path, &e))?;
+    }
+
+    Ok(manifest_path.to_string_lossy().to_string())
+}
+
+/// Reads and parses a JSON manifest written by [`write_manifest`].
+///
+/// Partition names must be plain file names, so a crafted manifest can't make
+/// reassembly read or hash files outside the manifest's directory.
+pub fn read_manifest(path: &Path) -> Result<SplitManifest, AppError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::io("Failed to read manifest", path, &e))?;
+    let manifest: SplitManifest = serde_json::from_str(&content)
+        .map_err(|e| AppError::invalid(format!("Invalid manifest: {e}")))?;
+
+    if let Some(partition) = manifest
+        .partitions
+        .iter()
+        .find(|p| !is_plain_file_name(&p.file_name))
+    {
+        return Err(AppError::invalid(format!(
+            "Invalid manifest: partition name {:?} is not a plain file name",
+            partition.file_name
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Whether `name` is a single file name, with no directories, root or `..`.
+pub fn is_plain_file_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
+
+/// Renders partition digests in `sha256sum` format (`<hex>  <file name>` per line).
+///
+/// Returns `None` when no partition has a SHA-256 digest.
+pub fn render_sha256_sidecar(manifest: &SplitManifest) -> Option<String> {
+    let mut content = String::new();
+    for partition in &manifest.partitions {
+        if let Some(hash) = &partition.hashes.sha256 {
+            let _ = writeln!(content, "{hash}  {}", partition.file_name);
+        }
+    }
+
+    if content.is_empty() {
+        None
+    } else {
+        Some(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::manifest::{
+        FileHashes, ManifestKind, ManifestPartition, ManifestSource, MANIFEST_VERSION,
+    };
+    use crate::models::preset::EncodeProfile;
+
+    fn make_manifest(sha256: Option<&str>) -> SplitManifest {
+        SplitManifest {
+            version: MANIFEST_VERSION,
+            kind: ManifestKind::Video,
+            encode_profile: EncodeProfile::Copy,
+            source: ManifestSource {
+                file_name: "movie.mp4".to_string(),
+                size_bytes: 100,
+                duration_secs: 10.0,
+                streams: Vec::new(),
+                hashes: FileHashes::default(),
+            },
+            partitions: vec![ManifestPartition {
+                index: 0,
+                file_name: "movie_part1.mp4".to_string(),
+                size_bytes: 100,
+                segments: Vec::new(),
+                hashes: FileHashes {
+                    sha256: sha256.map(str::to_string),
+                    blake3: None,
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn test_plain_file_names() {
+        assert!(is_plain_file_name("movie_part1.mp4"));
+        assert!(!is_plain_file_name("../movie_part1.mp4"));
+        assert!(!is_plain_file_name("/etc/passwd"));
+        assert!(!is_plain_file_name("parts/movie_part1.mp4"));
+        assert!(!is_plain_file_name(".."));
+        assert!(!is_plain_file_name(""));
+    }
+
+    #[test]
+    fn test_sidecar_format() {
+        let manifest = make_manifest(Some("abc123"));
+        assert_eq!(
+            render_sha256_sidecar(&manifest).as_deref(),
+            Some("abc123  movie_part1.mp4\n")
+        );
+    }
+
+    #[test]
+    fn test_sidecar_without_sha256() {
+        let manifest = make_manifest(None);
+        assert!(render_sha256_sidecar(&manifest).is_none());
+    }
+}
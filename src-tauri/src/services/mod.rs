@@ -1,2 +1,21 @@
+pub mod analysis;
+pub mod animation;
+pub mod audio;
+pub mod byte_splitter;
 pub mod calculator;
+pub mod capture;
+pub mod checksum;
+pub mod contact_sheet;
+pub mod exclusions;
+pub mod image_metadata;
+pub mod manifest;
+pub mod preflight;
+pub mod presets;
+pub mod preview;
+pub mod projects;
+pub mod reassembler;
+pub mod scratch;
 pub mod splitter;
+pub mod thumbnail_cache;
+pub mod thumbnails;
+pub mod transcode;
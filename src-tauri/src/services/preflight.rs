@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::models::partition::PreflightReport;
+
+/// Extra space reserved for container overhead, manifests and estimation error.
+const SAFETY_MARGIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Computes the space a split needs and whether the output volume can hold it.
+///
+/// Partitions are written straight from the source, so the requirement is the sum
+/// of the estimated partition sizes plus a safety margin.
+pub fn estimate_disk_space(partition_sizes: &[u64], available_bytes: u64) -> PreflightReport {
+    let partition_bytes: u64 = partition_sizes.iter().sum();
+    let required_bytes = partition_bytes + SAFETY_MARGIN_BYTES;
+
+    PreflightReport {
+        required_bytes,
+        partition_bytes,
+        available_bytes,
+        sufficient: required_bytes <= available_bytes,
+    }
+}
+
+/// Returns the free space available to the current user on the volume containing `path`.
+///
+/// `path` does not need to exist yet; its nearest existing ancestor is queried.
+pub fn available_space(path: &Path) -> Result<u64, AppError> {
+    let existing = existing_ancestor(path);
+    fs2::available_space(existing)
+        .map_err(|e| AppError::io("Failed to query free space for", existing, &e))
+}
+
+/// Walks up from `path` to the first ancestor that exists.
+fn existing_ancestor(path: &Path) -> &Path {
+    path.ancestors().find(|p| p.exists()).unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_required_space_includes_margin() {
+        let report = estimate_disk_space(&[4 * GB, 3 * GB, 2 * GB], 100 * GB);
+
+        assert_eq!(report.partition_bytes, 9 * GB);
+        assert_eq!(report.required_bytes, 9 * GB + SAFETY_MARGIN_BYTES);
+        assert!(report.sufficient);
+    }
+
+    #[test]
+    fn test_insufficient_space() {
+        let report = estimate_disk_space(&[4 * GB], 4 * GB);
+
+        assert!(!report.sufficient);
+    }
+}
@@ -0,0 +1,285 @@
+use std::path::Path;
+
+use crate::error::AppError;
+
+use crate::models::preset::{
+    EncodeProfile, PresetFile, SplitPreset, SplitStrategy, DEFAULT_NAMING_TEMPLATE, PRESETS_VERSION,
+};
+
+/// File name of the presets store inside the app config directory.
+const PRESETS_FILE_NAME: &str = "presets.json";
+
+const MIB: f64 = 1024.0 * 1024.0;
+const GB: f64 = 1_000_000_000.0;
+
+/// Presets offered before the user has saved any of their own.
+pub fn builtin_presets() -> Vec<SplitPreset> {
+    let preset = |name: &str, strategy, target| SplitPreset {
+        name: name.to_string(),
+        strategy,
+        target,
+        encode_profile: EncodeProfile::Copy,
+        naming_template: DEFAULT_NAMING_TEMPLATE.to_string(),
+        output_dir: None,
+    };
+
+    vec![
+        // FAT32 caps files at 4 GiB minus one byte; leave 16 MiB for estimation error
+        preset("FAT32 (4 GiB)", SplitStrategy::Size, 4080.0 * MIB),
+        preset("Email upload (2 GB)", SplitStrategy::Size, 2.0 * GB),
+        preset("Blu-ray (25 GB)", SplitStrategy::Size, 25.0 * GB),
+        preset("LMS (15 minutes)", SplitStrategy::Duration, 15.0 * 60.0),
+    ]
+}
+
+/// Loads the saved presets from `config_dir`, falling back to [`builtin_presets`]
+/// when nothing has been saved yet.
+pub fn load_presets(config_dir: &Path) -> Result<Vec<SplitPreset>, AppError> {
+    let path = config_dir.join(PRESETS_FILE_NAME);
+    if !path.exists() {
+        return Ok(builtin_presets());
+    }
+
+    Ok(read_preset_file(&path)?.presets)
+}
+
+/// Adds a new preset. Fails if one with the same name exists.
+pub fn create_preset(config_dir: &Path, preset: SplitPreset) -> Result<Vec<SplitPreset>, AppError> {
+    validate_preset(&preset)?;
+
+    let mut presets = load_presets(config_dir)?;
+    if find_preset(&presets, &preset.name).is_some() {
+        return Err(AppError::invalid(format!(
+            "A preset named \"{}\" already exists",
+            preset.name
+        )));
+    }
+
+    presets.push(preset);
+    save_presets(config_dir, &presets)?;
+    Ok(presets)
+}
+
+/// Replaces the preset called `name`, which may also be renamed.
+pub fn update_preset(
+    config_dir: &Path,
+    name: &str,
+    preset: SplitPreset,
+) -> Result<Vec<SplitPreset>, AppError> {
+    validate_preset(&preset)?;
+
+    let mut presets = load_presets(config_dir)?;
+    let index = find_preset(&presets, name)
+        .ok_or_else(|| AppError::invalid(format!("Preset not found: {name}")))?;
+    if let Some(other) = find_preset(&presets, &preset.name) {
+        if other != index {
+            return Err(AppError::invalid(format!(
+                "A preset named \"{}\" already exists",
+                preset.name
+            )));
+        }
+    }
+
+    presets[index] = preset;
+    save_presets(config_dir, &presets)?;
+    Ok(presets)
+}
+
+/// Removes the preset called `name`.
+pub fn delete_preset(config_dir: &Path, name: &str) -> Result<Vec<SplitPreset>, AppError> {
+    let mut presets = load_presets(config_dir)?;
+    let index = find_preset(&presets, name)
+        .ok_or_else(|| AppError::invalid(format!("Preset not found: {name}")))?;
+
+    presets.remove(index);
+    save_presets(config_dir, &presets)?;
+    Ok(presets)
+}
+
+/// Merges the presets in a JSON file into the store. Imported presets replace
+/// existing ones with the same name; others are appended.
+pub fn import_presets(config_dir: &Path, source: &Path) -> Result<Vec<SplitPreset>, AppError> {
+    let imported = read_preset_file(source)?.presets;
+    for preset in &imported {
+        validate_preset(preset)?;
+    }
+
+    let mut presets = load_presets(config_dir)?;
+    for preset in imported {
+        match find_preset(&presets, &preset.name) {
+            Some(index) => presets[index] = preset,
+            None => presets.push(preset),
+        }
+    }
+
+    save_presets(config_dir, &presets)?;
+    Ok(presets)
+}
+
+/// Writes presets to a JSON file that [`import_presets`] can read.
+///
+/// Exports every preset when `names` is empty.
+pub fn export_presets(
+    config_dir: &Path,
+    destination: &Path,
+    names: &[String],
+) -> Result<(), AppError> {
+    let presets: Vec<SplitPreset> = load_presets(config_dir)?
+        .into_iter()
+        .filter(|p| names.is_empty() || names.contains(&p.name))
+        .collect();
+
+    write_preset_file(destination, presets)
+}
+
+fn save_presets(config_dir: &Path, presets: &[SplitPreset]) -> Result<(), AppError> {
+    std::fs::create_dir_all(config_dir)
+        .map_err(|e| AppError::io("Failed to create config directory", config_dir, &e))?;
+
+    // Write to a temporary file first so a crash can't leave a truncated store
+    let path = config_dir.join(PRESETS_FILE_NAME);
+    let temp_path = config_dir.join(format!("{PRESETS_FILE_NAME}.tmp"));
+    write_preset_file(&temp_path, presets.to_vec())?;
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| AppError::io("Failed to save presets", &path, &e))
+}
+
+fn read_preset_file(path: &Path) -> Result<PresetFile, AppError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::io("Failed to read presets", path, &e))?;
+    let file: PresetFile = serde_json::from_str(&content)
+        .map_err(|e| AppError::invalid(format!("Invalid presets file: {e}")))?;
+
+    if file.version > PRESETS_VERSION {
+        return Err(AppError::invalid(format!(
+            "Presets file version {} is newer than supported version {PRESETS_VERSION}",
+            file.version
+        )));
+    }
+
+    Ok(file)
+}
+
+fn write_preset_file(path: &Path, presets: Vec<SplitPreset>) -> Result<(), AppError> {
+    let file = PresetFile {
+        version: PRESETS_VERSION,
+        presets,
+    };
+    let json = serde_json::to_string_pretty(&file)
+        .map_err(|e| AppError::internal(format!("Failed to serialize presets: {e}")))?;
+    std::fs::write(path, json).map_err(|e| AppError::io("Failed to write presets", path, &e))
+}
+
+fn find_preset(presets: &[SplitPreset], name: &str) -> Option<usize> {
+    presets.iter().position(|p| p.name == name)
+}
+
+/// Rejects presets that could not produce a valid split.
+fn validate_preset(preset: &SplitPreset) -> Result<(), AppError> {
+    if preset.name.trim().is_empty() {
+        return Err(AppError::invalid("Preset name must not be empty"));
+    }
+    if !preset.target.is_finite() || preset.target <= 0.0 {
+        return Err(AppError::invalid(format!(
+            "Preset \"{}\" needs a positive target",
+            preset.name
+        )));
+    }
+    if !preset.naming_template.contains("{n}") {
+        return Err(AppError::invalid(format!(
+            "Naming template of preset \"{}\" must contain {{n}} so partitions get distinct names",
+            preset.name
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "video-partitioner-presets-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn make_preset(name: &str) -> SplitPreset {
+        SplitPreset {
+            name: name.to_string(),
+            strategy: SplitStrategy::Size,
+            target: 1e9,
+            encode_profile: EncodeProfile::Copy,
+            naming_template: DEFAULT_NAMING_TEMPLATE.to_string(),
+            output_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_builtins_until_saved() {
+        let dir = temp_config_dir("builtins");
+        assert_eq!(load_presets(&dir).unwrap(), builtin_presets());
+
+        let presets = create_preset(&dir, make_preset("Custom")).unwrap();
+        assert_eq!(presets.len(), builtin_presets().len() + 1);
+        assert_eq!(load_presets(&dir).unwrap(), presets);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_crud_rejects_duplicates_and_missing() {
+        let dir = temp_config_dir("crud");
+        create_preset(&dir, make_preset("A")).unwrap();
+        create_preset(&dir, make_preset("B")).unwrap();
+
+        assert!(create_preset(&dir, make_preset("A")).is_err());
+        assert!(update_preset(&dir, "A", make_preset("B")).is_err());
+        assert!(delete_preset(&dir, "Missing").is_err());
+
+        let presets = update_preset(&dir, "A", make_preset("C")).unwrap();
+        assert!(presets.iter().any(|p| p.name == "C"));
+        assert!(!presets.iter().any(|p| p.name == "A"));
+
+        let presets = delete_preset(&dir, "C").unwrap();
+        assert!(!presets.iter().any(|p| p.name == "C"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let source = temp_config_dir("export");
+        let target = temp_config_dir("import");
+        let mut custom = make_preset("Custom");
+        custom.output_dir = Some("/mnt/usb".to_string());
+        create_preset(&source, custom.clone()).unwrap();
+
+        std::fs::create_dir_all(&target).unwrap();
+        let exported = target.join("exported.json");
+        export_presets(&source, &exported, &["Custom".to_string()]).unwrap();
+
+        let presets = import_presets(&target, &exported).unwrap();
+        assert_eq!(presets.last(), Some(&custom));
+
+        let _ = std::fs::remove_dir_all(&source);
+        let _ = std::fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut preset = make_preset(" ");
+        assert!(validate_preset(&preset).is_err());
+
+        preset.name = "Bad target".to_string();
+        preset.target = 0.0;
+        assert!(validate_preset(&preset).is_err());
+
+        preset.target = 1.0;
+        preset.naming_template = "{stem}.{ext}".to_string();
+        assert!(validate_preset(&preset).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+
+use crate::error::AppError;
+use crate::models::project::FileFingerprint;
+use crate::services::projects::fingerprint;
+use crate::utils::ffmpeg_wrapper::{format_ffmpeg_time, run_ffmpeg_cancellable};
+
+/// Number of preview frames kept in memory; small JPEGs are a few kilobytes each.
+const PREVIEW_CACHE_CAPACITY: usize = 64;
+
+/// Identifies a preview frame; timestamps are compared in whole milliseconds.
+/// The fingerprint makes frames of a replaced or re-exported file miss.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FrameKey {
+    path: String,
+    fingerprint: FileFingerprint,
+    millis: u64,
+    height: u32,
+}
+
+/// Least recently used cache of encoded frames, most recent at the front.
+#[derive(Debug)]
+struct FrameLru {
+    capacity: usize,
+    entries: VecDeque<(FrameKey, Arc<str>)>,
+}
+
+impl FrameLru {
+    const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, key: &FrameKey) -> Option<Arc<str>> {
+        let position = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(position)?;
+        let frame = Arc::clone(&entry.1);
+        self.entries.push_front(entry);
+        Some(frame)
+    }
+
+    fn put(&mut self, key: FrameKey, frame: Arc<str>) {
+        self.entries.retain(|(k, _)| k != &key);
+        self.entries.push_front((key, frame));
+        self.entries.truncate(self.capacity);
+    }
+}
+
+/// Serves hover preview frames: caches recent ones and cancels the `FFmpeg` run of a
+/// request as soon as a newer one arrives.
+pub struct PreviewFrames {
+    cache: Mutex<FrameLru>,
+    current: Mutex<Arc<AtomicBool>>,
+    next_id: AtomicU64,
+}
+
+impl Default for PreviewFrames {
+    fn default() -> Self {
+        Self {
+            cache: Mutex::new(FrameLru::new(PREVIEW_CACHE_CAPACITY)),
+            current: Mutex::new(Arc::new(AtomicBool::new(false))),
+            next_id: AtomicU64::new(0),
+        }
+    }
+}
+
+impl PreviewFrames {
+    /// Returns the frame of `video_path` at `timestamp_secs`, `height` pixels high, as a
+    /// `data:image/jpeg;base64,...` URL.
+    ///
+    /// Starting a request cancels the previous one, which then fails with
+    /// [`AppError::Cancelled`]; only the latest cursor position is worth decoding.
+    pub async fn frame(
+        &self,
+        video_path: &str,
+        timestamp_secs: f64,
+        height: u32,
+    ) -> Result<String, AppError> {
+        if height == 0 || !timestamp_secs.is_finite() || timestamp_secs < 0.0 {
+            return Err(AppError::invalid(
+                "Preview height and timestamp must be positive",
+            ));
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let key = FrameKey {
+            path: video_path.to_string(),
+            fingerprint: fingerprint(Path::new(video_path))?,
+            millis: (timestamp_secs * 1000.0).round() as u64,
+            height,
+        };
+        let cached = lock(&self.cache).get(&key);
+        if let Some(frame) = cached {
+            return Ok(frame.to_string());
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let superseded = std::mem::replace(&mut *lock(&self.current), Arc::clone(&cancel));
+        superseded.store(true, Ordering::Relaxed);
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let bytes = extract_frame(video_path, timestamp_secs, height, id, &cancel).await?;
+        let frame: Arc<str> = format!("data:image/jpeg;base64,{}", STANDARD.encode(bytes)).into();
+
+        lock(&self.cache).put(key, Arc::clone(&frame));
+        Ok(frame.to_string())
+    }
+}
+
+/// Decodes one small JPEG at `timestamp_secs` through a temporary file, since the sidecar
+/// only reports its output line by line as text.
+async fn extract_frame(
+    video_path: &str,
+    timestamp_secs: f64,
+    height: u32,
+    id: u64,
+    cancel: &AtomicBool,
+) -> Result<Vec<u8>, AppError> {
+    let output = std::env::temp_dir().join(format!(
+        "video-partitioner-preview-{}-{id}.jpg",
+        std::process::id()
+    ));
+    let output_str = output.to_string_lossy().to_string();
+    let ss_arg = format_ffmpeg_time(timestamp_secs);
+    let scale_filter = format!("scale=-2:{height}");
+
+    // Input seeking jumps to the preceding keyframe and decodes up to the exact time
+    let result = run_ffmpeg_cancellable(
+        &[
+            "-hide_banner",
+            "-ss",
+            &ss_arg,
+            "-i",
+            video_path,
+            "-an",
+            "-sn",
+            "-frames:v",
+            "1",
+            "-vf",
+            &scale_filter,
+            "-q:v",
+            "6",
+            "-y",
+            &output_str,
+        ],
+        &[cancel],
+    )
+    .await;
+
+    let bytes = match result {
+        Ok((_, _, Some(0))) => std::fs::read(&output)
+            .map_err(|e| AppError::io("Failed to read preview frame", &output, &e)),
+        Ok((_, stderr, exit_code)) => Err(AppError::ffmpeg(
+            "Preview frame failed",
+            Some(video_path),
+            exit_code,
+            &stderr,
+        )),
+        Err(e) => Err(e),
+    };
+    let _ = std::fs::remove_file(&output);
+    bytes
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(millis: u64) -> FrameKey {
+        FrameKey {
+            path: "/videos/a.mp4".to_string(),
+            fingerprint: FileFingerprint {
+                size_bytes: 1000,
+                modified_millis: 42,
+            },
+            millis,
+            height: 90,
+        }
+    }
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let mut lru = FrameLru::new(2);
+        lru.put(key(1), "one".into());
+        lru.put(key(2), "two".into());
+
+        // Reading 1 makes 2 the least recently used
+        assert_eq!(lru.get(&key(1)).as_deref(), Some("one"));
+        lru.put(key(3), "three".into());
+
+        assert_eq!(lru.get(&key(2)), None);
+        assert_eq!(lru.get(&key(1)).as_deref(), Some("one"));
+        assert_eq!(lru.get(&key(3)).as_deref(), Some("three"));
+    }
+
+    #[test]
+    fn test_replaced_file_misses() {
+        let mut lru = FrameLru::new(2);
+        lru.put(key(1), "old".into());
+
+        let mut replaced = key(1);
+        replaced.fingerprint.modified_millis += 1;
+        assert_eq!(lru.get(&replaced), None);
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+use crate::models::project::{FileFingerprint, ProjectFile, ProjectState, PROJECT_VERSION};
+
+/// Subdirectory of the app data directory holding one JSON file per video.
+const PROJECTS_DIR_NAME: &str = "projects";
+
+/// Loads the saved state of `file_path`.
+///
+/// Returns `None` when nothing was saved or the file changed (size or modification
+/// time) since, because saved timestamps may no longer match its content.
+pub fn load_project_state(
+    data_dir: &Path,
+    file_path: &str,
+) -> Result<Option<ProjectState>, AppError> {
+    let record_path = project_file_path(data_dir, file_path);
+    if !record_path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&record_path)
+        .map_err(|e| AppError::io("Failed to read project state", &record_path, &e))?;
+    let Ok(record) = serde_json::from_str::<ProjectFile>(&content) else {
+        // An unreadable record is treated like a stale one rather than blocking the file
+        return Ok(None);
+    };
+
+    let current = fingerprint(Path::new(file_path))?;
+    if record.version > PROJECT_VERSION
+        || record.file_path != file_path
+        || record.fingerprint != current
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(record.state))
+}
+
+/// Saves the state of `file_path`, replacing any earlier record.
+///
+/// An empty state removes the record instead.
+pub fn save_project_state(
+    data_dir: &Path,
+    file_path: &str,
+    state: ProjectState,
+) -> Result<(), AppError> {
+    let record_path = project_file_path(data_dir, file_path);
+    if state.is_empty() {
+        if record_path.exists() {
+            std::fs::remove_file(&record_path)
+                .map_err(|e| AppError::io("Failed to remove project state", &record_path, &e))?;
+        }
+        return Ok(());
+    }
+
+    let record = ProjectFile {
+        version: PROJECT_VERSION,
+        file_path: file_path.to_string(),
+        fingerprint: fingerprint(Path::new(file_path))?,
+        state,
+    };
+    let json = serde_json::to_string_pretty(&record)
+        .map_err(|e| AppError::internal(format!("Failed to serialize project state: {e}")))?;
+
+    let projects_dir = data_dir.join(PROJECTS_DIR_NAME);
+    std::fs::create_dir_all(&projects_dir)
+        .map_err(|e| AppError::io("Failed to create projects directory", &projects_dir, &e))?;
+
+    // Write to a temporary file first so a crash can't leave a truncated record
+    let temp_path = record_path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| AppError::io("Failed to write project state", &temp_path, &e))?;
+    std::fs::rename(&temp_path, &record_path)
+        .map_err(|e| AppError::io("Failed to save project state", &record_path, &e))
+}
+
+/// Reads the size and modification time of a file.
+pub fn fingerprint(path: &Path) -> Result<FileFingerprint, AppError> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| AppError::io("Failed to read metadata of", path, &e))?;
+    let modified_millis = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX));
+
+    Ok(FileFingerprint {
+        size_bytes: metadata.len(),
+        modified_millis,
+    })
+}
+
+/// Record location for a video, named after a digest of its path.
+fn project_file_path(data_dir: &Path, file_path: &str) -> PathBuf {
+    let digest = Sha256::digest(file_path.as_bytes());
+    let hex = format!("{digest:x}");
+    data_dir
+        .join(PROJECTS_DIR_NAME)
+        .join(format!("{}.json", &hex[..32]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::partition::TimeInterval;
+    use crate::models::project::Bookmark;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "video-partitioner-projects-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn make_state() -> ProjectState {
+        ProjectState {
+            exclusions: vec![TimeInterval {
+                start_secs: 10.0,
+                end_secs: 20.0,
+            }],
+            split_points: vec![42.5],
+            preset: Some("FAT32 (4 GiB)".to_string()),
+            bookmarks: vec![Bookmark {
+                time_secs: 5.0,
+                label: "Intro".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_state_roundtrip_and_staleness() {
+        let dir = temp_dir("roundtrip");
+        let video = dir.join("movie.mp4");
+        std::fs::write(&video, b"frames").unwrap();
+        let video_str = video.to_string_lossy().to_string();
+
+        assert_eq!(load_project_state(&dir, &video_str).unwrap(), None);
+
+        save_project_state(&dir, &video_str, make_state()).unwrap();
+        assert_eq!(
+            load_project_state(&dir, &video_str).unwrap(),
+            Some(make_state())
+        );
+
+        // A different size means the file was edited and the state no longer applies
+        std::fs::write(&video, b"more frames").unwrap();
+        assert_eq!(load_project_state(&dir, &video_str).unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_empty_state_removes_record() {
+        let dir = temp_dir("empty");
+        let video = dir.join("movie.mp4");
+        std::fs::write(&video, b"frames").unwrap();
+        let video_str = video.to_string_lossy().to_string();
+
+        save_project_state(&dir, &video_str, make_state()).unwrap();
+        save_project_state(&dir, &video_str, ProjectState::default()).unwrap();
+
+        assert!(!project_file_path(&dir, &video_str).exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use crate::error::AppError;
+use crate::models::manifest::{FileHashes, ManifestKind, ReassemblyReport, SplitManifest};
+use crate::models::preset::EncodeProfile;
+use crate::services::byte_splitter::join_file_chunks;
+use crate::services::checksum::hash_file;
+use crate::services::manifest::read_manifest;
+use crate::services::scratch::ScratchDir;
+use crate::services::splitter::concat_segments;
+use crate::utils::ffmpeg_wrapper::probe_media;
+
+/// Allowed duration drift per partition boundary, since stream copy cuts on keyframes.
+const DURATION_TOLERANCE_PER_PARTITION_SECS: f64 = 1.0;
+
+/// Reassembles the partitions listed in a split manifest into a single file.
+///
+/// Verifies every partition against the digests in the manifest before concatenating
+/// with the concat demuxer, then compares the result's duration and stream layout
+/// against what the manifest expects: the source's streams for stream-copied
+/// partitions, the first partition's for re-encoded ones. Partitions are looked up
+/// next to the manifest.
+/// Byte-chunk manifests are joined directly and checked against the source digests.
+///
+/// Without `output_path` the result is written next to the partitions under a name
+/// that doesn't exist yet, so an earlier reassembly is never overwritten.
+#[allow(clippy::cast_precision_loss)]
+pub async fn reassemble(
+    manifest_path: &str,
+    output_path: Option<&str>,
+) -> Result<ReassemblyReport, AppError> {
+    let manifest_path = Path::new(manifest_path);
+    let manifest = read_manifest(manifest_path)?;
+
+    if manifest.partitions.is_empty() {
+        return Err(AppError::invalid("Manifest does not list any partitions"));
+    }
+
+    let parts_dir = manifest_path
+        .parent()
+        .ok_or_else(|| AppError::invalid("Cannot determine manifest directory"))?;
+
+    let output =
+        output_path.map_or_else(|| default_output_path(&manifest, parts_dir), PathBuf::from);
+    let output_str = output.to_string_lossy().to_string();
+
+    if manifest.kind == ManifestKind::Bytes {
+        return reassemble_bytes(manifest, parts_dir.to_path_buf(), output).await;
+    }
+
+    let mut partitions = manifest.partitions.clone();
+    partitions.sort_by_key(|p| p.index);
+
+    let mut part_paths = Vec::with_capacity(partitions.len());
+    let mut verified = 0u32;
+
+    for partition in &partitions {
+        let path = parts_dir.join(&partition.file_name);
+        if !path.is_file() {
+            return Err(AppError::NotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        if verify_partition(&path, &partition.hashes).await? {
+            verified += 1;
+        }
+
+        part_paths.push(path.to_string_lossy().to_string());
+    }
+
+    let scratch = ScratchDir::create(None)?;
+    concat_segments(
+        &part_paths,
+        &output_str,
+        scratch.path(),
+        &AtomicBool::new(false),
+    )
+    .await?;
+
+    let probe = probe_media(&output_str).await?;
+    let expected_duration_secs: f64 = partitions
+        .iter()
+        .flat_map(|p| &p.segments)
+        .map(|s| s.end_secs - s.start_secs)
+        .sum();
+    let tolerance = DURATION_TOLERANCE_PER_PARTITION_SECS * partitions.len() as f64;
+    // Re-encoded partitions have other codecs and no subtitles, unlike the source
+    let expected_streams = match manifest.encode_profile {
+        EncodeProfile::Copy => manifest.source.streams,
+        EncodeProfile::H264 | EncodeProfile::Hevc => probe_media(&part_paths[0]).await?.streams,
+    };
+    let streams_match = probe.streams == expected_streams;
+
+    Ok(ReassemblyReport {
+        output_path: output_str,
+        verified_partitions: verified,
+        expected_duration_secs,
+        actual_duration_secs: probe.duration_secs,
+        duration_matches: (probe.duration_secs - expected_duration_secs).abs() <= tolerance,
+        expected_streams,
+        actual_streams: probe.streams,
+        streams_match,
+        source_hash_matches: None,
+    })
+}
+
+/// Joins byte chunks on the blocking thread pool and reports the outcome.
+async fn reassemble_bytes(
+    manifest: SplitManifest,
+    parts_dir: PathBuf,
+    output: PathBuf,
+) -> Result<ReassemblyReport, AppError> {
+    let output_str = output.to_string_lossy().to_string();
+    let outcome = tauri::async_runtime::spawn_blocking(move || {
+        join_file_chunks(&manifest, &parts_dir, &output)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Join task failed: {e}")))??;
+
+    Ok(ReassemblyReport {
+        output_path: output_str,
+        verified_partitions: outcome.verified_chunks,
+        expected_duration_secs: 0.0,
+        actual_duration_secs: 0.0,
+        duration_matches: true,
+        expected_streams: Vec::new(),
+        actual_streams: Vec::new(),
+        streams_match: true,
+        source_hash_matches: outcome.source_hash_matches,
+    })
+}
+
+/// Checks a partition against its recorded digests.
+///
+/// Returns `Ok(false)` when the manifest has no digests for it, and an error on mismatch.
+async fn verify_partition(path: &Path, expected: &FileHashes) -> Result<bool, AppError> {
+    let algorithms = expected.algorithms();
+    if algorithms.is_empty() {
+        return Ok(false);
+    }
+
+    let owned_path = path.to_path_buf();
+    let actual = tauri::async_runtime::spawn_blocking(move || hash_file(&owned_path, &algorithms))
+        .await
+        .map_err(|e| AppError::internal(format!("Hashing task failed: {e}")))??;
+
+    if &actual == expected {
+        Ok(true)
+    } else {
+        Err(AppError::invalid(format!(
+            "Checksum mismatch for partition: {}",
+            path.display()
+        )))
+    }
+}
+
+/// Builds `{stem}_reassembled.{ext}` from the source name, next to the partitions,
+/// adding `_2`, `_3`, ... to the stem until the name is free.
+fn default_output_path(manifest: &SplitManifest, parts_dir: &Path) -> PathBuf {
+    let source = Path::new(&manifest.source.file_name);
+    let stem = source
+        .file_stem()
+        .map_or_else(|| "output".to_string(), |s| s.to_string_lossy().to_string());
+    let extension = source
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut path = parts_dir.join(format!("{stem}_reassembled{extension}"));
+    let mut attempt = 1;
+    while path.exists() {
+        attempt += 1;
+        path = parts_dir.join(format!("{stem}_reassembled_{attempt}{extension}"));
+    }
+    path
+}
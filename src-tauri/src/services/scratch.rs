@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::AppError;
+
+/// Name of the directory under the system temp dir that holds job scratch dirs.
+const DEFAULT_SCRATCH_ROOT: &str = "video-partitioner-scratch";
+
+/// Distinguishes jobs started within the same process and nanosecond.
+static JOB_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A per-job directory for intermediate files, removed when dropped.
+///
+/// Dropping happens on success, on early return through `?`, and when the owning
+/// future is cancelled, so intermediates never outlive the job.
+pub struct ScratchDir {
+    path: PathBuf,
+}
+
+impl ScratchDir {
+    /// Creates a fresh job directory under `root`, or under the system temp dir if `None`.
+    pub fn create(root: Option<&Path>) -> Result<Self, AppError> {
+        let root = scratch_root(root);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let job = JOB_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = root.join(format!("job-{}-{nanos}-{job}", std::process::id()));
+
+        std::fs::create_dir_all(&path)
+            .map_err(|e| AppError::io("Failed to create scratch directory", &path, &e))?;
+
+        Ok(Self { path })
+    }
+
+    /// Stand-in for the job directory `create` would make under `root`, for showing
+    /// planned commands before the job exists. The `<id>` part is never created.
+    pub fn placeholder_path(root: Option<&Path>) -> PathBuf {
+        scratch_root(root).join("job-<id>")
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Resolves the directory that job scratch dirs are created in.
+pub fn scratch_root(root: Option<&Path>) -> PathBuf {
+    root.map_or_else(
+        || std::env::temp_dir().join(DEFAULT_SCRATCH_ROOT),
+        Path::to_path_buf,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_removed_on_drop() {
+        let scratch = ScratchDir::create(None).unwrap();
+        let path = scratch.path().to_path_buf();
+        std::fs::write(path.join("segment.mp4"), b"data").unwrap();
+        assert!(path.is_dir());
+
+        drop(scratch);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_unique_per_job() {
+        let first = ScratchDir::create(None).unwrap();
+        let second = ScratchDir::create(None).unwrap();
+        assert_ne!(first.path(), second.path());
+    }
+
+    #[test]
+    fn test_placeholder_is_beside_jobs() {
+        let job = ScratchDir::create(None).unwrap();
+        let placeholder = ScratchDir::placeholder_path(None);
+        assert_eq!(placeholder.parent(), job.path().parent());
+        assert!(!placeholder.exists());
+    }
+}
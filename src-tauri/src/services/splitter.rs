@@ -1,8 +1,49 @@
-use std::path::Path;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
-use crate::models::partition::{SplitRequest, TimeInterval};
+use futures::stream::{self, StreamExt};
+
+use crate::error::AppError;
+use crate::models::manifest::{
+    FileHashes, HashAlgorithm, ManifestKind, ManifestPartition, ManifestSource, SplitManifest,
+    MANIFEST_VERSION,
+};
+use crate::models::partition::{
+    PartitionPlan, PartitionPoint, PartitionTarget, PlannedSegment, PreflightReport, SplitPlan,
+    SplitProgress, SplitRequest, TimeInterval,
+};
+use crate::models::preset::{EncodeProfile, DEFAULT_NAMING_TEMPLATE};
+use crate::models::video::VideoMetadata;
 use crate::services::calculator::calculate_partition_points;
-use crate::utils::ffmpeg_wrapper::{format_ffmpeg_time, run_ffmpeg};
+use crate::services::checksum::hash_file;
+use crate::services::exclusions::normalize_exclusions;
+use crate::services::manifest::{is_plain_file_name, write_manifest};
+use crate::services::preflight::{available_space, estimate_disk_space};
+use crate::services::scratch::ScratchDir;
+use crate::utils::ffmpeg_wrapper::{
+    format_ffmpeg_time, is_cancelled, probe_keyframes, probe_media, run_ffmpeg_cancellable,
+    MediaProbe,
+};
+
+/// Codec arguments that stream-copy every stream, as video partitions are written.
+const COPY_ALL: &[&str] = &["-c", "copy"];
+
+/// Codec arguments of each [`EncodeProfile`]. Re-encodes drop subtitles, which MP4 can't
+/// hold in most source formats.
+const fn profile_codec_args(profile: EncodeProfile) -> &'static [&'static str] {
+    match profile {
+        EncodeProfile::Copy => COPY_ALL,
+        EncodeProfile::H264 => &[
+            "-sn", "-c:v", "libx264", "-preset", "medium", "-crf", "20", "-c:a", "aac", "-b:a",
+            "192k",
+        ],
+        EncodeProfile::Hevc => &[
+            "-sn", "-c:v", "libx265", "-preset", "medium", "-crf", "24", "-tag:v", "hvc1", "-c:a",
+            "aac", "-b:a", "192k",
+        ],
+    }
+}
 
 /// A time range in the original video timeline.
 struct Segment {
@@ -10,116 +51,626 @@ struct Segment {
     end: f64,
 }
 
-/// Executes the video split operation using `FFmpeg` stream copy.
-///
-/// Calculates partition points, maps each partition to original-timeline segments
-/// (skipping excluded intervals), extracts them, and concatenates if needed.
-#[allow(clippy::cast_possible_truncation)]
-pub async fn split_video(request: &SplitRequest) -> Result<Vec<String>, String> {
+/// A split whose partitions have been computed but not yet written.
+struct PreparedSplit {
+    file_stem: String,
+    extension: String,
+    naming_template: String,
+    source_size: u64,
+    probe: MediaProbe,
+    points: Vec<PartitionPoint>,
+    /// Original-timeline segments of each partition, parallel to `points`
+    segments: Vec<Vec<Segment>>,
+    /// Adjustments made while normalising the exclusions
+    warnings: Vec<String>,
+}
+
+/// Validates the request, probes the source and computes every partition's segments.
+async fn prepare_split(request: &SplitRequest) -> Result<PreparedSplit, AppError> {
     let input_path = Path::new(&request.input_path);
     if !input_path.exists() {
-        return Err(format!("Input file not found: {}", request.input_path));
+        return Err(AppError::NotFound {
+            path: request.input_path.clone(),
+        });
     }
 
     let output_dir = Path::new(&request.output_dir);
     if !output_dir.is_dir() {
-        return Err(format!("Output directory not found: {}", request.output_dir));
+        return Err(AppError::NotFound {
+            path: request.output_dir.clone(),
+        });
     }
 
     let fs_meta = std::fs::metadata(input_path)
-        .map_err(|e| format!("Failed to read file metadata: {e}"))?;
+        .map_err(|e| AppError::io("Failed to read file metadata of", input_path, &e))?;
 
     let file_stem = input_path
         .file_stem()
         .map_or_else(|| "output".to_string(), |s| s.to_string_lossy().to_string());
 
-    let extension = input_path
-        .extension()
-        .map_or_else(|| "mp4".to_string(), |e| e.to_string_lossy().to_string());
+    // Stream copy keeps the container; re-encoded partitions are always MP4
+    let extension = match request.encode_profile {
+        EncodeProfile::Copy => input_path
+            .extension()
+            .map_or_else(|| "mp4".to_string(), |e| e.to_string_lossy().to_string()),
+        EncodeProfile::H264 | EncodeProfile::Hevc => "mp4".to_string(),
+    };
 
-    let duration_secs = get_duration_from_ffmpeg(&request.input_path).await?;
+    let naming_template = request
+        .naming_template
+        .clone()
+        .unwrap_or_else(|| DEFAULT_NAMING_TEMPLATE.to_string());
+    validate_naming_template(&naming_template, &file_stem, &extension)?;
 
-    let metadata = crate::models::video::VideoMetadata {
+    let probe = probe_media(&request.input_path).await?;
+
+    let metadata = VideoMetadata {
         file_path: request.input_path.clone(),
         file_name: file_stem.clone(),
         file_size: fs_meta.len(),
-        duration_secs,
+        duration_secs: probe.duration_secs,
         width: 0,
         height: 0,
         video_codec: String::new(),
         audio_codec: None,
         bitrate: 0,
         format: extension.clone(),
+        frame_rate: 0.0,
     };
 
-    let points = calculate_partition_points(
-        &metadata,
-        request.target_size_bytes,
-        &request.exclusions,
-    );
+    let normalized = normalize_exclusions(&request.exclusions, probe.duration_secs)?;
+    let exclusions = normalized.intervals;
+    let points = calculate_partition_points(&metadata, request.target(), &exclusions)?;
 
     if points.is_empty() {
-        return Err(
-            "No partition points calculated. Check file size and target partition size.".to_string(),
-        );
+        return Err(AppError::invalid(
+            "No partition points calculated. Check file size and target partition size.",
+        ));
     }
 
-    let included = compute_included_intervals(&request.exclusions, duration_secs);
-    let mut output_files = Vec::new();
+    let included = compute_included_intervals(&exclusions, probe.duration_secs);
+    let segments = points
+        .iter()
+        .map(|point| {
+            map_partition_to_original_segments(point.start_secs, point.end_secs, &included)
+        })
+        .collect();
+
+    Ok(PreparedSplit {
+        file_stem,
+        extension,
+        naming_template,
+        source_size: fs_meta.len(),
+        probe,
+        points,
+        segments,
+        warnings: normalized.warnings,
+    })
+}
 
-    for point in &points {
-        let segments =
-            map_partition_to_original_segments(point.start_secs, point.end_secs, &included);
+/// Calculates partition points with start and end times on the original timeline,
+/// for showing a split on the timeline before running it.
+///
+/// Uses the same calculation and exclusion mapping as [`split_video`], so the preview
+/// always matches the partitions that get written.
+pub fn preview_partition_points(
+    metadata: &VideoMetadata,
+    target: PartitionTarget,
+    exclusions: &[TimeInterval],
+) -> Result<Vec<PartitionPoint>, AppError> {
+    let exclusions = normalize_exclusions(exclusions, metadata.duration_secs)?.intervals;
+    let points = calculate_partition_points(metadata, target, &exclusions)?;
+    let included = compute_included_intervals(&exclusions, metadata.duration_secs);
 
-        let output_name = if points.len() == 1 {
-            format!("{file_stem}_part1.{extension}")
-        } else {
-            format!("{file_stem}_part{}.{extension}", point.index + 1)
-        };
+    Ok(points
+        .into_iter()
+        .map(|point| {
+            let segments =
+                map_partition_to_original_segments(point.start_secs, point.end_secs, &included);
+            PartitionPoint {
+                start_secs: segments.first().map_or(point.start_secs, |seg| seg.start),
+                end_secs: segments.last().map_or(point.end_secs, |seg| seg.end),
+                ..point
+            }
+        })
+        .collect())
+}
+
+/// Original-timeline ranges of every partition a split of `metadata` with `target`
+/// would write, skipping exclusions.
+///
+/// Without a target the whole file is one partition made of every included range.
+/// Lets other exports produce parts covering exactly the same time as [`split_video`].
+pub fn partition_intervals(
+    metadata: &VideoMetadata,
+    target: Option<PartitionTarget>,
+    exclusions: &[TimeInterval],
+) -> Result<Vec<Vec<TimeInterval>>, AppError> {
+    let exclusions = normalize_exclusions(exclusions, metadata.duration_secs)?.intervals;
+    let included = compute_included_intervals(&exclusions, metadata.duration_secs);
+
+    let partitions = match target {
+        Some(target) => calculate_partition_points(metadata, target, &exclusions)?
+            .iter()
+            .map(|point| {
+                map_partition_to_original_segments(point.start_secs, point.end_secs, &included)
+            })
+            .collect(),
+        None => vec![included],
+    };
+
+    let partitions: Vec<Vec<TimeInterval>> = partitions
+        .into_iter()
+        .filter(|segments| !segments.is_empty())
+        .map(|segments| {
+            segments
+                .iter()
+                .map(|seg| TimeInterval {
+                    start_secs: seg.start,
+                    end_secs: seg.end,
+                })
+                .collect()
+        })
+        .collect();
+
+    if partitions.is_empty() {
+        return Err(AppError::invalid("Nothing is left after the exclusions"));
+    }
+    Ok(partitions)
+}
+
+/// Estimates the disk space a split needs and compares it with what the output
+/// volume has free.
+pub async fn preflight_split(request: &SplitRequest) -> Result<PreflightReport, AppError> {
+    let prepared = prepare_split(request).await?;
+    disk_space_report(&prepared, request)
+}
+
+fn disk_space_report(
+    prepared: &PreparedSplit,
+    request: &SplitRequest,
+) -> Result<PreflightReport, AppError> {
+    let partition_sizes: Vec<u64> = prepared
+        .points
+        .iter()
+        .map(|point| point.estimated_size_bytes)
+        .collect();
+
+    Ok(estimate_disk_space(
+        &partition_sizes,
+        available_space(Path::new(&request.output_dir))?,
+    ))
+}
 
-        let final_path = output_dir.join(&output_name);
-        let final_str = final_path.to_string_lossy().to_string();
+/// Computes everything a split would do without writing anything.
+///
+/// Each partition lists its original-timeline segments, the keyframe stream copy will
+/// actually start each one from, its estimated size, output path and the exact `FFmpeg`
+/// arguments. Concat list paths point into a `job-<id>` placeholder under the scratch
+/// root, since a real split creates and names its own job directory when it starts.
+pub async fn plan_split(request: &SplitRequest) -> Result<SplitPlan, AppError> {
+    let prepared = prepare_split(request).await?;
+    let space = disk_space_report(&prepared, request)?;
 
-        if segments.len() == 1 {
-            // Single continuous segment — extract directly
-            extract_segment(
+    // Re-encoded partitions start exactly where requested, so keyframes only matter for copies
+    let copies = request.encode_profile == EncodeProfile::Copy;
+    let has_video = prepared
+        .probe
+        .streams
+        .iter()
+        .any(|s| s.starts_with("video:"));
+    let keyframes = if copies && has_video {
+        probe_keyframes(&request.input_path).await?
+    } else {
+        Vec::new()
+    };
+    let work_dir = ScratchDir::placeholder_path(request.scratch_dir.as_deref().map(Path::new));
+    let output_dir = Path::new(&request.output_dir);
+
+    let partitions = prepared
+        .points
+        .iter()
+        .zip(&prepared.segments)
+        .map(|(point, segments)| {
+            let file_name = partition_file_name(
+                &prepared.naming_template,
+                &prepared.file_stem,
+                &prepared.extension,
+                point.index,
+            );
+            let output_path = output_dir.join(file_name).to_string_lossy().to_string();
+            let command = partition_command(
                 &request.input_path,
-                &final_str,
-                segments[0].start,
-                segments[0].end,
-            )
-            .await?;
-        } else {
-            // Multiple segments — extract each, then concatenate
-            let mut temp_paths = Vec::new();
-
-            for (i, seg) in segments.iter().enumerate() {
-                let temp_name = format!(
-                    "_temp_{file_stem}_p{}_s{i}.{extension}",
-                    point.index + 1
-                );
-                let temp_path = output_dir.join(&temp_name);
-                let temp_str = temp_path.to_string_lossy().to_string();
-
-                extract_segment(&request.input_path, &temp_str, seg.start, seg.end).await?;
-                temp_paths.push(temp_str);
+                segments,
+                &output_path,
+                &work_dir,
+                profile_codec_args(request.encode_profile),
+            );
+
+            PartitionPlan {
+                index: point.index,
+                estimated_size_bytes: point.estimated_size_bytes,
+                segments: segments
+                    .iter()
+                    .map(|seg| PlannedSegment {
+                        interval: TimeInterval {
+                            start_secs: seg.start,
+                            end_secs: seg.end,
+                        },
+                        keyframe_start_secs: keyframe_at_or_before(&keyframes, seg.start),
+                    })
+                    .collect(),
+                ffmpeg_args: command.args,
+                concat_list: command.concat_list.map(|(_, content)| content),
+                output_path,
             }
+        })
+        .collect();
 
-            concat_segments(&temp_paths, &final_str, output_dir).await?;
+    Ok(SplitPlan {
+        partitions,
+        space,
+        warnings: prepared.warnings,
+    })
+}
 
-            // Clean up temp segment files
-            for p in &temp_paths {
-                let _ = std::fs::remove_file(p);
+/// The last keyframe at or before `time`, given keyframe times in ascending order.
+fn keyframe_at_or_before(keyframes: &[f64], time: f64) -> Option<f64> {
+    let count = keyframes.partition_point(|&k| k <= time);
+    count.checked_sub(1).map(|i| keyframes[i])
+}
+
+/// Executes the video split operation using `FFmpeg`, stream-copying unless the request
+/// asks for a re-encode.
+///
+/// Calculates partition points, maps each partition to original-timeline segments
+/// (skipping excluded intervals) and writes each partition in a single pass.
+/// Refuses to start if the output volume lacks space for the partitions.
+/// When checksums are requested, hashes the source and every partition and writes
+/// a manifest next to the partitions. Reports progress after every partition.
+///
+/// Up to `request.worker_count` partitions are written concurrently; output files and
+/// manifest entries are always in partition order. If one partition fails, the others
+/// are stopped through a flag of this split's own and the first failure is returned;
+/// `cancel` is only ever read.
+///
+/// Concat lists live in a per-job scratch directory that is removed on success,
+/// error and cancellation. Setting `cancel` stops the running `FFmpeg` process.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub async fn split_video(
+    request: &SplitRequest,
+    cancel: &AtomicBool,
+    on_progress: &(dyn Fn(SplitProgress) + Send + Sync),
+) -> Result<Vec<String>, AppError> {
+    let output_dir = Path::new(&request.output_dir);
+    let prepared = prepare_split(request).await?;
+
+    let space = disk_space_report(&prepared, request)?;
+    if !space.sufficient {
+        return Err(AppError::DiskFull {
+            path: request.output_dir.clone(),
+            required_bytes: Some(space.required_bytes),
+            available_bytes: Some(space.available_bytes),
+        });
+    }
+
+    let PreparedSplit {
+        file_stem,
+        extension,
+        naming_template,
+        source_size,
+        probe,
+        points,
+        segments: partition_segments,
+        ..
+    } = prepared;
+    let scratch = ScratchDir::create(request.scratch_dir.as_deref().map(Path::new))?;
+    let total = points.len();
+    let completed = AtomicU32::new(0);
+    let failed = AtomicBool::new(false);
+    let stop = [cancel, &failed];
+
+    // Partitions are independent, so up to `worker_count` of them are written at once.
+    // `buffered` yields results in partition order regardless of completion order.
+    let results: Vec<Result<(String, Option<ManifestPartition>), AppError>> =
+        stream::iter(0..total)
+            .map(|i| {
+                let (point, segments) = (&points[i], &partition_segments[i]);
+                let output_name =
+                    partition_file_name(&naming_template, &file_stem, &extension, point.index);
+                let (completed, failed, stop) = (&completed, &failed, &stop);
+                let scratch_path = scratch.path();
+
+                async move {
+                    let output = write_and_describe(
+                        request,
+                        point.index,
+                        output_name,
+                        segments,
+                        scratch_path,
+                        stop,
+                    )
+                    .await;
+
+                    match &output {
+                        Ok(_) => {
+                            let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                            on_progress(SplitProgress {
+                                completed_partitions: done,
+                                total_partitions: total as u32,
+                                percent: f64::from(done) / total as f64 * 100.0,
+                            });
+                        }
+                        // Stop the other workers; the first real error is reported below
+                        Err(_) => failed.store(true, Ordering::Relaxed),
+                    }
+                    output
+                }
+            })
+            .buffered(worker_count(request.worker_count, total))
+            .collect()
+            .await;
+
+    let mut output_files = Vec::with_capacity(total);
+    let mut manifest_partitions = Vec::new();
+    let mut first_error = None;
+    for result in results {
+        match result {
+            Ok((path, entry)) => {
+                output_files.push(path);
+                manifest_partitions.extend(entry);
+            }
+            Err(e)
+                if first_error.is_none() || first_error == Some(AppError::Cancelled) =>
+            {
+                first_error = Some(e);
             }
+            Err(_) => {}
         }
+    }
+    if let Some(e) = first_error {
+        return Err(e);
+    }
 
-        output_files.push(final_str);
+    if !request.checksums.is_empty() {
+        let manifest = SplitManifest {
+            version: MANIFEST_VERSION,
+            kind: ManifestKind::Video,
+            encode_profile: request.encode_profile,
+            source: describe_source(request, source_size, probe).await?,
+            partitions: manifest_partitions,
+        };
+        write_manifest(&manifest, output_dir, &file_stem)?;
     }
 
     Ok(output_files)
 }
 
+/// Number of partitions to write concurrently: the requested count, at least one
+/// and at most the number of partitions.
+fn worker_count(requested: Option<u32>, partitions: usize) -> usize {
+    let requested = requested.map_or(1, |n| n as usize);
+    requested.clamp(1, partitions.max(1))
+}
+
+/// Writes one partition and, when checksums are requested, describes it for the manifest.
+///
+/// Returns the partition's path. A partially written file is removed on failure.
+async fn write_and_describe(
+    request: &SplitRequest,
+    index: u32,
+    output_name: String,
+    segments: &[Segment],
+    scratch_dir: &Path,
+    cancel: &[&AtomicBool],
+) -> Result<(String, Option<ManifestPartition>), AppError> {
+    if is_cancelled(cancel) {
+        return Err(AppError::Cancelled);
+    }
+
+    let final_path = Path::new(&request.output_dir).join(&output_name);
+    let final_str = final_path.to_string_lossy().to_string();
+
+    if let Err(e) = write_partition(
+        &request.input_path,
+        segments,
+        &final_str,
+        scratch_dir,
+        profile_codec_args(request.encode_profile),
+        cancel,
+    )
+    .await
+    {
+        let _ = std::fs::remove_file(&final_path);
+        return Err(e);
+    }
+
+    let entry = if request.checksums.is_empty() {
+        None
+    } else {
+        Some(
+            describe_partition(index, output_name, &final_str, segments, &request.checksums)
+                .await?,
+        )
+    };
+
+    Ok((final_str, entry))
+}
+
+/// File name of the partition at `index` (0-based) from a naming template, e.g.
+/// `movie_part1.mp4` from `{stem}_part{n}.{ext}`. `{n}` counts from 1.
+pub fn partition_file_name(template: &str, file_stem: &str, extension: &str, index: u32) -> String {
+    template
+        .replace("{stem}", file_stem)
+        .replace("{ext}", extension)
+        .replace("{n}", &(index + 1).to_string())
+}
+
+/// Rejects templates that would give every partition the same name or write outside
+/// the output directory.
+pub fn validate_naming_template(
+    template: &str,
+    file_stem: &str,
+    extension: &str,
+) -> Result<(), AppError> {
+    if !template.contains("{n}") {
+        return Err(AppError::invalid(
+            "The naming template must contain {n} so partitions get distinct names",
+        ));
+    }
+    if !is_plain_file_name(&partition_file_name(template, file_stem, extension, 0)) {
+        return Err(AppError::invalid(
+            "The naming template must produce a plain file name",
+        ));
+    }
+    Ok(())
+}
+
+/// An `FFmpeg` invocation that writes one output file.
+struct FfmpegCommand {
+    args: Vec<String>,
+    /// Concat list path and contents, written before running and removed afterwards
+    concat_list: Option<(PathBuf, String)>,
+}
+
+/// Builds the command that writes one partition straight from the source, encoded
+/// with `codec_args`.
+///
+/// Partitions spanning exclusions are written in a single pass by feeding every range
+/// to the concat demuxer as `inpoint`/`outpoint` pairs, so no intermediate segment
+/// files are created. Only the small concat list is written to `scratch_dir`.
+fn partition_command(
+    input_path: &str,
+    segments: &[Segment],
+    output_path: &str,
+    scratch_dir: &Path,
+    codec_args: &[&str],
+) -> FfmpegCommand {
+    if segments.len() == 1 {
+        // Single continuous segment — extract directly
+        return FfmpegCommand {
+            args: extract_args(
+                input_path,
+                output_path,
+                segments[0].start,
+                segments[0].end,
+                codec_args,
+            ),
+            concat_list: None,
+        };
+    }
+
+    let entries: Vec<ConcatEntry> = segments
+        .iter()
+        .map(|seg| ConcatEntry {
+            path: input_path,
+            inpoint: Some(seg.start),
+            outpoint: Some(seg.end),
+        })
+        .collect();
+
+    concat_command(&entries, output_path, scratch_dir, codec_args)
+}
+
+/// Writes one partition straight from the source. See [`partition_command`].
+async fn write_partition(
+    input_path: &str,
+    segments: &[Segment],
+    output_path: &str,
+    scratch_dir: &Path,
+    codec_args: &[&str],
+    cancel: &[&AtomicBool],
+) -> Result<(), AppError> {
+    let command = partition_command(input_path, segments, output_path, scratch_dir, codec_args);
+    run_command(&command, output_path, cancel).await
+}
+
+/// Writes the original-timeline `intervals` of `input_path` to one file, with
+/// `codec_args` choosing the streams and how they are encoded.
+///
+/// Uses the same single-pass extraction as video partitions; a concat list is written
+/// to `scratch_dir` when there are several intervals.
+pub async fn write_intervals(
+    input_path: &str,
+    intervals: &[TimeInterval],
+    output_path: &str,
+    codec_args: &[&str],
+    scratch_dir: &Path,
+    cancel: &AtomicBool,
+) -> Result<(), AppError> {
+    let segments: Vec<Segment> = intervals
+        .iter()
+        .map(|interval| Segment {
+            start: interval.start_secs,
+            end: interval.end_secs,
+        })
+        .collect();
+    let command = partition_command(input_path, &segments, output_path, scratch_dir, codec_args);
+    run_command(&command, output_path, &[cancel]).await
+}
+
+/// Builds the manifest entry for the split source.
+async fn describe_source(
+    request: &SplitRequest,
+    size_bytes: u64,
+    probe: MediaProbe,
+) -> Result<ManifestSource, AppError> {
+    let file_name = Path::new(&request.input_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Ok(ManifestSource {
+        file_name,
+        size_bytes,
+        duration_secs: probe.duration_secs,
+        streams: probe.streams,
+        hashes: hash_file_blocking(&request.input_path, &request.checksums).await?,
+    })
+}
+
+/// Builds the manifest entry for a written partition.
+async fn describe_partition(
+    index: u32,
+    file_name: String,
+    path: &str,
+    segments: &[Segment],
+    algorithms: &[HashAlgorithm],
+) -> Result<ManifestPartition, AppError> {
+    let size_bytes = std::fs::metadata(path)
+        .map_err(|e| AppError::io("Failed to read metadata of", Path::new(path), &e))?
+        .len();
+
+    Ok(ManifestPartition {
+        index,
+        file_name,
+        size_bytes,
+        segments: segments
+            .iter()
+            .map(|seg| TimeInterval {
+                start_secs: seg.start,
+                end_secs: seg.end,
+            })
+            .collect(),
+        hashes: hash_file_blocking(path, algorithms).await?,
+    })
+}
+
+/// Hashes a file on the blocking thread pool so large partitions don't stall the async runtime.
+async fn hash_file_blocking(
+    path: &str,
+    algorithms: &[HashAlgorithm],
+) -> Result<FileHashes, AppError> {
+    let path = path.to_string();
+    let algorithms = algorithms.to_vec();
+    tauri::async_runtime::spawn_blocking(move || hash_file(Path::new(&path), &algorithms))
+        .await
+        .map_err(|e| AppError::internal(format!("Hashing task failed: {e}")))?
+}
+
 /// Computes the included intervals (complement of exclusions within `[0, duration]`).
+///
+/// Expects exclusions normalised by [`normalize_exclusions`].
 fn compute_included_intervals(exclusions: &[TimeInterval], duration: f64) -> Vec<Segment> {
     let mut sorted = exclusions.to_vec();
     sorted.sort_by(|a, b| {
@@ -193,114 +744,341 @@ fn map_partition_to_original_segments(
     segments
 }
 
-/// Gets video duration by running ffmpeg -i and parsing the output.
-async fn get_duration_from_ffmpeg(file_path: &str) -> Result<f64, String> {
-    let (_, stderr, _) = run_ffmpeg(&["-i", file_path, "-hide_banner"]).await?;
-
-    for line in stderr.lines() {
-        if let Some(idx) = line.find("Duration:") {
-            let after = &line[idx + 9..];
-            let time_str = after.trim().split(',').next().unwrap_or("").trim();
-            let parts: Vec<&str> = time_str.split(':').collect();
-            if parts.len() == 3 {
-                let hours: f64 = parts[0].parse().unwrap_or(0.0);
-                let minutes: f64 = parts[1].parse().unwrap_or(0.0);
-                let seconds: f64 = parts[2].parse().unwrap_or(0.0);
-                return Ok(hours.mul_add(3600.0, minutes.mul_add(60.0, seconds)));
-            }
-        }
-    }
-
-    Err("Could not determine video duration from FFmpeg output".to_string())
-}
-
-/// Extracts a single segment from the input using `ffmpeg -c copy`.
-async fn extract_segment(
+/// Arguments that extract a single segment from the input, e.g. with `-c copy`.
+fn extract_args(
     input_path: &str,
     output_path: &str,
     start_secs: f64,
     end_secs: f64,
-) -> Result<(), String> {
-    let start_str = format_ffmpeg_time(start_secs);
-    let end_str = format_ffmpeg_time(end_secs);
-
-    let (_, stderr, exit_code) = run_ffmpeg(&[
+    codec_args: &[&str],
+) -> Vec<String> {
+    let input = [
         "-i",
         input_path,
         "-ss",
-        &start_str,
+        &format_ffmpeg_time(start_secs),
         "-to",
-        &end_str,
-        "-c",
-        "copy",
-        "-avoid_negative_ts",
-        "make_zero",
-        "-y",
-        output_path,
-    ])
-    .await?;
+        &format_ffmpeg_time(end_secs),
+    ];
+    let output = ["-avoid_negative_ts", "make_zero", "-y", output_path];
+    input
+        .iter()
+        .chain(codec_args)
+        .chain(&output)
+        .map(|arg| (*arg).to_string())
+        .collect()
+}
+
+/// Runs `command`, writing its concat list first and removing it afterwards.
+async fn run_command(
+    command: &FfmpegCommand,
+    output_path: &str,
+    cancel: &[&AtomicBool],
+) -> Result<(), AppError> {
+    if let Some((list_path, content)) = &command.concat_list {
+        std::fs::write(list_path, content)
+            .map_err(|e| AppError::io("Failed to write concat list", list_path, &e))?;
+    }
+
+    let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+    let result = run_ffmpeg_cancellable(&args, cancel).await;
+
+    if let Some((list_path, _)) = &command.concat_list {
+        let _ = std::fs::remove_file(list_path);
+    }
+    let (_, stderr, exit_code) = result?;
 
     match exit_code {
         Some(0) => Ok(()),
-        Some(code) => Err(format!("FFmpeg exited with code {code}: {stderr}")),
-        None => {
-            if Path::new(output_path).exists() {
-                Ok(())
-            } else {
-                Err(format!("FFmpeg terminated unexpectedly: {stderr}"))
-            }
-        }
+        None if Path::new(output_path).exists() => Ok(()),
+        _ => Err(AppError::ffmpeg(
+            "Failed to write output",
+            Some(output_path),
+            exit_code,
+            &stderr,
+        )),
     }
 }
 
+/// One `file` directive of a concat demuxer list, optionally trimmed to a range.
+struct ConcatEntry<'a> {
+    path: &'a str,
+    inpoint: Option<f64>,
+    outpoint: Option<f64>,
+}
+
 /// Concatenates multiple segment files into a single output using the concat demuxer.
-async fn concat_segments(
+///
+/// The concat list is written to `work_dir`, which should be a per-job scratch directory.
+pub async fn concat_segments(
     segment_paths: &[String],
     output_path: &str,
     work_dir: &Path,
-) -> Result<(), String> {
-    // Write the concat list file
-    let list_path = work_dir.join("_temp_concat_list.txt");
-    let list_str = list_path.to_string_lossy().to_string();
-
-    let mut list_content = String::new();
-    for path in segment_paths {
-        // FFmpeg concat demuxer needs forward slashes or escaped backslashes
-        let escaped = path.replace('\\', "/");
-        list_content.push_str(&format!("file '{escaped}'\n"));
-    }
+    cancel: &AtomicBool,
+) -> Result<(), AppError> {
+    let entries: Vec<ConcatEntry> = segment_paths
+        .iter()
+        .map(|path| ConcatEntry {
+            path,
+            inpoint: None,
+            outpoint: None,
+        })
+        .collect();
 
-    std::fs::write(&list_path, &list_content)
-        .map_err(|e| format!("Failed to write concat list: {e}"))?;
+    let command = concat_command(&entries, output_path, work_dir, COPY_ALL);
+    run_command(&command, output_path, &[cancel]).await
+}
 
-    let (_, stderr, exit_code) = run_ffmpeg(&[
-        "-f",
-        "concat",
-        "-safe",
-        "0",
-        "-i",
-        &list_str,
-        "-c",
-        "copy",
-        "-y",
-        output_path,
-    ])
-    .await?;
+/// Builds a concat of `entries` encoded with `codec_args`, with the list placed in
+/// `work_dir`.
+fn concat_command(
+    entries: &[ConcatEntry<'_>],
+    output_path: &str,
+    work_dir: &Path,
+    codec_args: &[&str],
+) -> FfmpegCommand {
+    // Name the list after the output so concurrent jobs sharing a scratch dir don't collide
+    let output_name = Path::new(output_path)
+        .file_name()
+        .map_or_else(|| "output".to_string(), |n| n.to_string_lossy().to_string());
+    let list_path = work_dir.join(format!("{output_name}.ffconcat"));
 
-    // Clean up the list file
-    let _ = std::fs::remove_file(&list_path);
+    let list = list_path.to_string_lossy();
+    let input = ["-f", "concat", "-safe", "0", "-i", &list];
+    let output = ["-avoid_negative_ts", "make_zero", "-y", output_path];
+    let args = input
+        .iter()
+        .chain(codec_args)
+        .chain(&output)
+        .map(|arg| (*arg).to_string())
+        .collect();
 
-    match exit_code {
-        Some(0) => Ok(()),
-        Some(code) => Err(format!(
-            "FFmpeg concat exited with code {code}: {stderr}"
-        )),
-        None => {
-            if Path::new(output_path).exists() {
-                Ok(())
-            } else {
-                Err(format!("FFmpeg concat failed: {stderr}"))
-            }
+    FfmpegCommand {
+        args,
+        concat_list: Some((list_path, render_concat_list(entries))),
+    }
+}
+
+/// Renders an `ffconcat` list. Paths use forward slashes and single quotes are escaped.
+fn render_concat_list(entries: &[ConcatEntry]) -> String {
+    let mut content = String::from("ffconcat version 1.0\n");
+    for entry in entries {
+        let escaped = entry.path.replace('\\', "/").replace('\'', "'\\''");
+        let _ = writeln!(content, "file '{escaped}'");
+        if let Some(inpoint) = entry.inpoint {
+            let _ = writeln!(content, "inpoint {inpoint:.6}");
+        }
+        if let Some(outpoint) = entry.outpoint {
+            let _ = writeln!(content, "outpoint {outpoint:.6}");
         }
     }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_concat_list_with_ranges() {
+        let entries = [
+            ConcatEntry {
+                path: "C:\\videos\\movie.mp4",
+                inpoint: Some(0.0),
+                outpoint: Some(20.0),
+            },
+            ConcatEntry {
+                path: "C:\\videos\\movie.mp4",
+                inpoint: Some(40.0),
+                outpoint: Some(55.5),
+            },
+        ];
+
+        assert_eq!(
+            render_concat_list(&entries),
+            "ffconcat version 1.0\n\
+             file 'C:/videos/movie.mp4'\ninpoint 0.000000\noutpoint 20.000000\n\
+             file 'C:/videos/movie.mp4'\ninpoint 40.000000\noutpoint 55.500000\n"
+        );
+    }
+
+    #[test]
+    fn test_render_concat_list_escapes_quotes() {
+        let entries = [ConcatEntry {
+            path: "/videos/it's.mp4",
+            inpoint: None,
+            outpoint: None,
+        }];
+
+        assert_eq!(
+            render_concat_list(&entries),
+            "ffconcat version 1.0\nfile '/videos/it'\\''s.mp4'\n"
+        );
+    }
+
+    #[test]
+    fn test_worker_count_is_clamped() {
+        assert_eq!(worker_count(None, 5), 1);
+        assert_eq!(worker_count(Some(0), 5), 1);
+        assert_eq!(worker_count(Some(4), 5), 4);
+        assert_eq!(worker_count(Some(8), 3), 3);
+        assert_eq!(worker_count(Some(4), 0), 1);
+    }
+
+    #[test]
+    fn test_map_partition_across_exclusion() {
+        let included = compute_included_intervals(
+            &[TimeInterval {
+                start_secs: 20.0,
+                end_secs: 40.0,
+            }],
+            100.0,
+        );
+        let segments = map_partition_to_original_segments(10.0, 30.0, &included);
+
+        assert_eq!(segments.len(), 2);
+        assert!((segments[0].start - 10.0).abs() < f64::EPSILON);
+        assert!((segments[0].end - 20.0).abs() < f64::EPSILON);
+        assert!((segments[1].start - 40.0).abs() < f64::EPSILON);
+        assert!((segments[1].end - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_naming_template() {
+        assert_eq!(partition_file_name("{n} {stem}.{ext}", "talk", "mkv", 2), "3 talk.mkv");
+        assert_eq!(
+            partition_file_name(DEFAULT_NAMING_TEMPLATE, "talk", "mkv", 0),
+            "talk_part1.mkv"
+        );
+
+        assert!(validate_naming_template("{stem}_{n}.{ext}", "talk", "mp4").is_ok());
+        assert!(validate_naming_template("{stem}.{ext}", "talk", "mp4").is_err());
+        assert!(validate_naming_template("../{stem}_{n}.{ext}", "talk", "mp4").is_err());
+        assert!(validate_naming_template("parts/{n}.{ext}", "talk", "mp4").is_err());
+    }
+
+    #[test]
+    fn test_keyframe_at_or_before() {
+        let keyframes = [0.0, 2.5, 5.0];
+        assert_eq!(keyframe_at_or_before(&keyframes, 4.9), Some(2.5));
+        assert_eq!(keyframe_at_or_before(&keyframes, 5.0), Some(5.0));
+        assert_eq!(keyframe_at_or_before(&keyframes[1..], 1.0), None);
+        assert_eq!(keyframe_at_or_before(&[], 1.0), None);
+    }
+
+    #[test]
+    fn test_partition_command() {
+        let scratch = Path::new("/tmp/scratch");
+        let single = partition_command(
+            "/in.mp4",
+            &[Segment {
+                start: 0.0,
+                end: 61.5,
+            }],
+            "/out/in_part1.mp4",
+            scratch,
+            COPY_ALL,
+        );
+        assert!(single.concat_list.is_none());
+        assert_eq!(
+            single.args[..8],
+            ["-i", "/in.mp4", "-ss", "00:00:00.000", "-to", "00:01:01.500", "-c", "copy"]
+        );
+
+        let spanning = partition_command(
+            "/in.mp4",
+            &[
+                Segment {
+                    start: 0.0,
+                    end: 20.0,
+                },
+                Segment {
+                    start: 40.0,
+                    end: 50.0,
+                },
+            ],
+            "/out/in_part2.mp4",
+            scratch,
+            &["-map", "0:a", "-c:a", "flac"],
+        );
+        let (list_path, content) = spanning.concat_list.unwrap();
+        assert_eq!(list_path, scratch.join("in_part2.mp4.ffconcat"));
+        assert!(content.contains("inpoint 40.000000"));
+        assert_eq!(spanning.args[5], list_path.to_string_lossy());
+        assert_eq!(spanning.args[6..10], ["-map", "0:a", "-c:a", "flac"]);
+        assert_eq!(spanning.args.last().unwrap(), "/out/in_part2.mp4");
+    }
+
+    #[test]
+    fn test_preview_points_use_original_timeline() {
+        let metadata = VideoMetadata {
+            file_path: String::new(),
+            file_name: String::new(),
+            file_size: 1000,
+            duration_secs: 100.0,
+            width: 1920,
+            height: 1080,
+            video_codec: "h264".to_string(),
+            audio_codec: None,
+            bitrate: 0,
+            format: "mp4".to_string(),
+            frame_rate: 30.0,
+        };
+        let exclusions = [TimeInterval {
+            start_secs: 20.0,
+            end_secs: 40.0,
+        }];
+
+        // 80 s remain, so two 400-byte partitions of 40 s each
+        let points =
+            preview_partition_points(&metadata, PartitionTarget::Size(400), &exclusions).unwrap();
+
+        assert_eq!(points.len(), 2);
+        assert!((points[0].start_secs - 0.0).abs() < f64::EPSILON);
+        assert!((points[0].end_secs - 60.0).abs() < f64::EPSILON);
+        assert!((points[1].start_secs - 60.0).abs() < f64::EPSILON);
+        assert!((points[1].end_secs - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_partition_intervals_follow_split() {
+        let metadata = VideoMetadata {
+            file_path: String::new(),
+            file_name: String::new(),
+            file_size: 1000,
+            duration_secs: 100.0,
+            width: 0,
+            height: 0,
+            video_codec: String::new(),
+            audio_codec: Some("aac".to_string()),
+            bitrate: 0,
+            format: "mp4".to_string(),
+            frame_rate: 0.0,
+        };
+        let exclusions = [TimeInterval {
+            start_secs: 20.0,
+            end_secs: 40.0,
+        }];
+
+        // Same partitions as the split: 0-20 + 40-60, then 60-100
+        let parts =
+            partition_intervals(&metadata, Some(PartitionTarget::Size(400)), &exclusions).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].len(), 2);
+        assert!((parts[0][1].start_secs - 40.0).abs() < 1e-9);
+        assert!((parts[0][1].end_secs - 60.0).abs() < 1e-9);
+        assert!((parts[1][0].start_secs - 60.0).abs() < 1e-9);
+
+        // Without a target, one part of everything that isn't excluded
+        let whole = partition_intervals(&metadata, None, &exclusions).unwrap();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].len(), 2);
+        assert!((whole[0][1].end_secs - 100.0).abs() < 1e-9);
+
+        let everything = [TimeInterval {
+            start_secs: 0.0,
+            end_secs: 100.0,
+        }];
+        assert!(partition_intervals(&metadata, None, &everything).is_err());
+    }
 }
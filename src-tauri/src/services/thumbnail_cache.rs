@@ -0,0 +1,336 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+use crate::models::project::FileFingerprint;
+use crate::models::thumbnail::{CacheEntry, CacheIndex, ThumbnailCacheInfo, CACHE_INDEX_VERSION};
+
+/// Subdirectory of the app cache directory holding the cache of generated thumbnails,
+/// sprite sheets and converted previews.
+const CACHE_DIR_NAME: &str = "thumbnails";
+
+/// Index of cached entries, next to the entry directories.
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// Size above which least recently used entries are evicted.
+pub const CACHE_LIMIT_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Serializes read-modify-write cycles of the index between concurrent commands.
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+/// Per-key locks of entries being looked up or generated.
+static IN_FLIGHT: Mutex<Vec<(String, Weak<tokio::sync::Mutex<()>>)>> = Mutex::new(Vec::new());
+
+/// Held while an entry is looked up and generated; see [`lock_entry`].
+pub struct EntryGuard {
+    _lock: tokio::sync::OwnedMutexGuard<()>,
+}
+
+/// Waits until no other request is generating the entry `key`, then holds it until the
+/// guard is dropped.
+///
+/// Taken before [`lookup`], so a second request for the same key finds the first one's
+/// result instead of running `FFmpeg` into the same files.
+pub async fn lock_entry(key: &str) -> EntryGuard {
+    let lock = {
+        let mut in_flight = IN_FLIGHT
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        in_flight.retain(|(_, lock)| lock.strong_count() > 0);
+        let existing = in_flight
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, lock)| lock.upgrade());
+        existing.unwrap_or_else(|| {
+            let lock = Arc::new(tokio::sync::Mutex::new(()));
+            in_flight.push((key.to_string(), Arc::downgrade(&lock)));
+            lock
+        })
+    };
+    EntryGuard {
+        _lock: lock.lock_owned().await,
+    }
+}
+
+/// Key of a source file in a particular version and a generated variant of it.
+///
+/// Editing or replacing the file changes its fingerprint and therefore the key.
+pub fn cache_key(source_path: &str, fingerprint: FileFingerprint, variant: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        source_path,
+        &fingerprint.size_bytes.to_string(),
+        &fingerprint.modified_millis.to_string(),
+        variant,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let hex = format!("{:x}", hasher.finalize());
+    hex[..32].to_string()
+}
+
+/// Directory holding the files of an entry.
+pub fn entry_dir(cache_dir: &Path, key: &str) -> PathBuf {
+    cache_dir.join(CACHE_DIR_NAME).join(key)
+}
+
+/// Looks up an entry and marks it as recently used.
+pub fn lookup(cache_dir: &Path, key: &str) -> Result<Option<CacheEntry>, AppError> {
+    let _guard = lock_index();
+    let mut index = read_index(cache_dir);
+    let Some(entry) = index.entries.iter_mut().find(|e| e.key == key) else {
+        return Ok(None);
+    };
+    entry.last_used_millis = now_millis();
+    let entry = entry.clone();
+    write_index(cache_dir, &index)?;
+    Ok(Some(entry))
+}
+
+/// Records the files written to [`entry_dir`] as an entry, replacing any earlier one
+/// with the same key.
+///
+/// Least recently used entries are then evicted until the cache fits in `limit_bytes`;
+/// the new entry itself is always kept.
+pub fn insert(
+    cache_dir: &Path,
+    key: &str,
+    source_path: &str,
+    fingerprint: FileFingerprint,
+    variant: &str,
+    limit_bytes: u64,
+) -> Result<CacheEntry, AppError> {
+    let entry = CacheEntry {
+        key: key.to_string(),
+        source_path: source_path.to_string(),
+        fingerprint,
+        variant: variant.to_string(),
+        size_bytes: dir_size(&entry_dir(cache_dir, key)),
+        last_used_millis: now_millis(),
+    };
+
+    let _guard = lock_index();
+    let mut index = read_index(cache_dir);
+    index.entries.retain(|e| e.key != key);
+    index.entries.push(entry.clone());
+
+    for evicted in evict(&mut index.entries, key, limit_bytes) {
+        let _ = std::fs::remove_dir_all(entry_dir(cache_dir, &evicted.key));
+    }
+
+    write_index(cache_dir, &index)?;
+    Ok(entry)
+}
+
+/// Removes one entry, e.g. after finding its files incomplete.
+pub fn remove(cache_dir: &Path, key: &str) -> Result<(), AppError> {
+    let _guard = lock_index();
+    let mut index = read_index(cache_dir);
+    index.entries.retain(|e| e.key != key);
+    let _ = std::fs::remove_dir_all(entry_dir(cache_dir, key));
+    write_index(cache_dir, &index)
+}
+
+/// Describes the cache contents, most recently used first.
+pub fn inspect(cache_dir: &Path, limit_bytes: u64) -> ThumbnailCacheInfo {
+    let _guard = lock_index();
+    let mut entries = read_index(cache_dir).entries;
+    entries.sort_by_key(|e| std::cmp::Reverse(e.last_used_millis));
+
+    ThumbnailCacheInfo {
+        directory: cache_dir.join(CACHE_DIR_NAME).to_string_lossy().to_string(),
+        total_bytes: entries.iter().map(|e| e.size_bytes).sum(),
+        limit_bytes,
+        entries,
+    }
+}
+
+/// Deletes every cached file, including leftovers of interrupted generations.
+///
+/// Returns the number of bytes freed.
+pub fn clear(cache_dir: &Path) -> Result<u64, AppError> {
+    let _guard = lock_index();
+    let root = cache_dir.join(CACHE_DIR_NAME);
+    let freed = dir_size(&root);
+    if root.exists() {
+        std::fs::remove_dir_all(&root)
+            .map_err(|e| AppError::io("Failed to clear thumbnail cache", &root, &e))?;
+    }
+    Ok(freed)
+}
+
+/// Removes least recently used entries other than `keep` until the total fits in
+/// `limit_bytes`, returning the removed entries.
+fn evict(entries: &mut Vec<CacheEntry>, keep: &str, limit_bytes: u64) -> Vec<CacheEntry> {
+    entries.sort_by_key(|e| e.last_used_millis);
+
+    let mut total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+    let mut evicted = Vec::new();
+    let mut i = 0;
+    while total > limit_bytes && i < entries.len() {
+        if entries[i].key == keep {
+            i += 1;
+            continue;
+        }
+        let entry = entries.remove(i);
+        total -= entry.size_bytes;
+        evicted.push(entry);
+    }
+    evicted
+}
+
+fn lock_index() -> std::sync::MutexGuard<'static, ()> {
+    // The lock guards no data, so a panic while holding it leaves nothing inconsistent
+    INDEX_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Reads the index; a missing, unreadable or newer index counts as an empty cache.
+fn read_index(cache_dir: &Path) -> CacheIndex {
+    let path = cache_dir.join(CACHE_DIR_NAME).join(INDEX_FILE_NAME);
+    let index = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<CacheIndex>(&content).ok())
+        .filter(|index| index.version <= CACHE_INDEX_VERSION)
+        .unwrap_or_default();
+
+    // Entries whose files were deleted behind our back are forgotten
+    CacheIndex {
+        version: CACHE_INDEX_VERSION,
+        entries: index
+            .entries
+            .into_iter()
+            .filter(|e| entry_dir(cache_dir, &e.key).is_dir())
+            .collect(),
+    }
+}
+
+fn write_index(cache_dir: &Path, index: &CacheIndex) -> Result<(), AppError> {
+    let root = cache_dir.join(CACHE_DIR_NAME);
+    std::fs::create_dir_all(&root)
+        .map_err(|e| AppError::io("Failed to create thumbnail cache", &root, &e))?;
+
+    let json = serde_json::to_string_pretty(index)
+        .map_err(|e| AppError::internal(format!("Failed to serialize cache index: {e}")))?;
+
+    // Write to a temporary file first so a crash can't leave a truncated index
+    let path = root.join(INDEX_FILE_NAME);
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| AppError::io("Failed to write cache index", &temp_path, &e))?;
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| AppError::io("Failed to save cache index", &path, &e))
+}
+
+/// Combined size of the files under `path`, or 0 if it doesn't exist.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            _ => entry.metadata().map_or(0, |m| m.len()),
+        })
+        .sum()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "video-partitioner-cache-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    const FINGERPRINT: FileFingerprint = FileFingerprint {
+        size_bytes: 1000,
+        modified_millis: 42,
+    };
+
+    fn add_entry(cache_dir: &Path, variant: &str, bytes: usize, limit: u64) -> String {
+        let key = cache_key("/videos/a.mp4", FINGERPRINT, variant);
+        let dir = entry_dir(cache_dir, &key);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("thumb_0001.jpg"), vec![0u8; bytes]).unwrap();
+        insert(
+            cache_dir,
+            &key,
+            "/videos/a.mp4",
+            FINGERPRINT,
+            variant,
+            limit,
+        )
+        .unwrap();
+        key
+    }
+
+    #[test]
+    fn test_key_depends_on_fingerprint_and_variant() {
+        let key = cache_key("/videos/a.mp4", FINGERPRINT, "strip-20x60");
+        assert_eq!(key.len(), 32);
+        assert_eq!(key, cache_key("/videos/a.mp4", FINGERPRINT, "strip-20x60"));
+
+        let edited = FileFingerprint {
+            modified_millis: 43,
+            ..FINGERPRINT
+        };
+        assert_ne!(key, cache_key("/videos/a.mp4", edited, "strip-20x60"));
+        assert_ne!(key, cache_key("/videos/a.mp4", FINGERPRINT, "strip-40x60"));
+    }
+
+    #[test]
+    fn test_least_recently_used_entries_are_evicted() {
+        let dir = temp_cache_dir("lru");
+
+        let first = add_entry(&dir, "strip-1", 400, 1000);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let second = add_entry(&dir, "strip-2", 400, 1000);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        // Reading the first entry makes the second the least recently used
+        assert!(lookup(&dir, &first).unwrap().is_some());
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let third = add_entry(&dir, "strip-3", 400, 1000);
+
+        let info = inspect(&dir, 1000);
+        let keys: Vec<&str> = info.entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec![third.as_str(), first.as_str()]);
+        assert_eq!(info.total_bytes, 800);
+        assert!(!entry_dir(&dir, &second).exists());
+
+        assert!(clear(&dir).unwrap() >= 800);
+        assert!(inspect(&dir, 1000).entries.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_entry_lock_is_per_key() {
+        let first = lock_entry("entry-a").now_or_never().unwrap();
+        assert!(lock_entry("entry-a").now_or_never().is_none());
+        assert!(lock_entry("entry-b").now_or_never().is_some());
+
+        drop(first);
+        assert!(lock_entry("entry-a").now_or_never().is_some());
+    }
+}
@@ -0,0 +1,399 @@
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use futures::stream::{self, TryStreamExt};
+
+use crate::error::AppError;
+use crate::models::thumbnail::{SpriteSheet, ThumbnailReady};
+use crate::services::projects::fingerprint;
+use crate::services::thumbnail_cache;
+use crate::utils::ffmpeg_wrapper::{format_ffmpeg_time, probe_media, run_ffmpeg};
+
+/// Upper bound on concurrent `FFmpeg` processes; each one mostly waits on a seek.
+const MAX_THUMBNAIL_WORKERS: usize = 6;
+
+/// Files in a sprite sheet's cache entry: the image, its `WebVTT` track and a
+/// description of its layout.
+const SPRITE_IMAGE_FILE_NAME: &str = "sprite.jpg";
+const SPRITE_TRACK_FILE_NAME: &str = "thumbnails.vtt";
+const SPRITE_DESCRIPTION_FILE_NAME: &str = "sprite.json";
+
+/// Generates `count` evenly-spaced thumbnails of a video, `height` pixels high.
+///
+/// Up to [`thumbnail_workers`] thumbnails are extracted at once, each with fast input-level
+/// seeking (`-ss` before `-i`). `on_ready` is called as soon as each file exists, in
+/// completion order. Returns all paths in timeline order.
+///
+/// Strips are kept in the thumbnail cache under `cache_dir`, keyed by the video's path,
+/// size and modification time plus `count` and `height`; a cached strip is returned
+/// without running `FFmpeg`.
+pub async fn generate_thumbnails(
+    cache_dir: &Path,
+    video_path: &str,
+    count: u32,
+    height: u32,
+    on_ready: &(dyn Fn(ThumbnailReady) + Send + Sync),
+) -> Result<Vec<String>, AppError> {
+    if count == 0 {
+        return Err(AppError::invalid("Thumbnail count must be greater than 0"));
+    }
+
+    let fingerprint = fingerprint(Path::new(video_path))?;
+    let variant = format!("strip-{count}x{height}");
+    let key = thumbnail_cache::cache_key(video_path, fingerprint, &variant);
+    let thumb_dir = thumbnail_cache::entry_dir(cache_dir, &key);
+    let paths: Vec<PathBuf> = (1..=count)
+        .map(|i| thumb_dir.join(format!("thumb_{i:04}.jpg")))
+        .collect();
+
+    let _generating = thumbnail_cache::lock_entry(&key).await;
+    if thumbnail_cache::lookup(cache_dir, &key)?.is_some() {
+        if paths.iter().all(|p| p.exists()) {
+            return Ok(path_strings(&paths));
+        }
+        thumbnail_cache::remove(cache_dir, &key)?;
+    }
+
+    // Leftovers of an interrupted run for the same key are replaced
+    let _ = std::fs::remove_dir_all(&thumb_dir);
+    std::fs::create_dir_all(&thumb_dir)
+        .map_err(|e| AppError::io("Failed to create thumbnail directory", &thumb_dir, &e))?;
+
+    let result = extract_all(video_path, &paths, height, on_ready).await;
+    if let Err(e) = result {
+        let _ = std::fs::remove_dir_all(&thumb_dir);
+        return Err(e);
+    }
+
+    thumbnail_cache::insert(
+        cache_dir,
+        &key,
+        video_path,
+        fingerprint,
+        &variant,
+        thumbnail_cache::CACHE_LIMIT_BYTES,
+    )?;
+    Ok(path_strings(&paths))
+}
+
+/// Renders `count` thumbnails of a video, `height` pixels high, into one tiled sprite
+/// sheet with a single `FFmpeg` pass, and writes a `WebVTT` track mapping each tile's
+/// time range to its `#xywh=` coordinates.
+///
+/// Only keyframes are decoded, so each tile shows the keyframe nearest the start of
+/// its range. Sheets are cached like strips of separate thumbnails.
+pub async fn generate_sprite_sheet(
+    cache_dir: &Path,
+    video_path: &str,
+    count: u32,
+    height: u32,
+) -> Result<SpriteSheet, AppError> {
+    if count == 0 || height == 0 {
+        return Err(AppError::invalid(
+            "Thumbnail count and height must be greater than 0",
+        ));
+    }
+
+    let fingerprint = fingerprint(Path::new(video_path))?;
+    let variant = format!("sprite-{count}x{height}");
+    let key = thumbnail_cache::cache_key(video_path, fingerprint, &variant);
+    let sheet_dir = thumbnail_cache::entry_dir(cache_dir, &key);
+    let description_path = sheet_dir.join(SPRITE_DESCRIPTION_FILE_NAME);
+
+    let _generating = thumbnail_cache::lock_entry(&key).await;
+    if thumbnail_cache::lookup(cache_dir, &key)?.is_some() {
+        let cached = std::fs::read_to_string(&description_path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<SpriteSheet>(&json).ok())
+            .filter(|sheet| Path::new(&sheet.image_path).exists());
+        if let Some(sheet) = cached {
+            return Ok(sheet);
+        }
+        thumbnail_cache::remove(cache_dir, &key)?;
+    }
+
+    let _ = std::fs::remove_dir_all(&sheet_dir);
+    std::fs::create_dir_all(&sheet_dir)
+        .map_err(|e| AppError::io("Failed to create thumbnail directory", &sheet_dir, &e))?;
+
+    let result = render_sprite_sheet(video_path, &sheet_dir, count, height).await;
+    let sheet = match result {
+        Ok(sheet) => sheet,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&sheet_dir);
+            return Err(e);
+        }
+    };
+
+    let json = serde_json::to_string_pretty(&sheet)
+        .map_err(|e| AppError::internal(format!("Failed to serialize sprite sheet: {e}")))?;
+    std::fs::write(&description_path, json)
+        .map_err(|e| AppError::io("Failed to write sprite sheet", &description_path, &e))?;
+
+    thumbnail_cache::insert(
+        cache_dir,
+        &key,
+        video_path,
+        fingerprint,
+        &variant,
+        thumbnail_cache::CACHE_LIMIT_BYTES,
+    )?;
+    Ok(sheet)
+}
+
+/// Writes the sprite sheet image and its `WebVTT` track into `sheet_dir`.
+async fn render_sprite_sheet(
+    video_path: &str,
+    sheet_dir: &Path,
+    count: u32,
+    height: u32,
+) -> Result<SpriteSheet, AppError> {
+    let probe = probe_media(video_path).await?;
+    if probe.duration_secs <= 0.0 {
+        return Err(AppError::invalid("Video has no duration"));
+    }
+
+    let (width, source_height) = probe.resolution;
+    let columns = sprite_columns(count);
+    let sheet = SpriteSheet {
+        image_path: path_string(&sheet_dir.join(SPRITE_IMAGE_FILE_NAME)),
+        vtt_path: path_string(&sheet_dir.join(SPRITE_TRACK_FILE_NAME)),
+        count,
+        columns,
+        rows: count.div_ceil(columns),
+        tile_width: tile_width(width, source_height, height),
+        tile_height: height,
+        interval_secs: probe.duration_secs / f64::from(count),
+    };
+
+    // `fps` picks one frame per tile and `tile` lays them out; decoding only keyframes
+    // keeps the single pass fast even for long videos
+    let filter = format!(
+        "fps={count}/{duration},scale={w}:{h},tile={columns}x{rows}",
+        duration = probe.duration_secs,
+        w = sheet.tile_width,
+        h = sheet.tile_height,
+        columns = sheet.columns,
+        rows = sheet.rows,
+    );
+    let (_, stderr, exit_code) = run_ffmpeg(&[
+        "-hide_banner",
+        "-skip_frame",
+        "nokey",
+        "-i",
+        video_path,
+        "-vf",
+        &filter,
+        "-frames:v",
+        "1",
+        "-q:v",
+        "5",
+        "-y",
+        &sheet.image_path,
+    ])
+    .await?;
+
+    if exit_code.is_some_and(|code| code != 0) || !Path::new(&sheet.image_path).exists() {
+        return Err(AppError::ffmpeg(
+            "Sprite sheet failed",
+            Some(video_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+
+    let vtt_path = Path::new(&sheet.vtt_path);
+    std::fs::write(
+        vtt_path,
+        sprite_vtt(&sheet, probe.duration_secs, SPRITE_IMAGE_FILE_NAME),
+    )
+    .map_err(|e| AppError::io("Failed to write thumbnail track", vtt_path, &e))?;
+
+    Ok(sheet)
+}
+
+/// Extracts one thumbnail per path, spread evenly over the video.
+async fn extract_all(
+    video_path: &str,
+    paths: &[PathBuf],
+    height: u32,
+    on_ready: &(dyn Fn(ThumbnailReady) + Send + Sync),
+) -> Result<(), AppError> {
+    let duration = probe_media(video_path).await?.duration_secs;
+    if duration <= 0.0 {
+        return Err(AppError::invalid("Video has no duration"));
+    }
+
+    let count = u32::try_from(paths.len()).unwrap_or(u32::MAX);
+    let scale_filter = format!("scale=-1:{height}");
+    let timestamps = thumbnail_timestamps(duration, count);
+
+    stream::iter(paths.iter().zip(timestamps).enumerate().map(Ok))
+        .try_for_each_concurrent(thumbnail_workers(), |(index, (path, timestamp))| {
+            let scale_filter = &scale_filter;
+            async move {
+                let output = path.to_string_lossy().to_string();
+                extract_thumbnail(video_path, timestamp, scale_filter, &output, index).await?;
+                on_ready(ThumbnailReady {
+                    video_path: video_path.to_string(),
+                    index: u32::try_from(index).unwrap_or(u32::MAX),
+                    total: count,
+                    path: output,
+                });
+                Ok(())
+            }
+        })
+        .await
+}
+
+/// Writes one thumbnail taken at `timestamp` to `output`.
+async fn extract_thumbnail(
+    video_path: &str,
+    timestamp: f64,
+    scale_filter: &str,
+    output: &str,
+    index: usize,
+) -> Result<(), AppError> {
+    let ss_arg = format_ffmpeg_time(timestamp);
+    let (_, stderr, exit_code) = run_ffmpeg(&[
+        "-ss",
+        &ss_arg,
+        "-i",
+        video_path,
+        "-vframes",
+        "1",
+        "-vf",
+        scale_filter,
+        "-q:v",
+        "5",
+        "-y",
+        output,
+    ])
+    .await?;
+
+    // A process killed by a signal has no exit code and may not have written anything
+    if exit_code != Some(0) || !Path::new(output).exists() {
+        return Err(AppError::ffmpeg(
+            &format!("Thumbnail {index} failed"),
+            Some(video_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+    Ok(())
+}
+
+fn path_strings(paths: &[PathBuf]) -> Vec<String> {
+    paths.iter().map(|p| path_string(p)).collect()
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+/// Timestamps at the middle of `count` equal slices of the video.
+pub fn thumbnail_timestamps(duration: f64, count: u32) -> Vec<f64> {
+    let interval = duration / f64::from(count);
+    (0..count)
+        .map(|i| interval * (f64::from(i) + 0.5))
+        .collect()
+}
+
+/// Tiles per row, keeping the sheet roughly square.
+fn sprite_columns(count: u32) -> u32 {
+    (1..=count).find(|c| c * c >= count).unwrap_or(1)
+}
+
+/// Width of a tile `height` pixels high that keeps the video's aspect ratio, rounded to
+/// an even number for the encoder. Unknown dimensions are assumed to be 16:9.
+fn tile_width(source_width: u32, source_height: u32, height: u32) -> u32 {
+    let (w, h) = if source_width > 0 && source_height > 0 {
+        (u64::from(source_width), u64::from(source_height))
+    } else {
+        (16, 9)
+    };
+    let width = (u64::from(height) * w + h / 2) / h;
+    u32::try_from(width.div_ceil(2) * 2)
+        .unwrap_or(u32::MAX)
+        .max(2)
+}
+
+/// `WebVTT` track with one cue per tile, pointing into `image_name` with a
+/// media fragment (`#xywh=x,y,w,h`).
+fn sprite_vtt(sheet: &SpriteSheet, duration: f64, image_name: &str) -> String {
+    let mut vtt = String::from("WEBVTT\n");
+    for i in 0..sheet.count {
+        let start = sheet.interval_secs * f64::from(i);
+        let end = if i + 1 == sheet.count {
+            duration
+        } else {
+            sheet.interval_secs * f64::from(i + 1)
+        };
+        let x = (i % sheet.columns) * sheet.tile_width;
+        let y = (i / sheet.columns) * sheet.tile_height;
+        let _ = write!(
+            vtt,
+            "\n{} --> {}\n{image_name}#xywh={x},{y},{},{}\n",
+            format_ffmpeg_time(start),
+            format_ffmpeg_time(end),
+            sheet.tile_width,
+            sheet.tile_height,
+        );
+    }
+    vtt
+}
+
+/// Number of thumbnails to extract at once: one per core, within
+/// `1..=MAX_THUMBNAIL_WORKERS`.
+pub fn thumbnail_workers() -> usize {
+    thread::available_parallelism()
+        .map_or(1, usize::from)
+        .clamp(1, MAX_THUMBNAIL_WORKERS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_timestamps() {
+        assert_eq!(thumbnail_timestamps(100.0, 4), vec![12.5, 37.5, 62.5, 87.5]);
+        assert_eq!(thumbnail_timestamps(10.0, 1), vec![5.0]);
+    }
+
+    #[test]
+    fn test_sprite_geometry() {
+        assert_eq!(sprite_columns(1), 1);
+        assert_eq!(sprite_columns(20), 5);
+        assert_eq!(sprite_columns(100), 10);
+
+        assert_eq!(tile_width(1920, 1080, 60), 108);
+        assert_eq!(tile_width(1440, 1080, 90), 120);
+        // Odd widths round up to an even number
+        assert_eq!(tile_width(1000, 1000, 45), 46);
+        assert_eq!(tile_width(0, 0, 90), 160);
+    }
+
+    #[test]
+    fn test_sprite_vtt() {
+        let sheet = SpriteSheet {
+            image_path: "/cache/sprite.jpg".to_string(),
+            vtt_path: "/cache/thumbnails.vtt".to_string(),
+            count: 3,
+            columns: 2,
+            rows: 2,
+            tile_width: 108,
+            tile_height: 60,
+            interval_secs: 10.0,
+        };
+
+        assert_eq!(
+            sprite_vtt(&sheet, 29.5, "sprite.jpg"),
+            "WEBVTT\n\
+             \n00:00:00.000 --> 00:00:10.000\nsprite.jpg#xywh=0,0,108,60\n\
+             \n00:00:10.000 --> 00:00:20.000\nsprite.jpg#xywh=108,0,108,60\n\
+             \n00:00:20.000 --> 00:00:29.500\nsprite.jpg#xywh=0,60,108,60\n"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::error::AppError;
+use crate::models::video::PreviewSource;
+use crate::services::projects::fingerprint;
+use crate::services::thumbnail_cache;
+use crate::utils::ffmpeg_wrapper::{probe_media, run_ffmpeg_cancellable};
+
+/// Image formats the webview decodes natively.
+const BROWSER_IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "svg"];
+
+/// Containers the webview plays when their streams use [`BROWSER_VIDEO_CODECS`] and
+/// [`BROWSER_AUDIO_CODECS`].
+const BROWSER_VIDEO_CONTAINERS: &[&str] = &["mp4", "m4v", "mov", "webm"];
+const BROWSER_VIDEO_CODECS: &[&str] = &["h264", "vp8", "vp9", "av1"];
+const BROWSER_AUDIO_CODECS: &[&str] = &["aac", "mp3", "opus", "vorbis", "flac"];
+
+/// Images larger than this are converted to JPEG instead of PNG to keep previews quick
+/// to load.
+const PNG_MAX_SOURCE_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Height of video proxies; smaller sources keep their height.
+const PROXY_HEIGHT: u32 = 360;
+
+/// Distinguishes concurrent conversions writing into the same cache entry.
+static NEXT_PARTIAL_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Returns something the webview can show for `file_path`.
+///
+/// Browser-friendly files are returned as they are. Other images are converted to
+/// PNG (JPEG for very large ones) and other videos to a low-resolution H.264/AAC
+/// proxy with the same timeline. Converted copies are kept in the thumbnail cache
+/// under `cache_dir`, keyed by the file's path, size and modification time.
+/// Setting `cancel` stops a running conversion.
+pub async fn prepare_preview(
+    cache_dir: &Path,
+    file_path: &str,
+    cancel: &AtomicBool,
+) -> Result<PreviewSource, AppError> {
+    let source = Path::new(file_path);
+    let fingerprint = fingerprint(source)?;
+    let extension = source
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let (variant, file_name, args) = if is_image_extension(&extension) {
+        if BROWSER_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            return Ok(original(file_path));
+        }
+        if fingerprint.size_bytes > PNG_MAX_SOURCE_BYTES {
+            ("preview-jpeg", "preview.jpg", image_args(Some("3")))
+        } else {
+            ("preview-png", "preview.png", image_args(None))
+        }
+    } else {
+        let probe = probe_media(file_path).await?;
+        if browser_playable(&extension, &probe.streams) {
+            return Ok(original(file_path));
+        }
+        ("proxy-360p", "proxy.mp4", proxy_args())
+    };
+
+    let key = thumbnail_cache::cache_key(file_path, fingerprint, variant);
+    let entry_dir = thumbnail_cache::entry_dir(cache_dir, &key);
+    let output = entry_dir.join(file_name);
+
+    let _generating = thumbnail_cache::lock_entry(&key).await;
+    if thumbnail_cache::lookup(cache_dir, &key)?.is_some() {
+        if output.exists() {
+            return Ok(transcoded(&output));
+        }
+        thumbnail_cache::remove(cache_dir, &key)?;
+    }
+
+    std::fs::create_dir_all(&entry_dir)
+        .map_err(|e| AppError::io("Failed to create preview directory", &entry_dir, &e))?;
+
+    // Convert into a uniquely named file so a superseded run can't clobber a newer one
+    let partial = partial_path(&entry_dir, file_name);
+    if let Err(e) = convert(file_path, &args, &partial, cancel).await {
+        let _ = std::fs::remove_file(&partial);
+        return Err(e);
+    }
+    std::fs::rename(&partial, &output)
+        .map_err(|e| AppError::io("Failed to save preview", &output, &e))?;
+
+    thumbnail_cache::insert(
+        cache_dir,
+        &key,
+        file_path,
+        fingerprint,
+        variant,
+        thumbnail_cache::CACHE_LIMIT_BYTES,
+    )?;
+    Ok(transcoded(&output))
+}
+
+/// Runs `FFmpeg` on `input` with `args` between the input and the output file.
+async fn convert(
+    input: &str,
+    args: &[String],
+    output: &Path,
+    cancel: &AtomicBool,
+) -> Result<(), AppError> {
+    let output_str = output.to_string_lossy().to_string();
+    let mut full_args = vec!["-hide_banner", "-i", input];
+    full_args.extend(args.iter().map(String::as_str));
+    full_args.extend(["-y", &output_str]);
+
+    let (_, stderr, exit_code) = run_ffmpeg_cancellable(&full_args, &[cancel]).await?;
+    if exit_code != Some(0) || !output.exists() {
+        return Err(AppError::ffmpeg(
+            "Preview conversion failed",
+            Some(input),
+            exit_code,
+            &stderr,
+        ));
+    }
+    Ok(())
+}
+
+/// Arguments converting the first frame of an image, with a quality for JPEG output.
+fn image_args(jpeg_quality: Option<&str>) -> Vec<String> {
+    let mut args = vec!["-frames:v".to_string(), "1".to_string()];
+    if let Some(quality) = jpeg_quality {
+        args.extend(["-q:v".to_string(), quality.to_string()]);
+    }
+    args
+}
+
+/// Arguments for a small H.264/AAC proxy that starts playing before it is fully read.
+fn proxy_args() -> Vec<String> {
+    [
+        "-map",
+        "0:v:0",
+        "-map",
+        "0:a:0?",
+        "-vf",
+        &format!("scale=-2:'min({PROXY_HEIGHT},ih)'"),
+        "-c:v",
+        "libx264",
+        "-preset",
+        "veryfast",
+        "-crf",
+        "28",
+        "-pix_fmt",
+        "yuv420p",
+        "-c:a",
+        "aac",
+        "-b:a",
+        "96k",
+        "-ac",
+        "2",
+        "-movflags",
+        "+faststart",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+/// Whether the webview can play a file in this container with these streams, as
+/// reported by [`probe_media`] (`video:h264`, `audio:aac`, ...).
+fn browser_playable(extension: &str, streams: &[String]) -> bool {
+    if !BROWSER_VIDEO_CONTAINERS.contains(&extension) {
+        return false;
+    }
+    let video_ok =
+        first_codec(streams, "video:").is_some_and(|c| BROWSER_VIDEO_CODECS.contains(&c));
+    // A video without audio plays fine
+    let audio_unsupported =
+        first_codec(streams, "audio:").is_some_and(|c| !BROWSER_AUDIO_CODECS.contains(&c));
+    video_ok && !audio_unsupported
+}
+
+fn first_codec<'a>(streams: &'a [String], kind: &str) -> Option<&'a str> {
+    streams.iter().find_map(|s| s.strip_prefix(kind))
+}
+
+fn is_image_extension(extension: &str) -> bool {
+    BROWSER_IMAGE_EXTENSIONS.contains(&extension) || ["bmp", "tif", "tiff"].contains(&extension)
+}
+
+fn partial_path(entry_dir: &Path, file_name: &str) -> PathBuf {
+    let id = NEXT_PARTIAL_ID.fetch_add(1, Ordering::Relaxed);
+    entry_dir.join(format!("partial-{}-{id}-{file_name}", std::process::id()))
+}
+
+fn original(file_path: &str) -> PreviewSource {
+    PreviewSource {
+        path: file_path.to_string(),
+        transcoded: false,
+    }
+}
+
+fn transcoded(path: &Path) -> PreviewSource {
+    PreviewSource {
+        path: path.to_string_lossy().to_string(),
+        transcoded: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn streams(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_browser_playable() {
+        assert!(browser_playable(
+            "mp4",
+            &streams(&["video:h264", "audio:aac"])
+        ));
+        assert!(browser_playable("webm", &streams(&["video:vp9"])));
+
+        // Unsupported container, video codec or audio codec
+        assert!(!browser_playable(
+            "avi",
+            &streams(&["video:h264", "audio:mp3"])
+        ));
+        assert!(!browser_playable(
+            "mp4",
+            &streams(&["video:hevc", "audio:aac"])
+        ));
+        assert!(!browser_playable(
+            "mov",
+            &streams(&["video:h264", "audio:pcm_s16le"])
+        ));
+        assert!(!browser_playable("mp4", &streams(&["audio:aac"])));
+    }
+}
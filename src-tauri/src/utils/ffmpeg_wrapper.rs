@@ -1,36 +1,235 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
 use tauri::api::process::{Command, CommandEvent};
 
+use crate::error::AppError;
+
+/// How often a running process checks its cancel flag, since `FFmpeg` may print nothing
+/// for minutes during a stream copy.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Spawns the `FFmpeg` sidecar with the given arguments and collects all stderr output.
-pub async fn run_ffmpeg(args: &[&str]) -> Result<(String, String, Option<i32>), String> {
-    let (mut rx, _child) = Command::new_sidecar("ffmpeg")
-        .map_err(|e| format!("FFmpeg sidecar not found: {e}"))?
+pub async fn run_ffmpeg(args: &[&str]) -> Result<(String, String, Option<i32>), AppError> {
+    run_ffmpeg_cancellable(args, &[]).await
+}
+
+/// Whether any of a job's cancel flags is set.
+pub fn is_cancelled(cancel: &[&AtomicBool]) -> bool {
+    cancel.iter().any(|flag| flag.load(Ordering::Relaxed))
+}
+
+/// Like [`run_ffmpeg`], but kills the process and returns [`AppError::Cancelled`]
+/// within [`CANCEL_POLL_INTERVAL`] of any flag in `cancel` being set, whether or not
+/// the process is producing output.
+pub async fn run_ffmpeg_cancellable(
+    args: &[&str],
+    cancel: &[&AtomicBool],
+) -> Result<(String, String, Option<i32>), AppError> {
+    if is_cancelled(cancel) {
+        return Err(AppError::Cancelled);
+    }
+
+    let (mut rx, child) = Command::new_sidecar("ffmpeg")
+        .map_err(|e| AppError::SidecarMissing {
+            message: e.to_string(),
+        })?
         .args(args)
         .spawn()
-        .map_err(|e| format!("Failed to run FFmpeg: {e}"))?;
+        .map_err(|e| AppError::SidecarMissing {
+            message: e.to_string(),
+        })?;
 
     let mut stdout = String::new();
     let mut stderr = String::new();
     let mut exit_code = None;
 
-    while let Some(event) = rx.recv().await {
-        match event {
-            CommandEvent::Stdout(line) => {
-                stdout.push_str(&line);
-                stdout.push('\n');
+    let mut poll = tokio::time::interval(CANCEL_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            event = rx.recv() => match event {
+                Some(CommandEvent::Stdout(line)) => {
+                    stdout.push_str(&line);
+                    stdout.push('\n');
+                }
+                Some(CommandEvent::Stderr(line)) => {
+                    stderr.push_str(&line);
+                    stderr.push('\n');
+                }
+                Some(CommandEvent::Terminated(payload)) => {
+                    exit_code = payload.code;
+                    break;
+                }
+                Some(_) => {}
+                None => break,
+            },
+            _ = poll.tick() => {
+                if is_cancelled(cancel) {
+                    let _ = child.kill();
+                    return Err(AppError::Cancelled);
+                }
             }
-            CommandEvent::Stderr(line) => {
-                stderr.push_str(&line);
-                stderr.push('\n');
+        }
+    }
+
+    Ok((stdout, stderr, exit_code))
+}
+
+/// Duration and stream layout of a media file as reported by `ffmpeg -i`.
+pub struct MediaProbe {
+    /// Duration in seconds
+    pub duration_secs: f64,
+    /// Streams in file order, formatted as `kind:codec` (e.g. `video:h264`)
+    pub streams: Vec<String>,
+    /// Width and height of the first video stream, or `(0, 0)` if there is none
+    pub resolution: (u32, u32),
+}
+
+/// Probes a media file by running `ffmpeg -i` and parsing its stderr.
+pub async fn probe_media(file_path: &str) -> Result<MediaProbe, AppError> {
+    let (_, stderr, exit_code) = run_ffmpeg(&["-i", file_path, "-hide_banner"]).await?;
+
+    // `ffmpeg -i` without an output always exits with an error, so only the log tells
+    let duration_secs = parse_duration(&stderr).ok_or_else(|| {
+        AppError::ffmpeg(
+            "Could not determine media duration",
+            Some(file_path),
+            exit_code,
+            &stderr,
+        )
+    })?;
+
+    Ok(MediaProbe {
+        duration_secs,
+        streams: parse_stream_layout(&stderr),
+        resolution: parse_resolution(&stderr),
+    })
+}
+
+/// Parses the first `Duration: HH:MM:SS.ms` entry from `FFmpeg` output.
+pub fn parse_duration(output: &str) -> Option<f64> {
+    for line in output.lines() {
+        if let Some(idx) = line.find("Duration:") {
+            let after = &line[idx + 9..];
+            let time_str = after.trim().split(',').next().unwrap_or("").trim();
+            let parts: Vec<&str> = time_str.split(':').collect();
+            if parts.len() == 3 {
+                let hours: f64 = parts[0].parse().unwrap_or(0.0);
+                let minutes: f64 = parts[1].parse().unwrap_or(0.0);
+                let seconds: f64 = parts[2].parse().unwrap_or(0.0);
+                return Some(hours.mul_add(3600.0, minutes.mul_add(60.0, seconds)));
             }
-            CommandEvent::Terminated(payload) => {
-                exit_code = payload.code;
+        }
+    }
+    None
+}
+
+/// Parses video resolution from `FFmpeg` output like `1920x1080`.
+pub fn parse_resolution(output: &str) -> (u32, u32) {
+    for line in output.lines() {
+        if line.contains("Video:") {
+            // Look for patterns like "1920x1080" or "1280x720"
+            for part in line.split([' ', ',']) {
+                let part = part.trim();
+                if let Some((w, h)) = part.split_once('x') {
+                    if let (Ok(width), Ok(height)) = (w.parse::<u32>(), h.parse::<u32>()) {
+                        if width >= 16 && height >= 16 && width <= 15360 && height <= 8640 {
+                            return (width, height);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    (0, 0)
+}
+
+/// Parses stream declarations like `Stream #0:1(und): Audio: aac (LC)` into `audio:aac`.
+pub fn parse_stream_layout(output: &str) -> Vec<String> {
+    const KINDS: [&str; 5] = ["Video", "Audio", "Subtitle", "Data", "Attachment"];
+
+    let mut streams = Vec::new();
+    for line in output.lines() {
+        let Some(idx) = line.find("Stream #") else {
+            continue;
+        };
+        let rest = &line[idx..];
+        for kind in KINDS {
+            let marker = format!(": {kind}: ");
+            if let Some(pos) = rest.find(&marker) {
+                let codec = rest[pos + marker.len()..]
+                    .split([' ', ','])
+                    .next()
+                    .unwrap_or("");
+                streams.push(format!("{}:{codec}", kind.to_lowercase()));
                 break;
             }
-            _ => {}
         }
     }
+    streams
+}
 
-    Ok((stdout, stderr, exit_code))
+/// Lists the presentation times of the first video stream's keyframes in seconds.
+///
+/// Only keyframes are decoded, so this is much faster than a full decode.
+pub async fn probe_keyframes(file_path: &str) -> Result<Vec<f64>, AppError> {
+    let (_, stderr, exit_code) = run_ffmpeg(&[
+        "-hide_banner",
+        "-nostats",
+        "-skip_frame",
+        "nokey",
+        "-i",
+        file_path,
+        "-map",
+        "0:v:0",
+        "-vf",
+        "showinfo",
+        "-f",
+        "null",
+        "-",
+    ])
+    .await?;
+
+    if exit_code != Some(0) {
+        return Err(AppError::ffmpeg(
+            "Keyframe probe failed",
+            Some(file_path),
+            exit_code,
+            &stderr,
+        ));
+    }
+    Ok(parse_keyframe_times(&stderr))
+}
+
+/// Parses the `pts_time:` of every frame logged by the `showinfo` filter.
+pub fn parse_keyframe_times(output: &str) -> Vec<f64> {
+    let mut times: Vec<f64> = output
+        .lines()
+        .filter(|line| line.contains("Parsed_showinfo"))
+        .filter_map(|line| {
+            let idx = line.find("pts_time:")?;
+            line[idx + 9..].split_whitespace().next()?.parse().ok()
+        })
+        .collect();
+    times.sort_by(f64::total_cmp);
+    times
+}
+
+/// Escapes `value` for use as a filter option inside a `-vf` filtergraph: first for the
+/// option parser (`\`, `'`, `:`), then for the graph parser (`\`, `'`, `[`, `]`, `,`, `;`).
+pub fn escape_filter_value(value: &str) -> String {
+    let escape = |text: &str, special: &[char]| {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if special.contains(&c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    };
+    let option = escape(value, &['\\', '\'', ':']);
+    escape(&option, &['\\', '\'', '[', ']', ',', ';'])
 }
 
 /// Formats a duration in seconds to `FFmpeg`'s HH:MM:SS.mmm format.
@@ -45,6 +244,19 @@ pub fn format_ffmpeg_time(seconds: f64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_escape_filter_value() {
+        // The example from FFmpeg's filtergraph escaping notes
+        assert_eq!(
+            escape_filter_value("this is a 'string': may contain one, or more, special characters"),
+            r"this is a \\\'string\\\'\\: may contain one\, or more\, special characters"
+        );
+        assert_eq!(
+            escape_filter_value("C:/Windows/Fonts/arial.ttf"),
+            r"C\\:/Windows/Fonts/arial.ttf"
+        );
+    }
+
     #[test]
     fn test_format_zero() {
         assert_eq!(format_ffmpeg_time(0.0), "00:00:00.000");
@@ -59,4 +271,35 @@ mod tests {
     fn test_format_hours() {
         assert_eq!(format_ffmpeg_time(3723.0), "01:02:03.000");
     }
+
+    #[test]
+    fn test_parse_duration() {
+        let output = "  Duration: 01:02:03.50, start: 0.000000, bitrate: 5000 kb/s";
+        assert_eq!(parse_duration(output), Some(3723.5));
+        assert_eq!(parse_duration("no duration here"), None);
+    }
+
+    #[test]
+    fn test_parse_stream_layout() {
+        let output = "\
+  Stream #0:0[0x1](und): Video: h264 (High) (avc1 / 0x31637661), yuv420p, 1920x1080
+  Stream #0:1[0x2](eng): Audio: aac (LC) (mp4a / 0x6134706D), 48000 Hz, stereo
+  Stream #0:2(eng): Subtitle: mov_text (tx3g / 0x67337874)
+Stream mapping:
+  Stream #0:0 -> #0:0 (copy)";
+        assert_eq!(
+            parse_stream_layout(output),
+            vec!["video:h264", "audio:aac", "subtitle:mov_text"]
+        );
+    }
+
+    #[test]
+    fn test_parse_keyframe_times() {
+        let output = "\
+[Parsed_showinfo_0 @ 0x600] config in time_base: 1/15360, frame_rate: 30/1
+[Parsed_showinfo_0 @ 0x600] n:   0 pts:      0 pts_time:0       duration:512 fmt:yuv420p
+[Parsed_showinfo_0 @ 0x600] n:   1 pts: 38400 pts_time:2.5     duration:512 fmt:yuv420p
+[Parsed_showinfo_0 @ 0x600] n:   2 pts: 76800 pts_time:5.0     duration:512 fmt:yuv420p";
+        assert_eq!(parse_keyframe_times(output), vec![0.0, 2.5, 5.0]);
+    }
 }
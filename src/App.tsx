@@ -1,5 +1,10 @@
 import { invoke } from '@tauri-apps/api/tauri';
 import { useCallback, useEffect } from 'react';
+import { AnimationExportPanel } from './components/AnimationExportPanel';
+import { AudioExportPanel } from './components/AudioExportPanel';
+import { ByteSplitConfig } from './components/ByteSplitConfig';
+import { CaptureSettingsPanel } from './components/CaptureSettingsPanel';
+import { ContactSheetPanel } from './components/ContactSheetPanel';
 import { FileSelector } from './components/FileSelector';
 import { PartitionConfig } from './components/PartitionConfig';
 import { PhotoViewer } from './components/PhotoViewer';
@@ -8,9 +13,10 @@ import { ShortcutsBar } from './components/ShortcutsBar';
 import { Timeline } from './components/Timeline';
 import { ToastContainer } from './components/Toast';
 import { VideoPlayer } from './components/VideoPlayer';
+import { useFrameCapture } from './hooks/useFrameCapture';
+import { useProjectState } from './hooks/useProjectState';
 import { useDirectoryStore } from './stores/directoryStore';
 import { usePartitionStore } from './stores/partitionStore';
-import { useToastStore } from './stores/toastStore';
 import { useVideoStore } from './stores/videoStore';
 
 export const App = () => {
@@ -19,9 +25,7 @@ export const App = () => {
   const setVideoFile = useVideoStore((state) => state.setVideoFile);
   const resetPartition = usePartitionStore((state) => state.reset);
 
-  const currentTime = useVideoStore((state) => state.currentTime);
   const setTogglePlay = useVideoStore((state) => state.setTogglePlay);
-  const addToast = useToastStore((state) => state.addToast);
 
   const directoryPath = useDirectoryStore((state) => state.directoryPath);
   const files = useDirectoryStore((state) => state.files);
@@ -30,6 +34,9 @@ export const App = () => {
   const removeFile = useDirectoryStore((state) => state.removeFile);
   const resetDirectory = useDirectoryStore((state) => state.reset);
 
+  useProjectState();
+  const captureFrame = useFrameCapture();
+
   const currentFile = directoryPath ? files[currentIndex] : null;
   const isDirectoryMode = directoryPath !== null;
   const isVideoFile = currentFile?.fileType === 'video';
@@ -73,20 +80,6 @@ export const App = () => {
     }
   }, [isDirectoryMode, currentFile, currentIndex, removeFile, resetVideo, resetPartition]);
 
-  const handleScreenshot = useCallback(async () => {
-    if (!videoFile) return;
-    try {
-      const outputPath: string = await invoke('capture_frame', {
-        videoPath: videoFile,
-        timestampSecs: currentTime,
-      });
-      const fileName = outputPath.split('\\').pop() ?? outputPath.split('/').pop() ?? outputPath;
-      addToast(`Saved: ${fileName}`, 'success');
-    } catch (err) {
-      addToast(`Screenshot failed: ${err}`, 'error');
-    }
-  }, [videoFile, currentTime, addToast]);
-
   const navigateFiles = useCallback(
     (direction: -1 | 1) => {
       if (!isDirectoryMode || files.length === 0) return;
@@ -111,13 +104,13 @@ export const App = () => {
         e.preventDefault();
         setTogglePlay();
       } else if (e.key === 's' || e.key === 'S') {
-        handleScreenshot();
+        captureFrame();
       }
     };
 
     window.addEventListener('keydown', handleKeyDown);
     return () => window.removeEventListener('keydown', handleKeyDown);
-  }, [navigateFiles, handleDeleteFile, setTogglePlay, handleScreenshot]);
+  }, [navigateFiles, handleDeleteFile, setTogglePlay, captureFrame]);
 
   const showFileSelector = !videoFile && !isDirectoryMode;
   const showDirectoryVideo = isDirectoryMode && isVideoFile && videoFile;
@@ -149,7 +142,15 @@ export const App = () => {
       <main className="flex flex-1 overflow-hidden">
         {showFileSelector && <FileSelector />}
 
-        {showDirectoryImage && <PhotoViewer />}
+        {showDirectoryImage && (
+          <>
+            <PhotoViewer />
+            <aside className="w-80 overflow-y-auto border-l border-gray-700 p-4">
+              <ByteSplitConfig />
+              <ContactSheetPanel />
+            </aside>
+          </>
+        )}
 
         {(showDirectoryVideo || showSingleVideo) && (
           <>
@@ -159,6 +160,10 @@ export const App = () => {
             </div>
             <aside className="w-80 overflow-y-auto border-l border-gray-700 p-4">
               <PartitionConfig />
+              <CaptureSettingsPanel />
+              <AnimationExportPanel />
+              <AudioExportPanel />
+              <ContactSheetPanel />
             </aside>
           </>
         )}
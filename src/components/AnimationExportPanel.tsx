@@ -0,0 +1,160 @@
+import { save } from '@tauri-apps/api/dialog';
+import { invoke } from '@tauri-apps/api/tauri';
+import { useState } from 'react';
+import { useToastStore } from '../stores/toastStore';
+import { useVideoStore } from '../stores/videoStore';
+import type { AnimationExport, AnimationFormat } from '../types/animation';
+import { describeError } from '../utils/errors';
+import { formatDuration, formatFileSize } from '../utils/formatters';
+
+const toSeconds = (value: string) => Math.max(0, Number(value) || 0);
+
+export const AnimationExportPanel = () => {
+  const videoFile = useVideoStore((state) => state.videoFile);
+  const currentTime = useVideoStore((state) => state.currentTime);
+  const addToast = useToastStore((state) => state.addToast);
+  const [startSecs, setStartSecs] = useState(0);
+  const [endSecs, setEndSecs] = useState(3);
+  const [format, setFormat] = useState<AnimationFormat>('gif');
+  const [fps, setFps] = useState(12);
+  const [width, setWidth] = useState(480);
+  const [maxSizeMb, setMaxSizeMb] = useState('');
+  const [busy, setBusy] = useState(false);
+
+  const handleExport = async () => {
+    if (!videoFile) return;
+    const outputPath = await save({
+      title: 'Export animation',
+      filters: [{ name: format.toUpperCase(), extensions: [format] }],
+    });
+    if (!outputPath) return;
+
+    const maxSize = Number(maxSizeMb);
+    setBusy(true);
+    try {
+      const result = await invoke<AnimationExport>('export_animation', {
+        request: {
+          videoPath: videoFile,
+          startSecs,
+          endSecs,
+          outputPath,
+          format,
+          fps,
+          width,
+          maxSizeBytes: maxSize > 0 ? Math.round(maxSize * 1024 * 1024) : null,
+        },
+      });
+      const summary = `${formatFileSize(result.sizeBytes)}, ${result.width}px at ${result.fps} fps`;
+      if (result.withinTarget) {
+        addToast(`Saved animation (${summary})`, 'success');
+      } else {
+        addToast(`Saved animation (${summary}), but it is still above the size target`, 'error');
+      }
+    } catch (err) {
+      addToast(`Animation export failed: ${describeError(err)}`, 'error');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="mt-6 space-y-3 border-t border-gray-700 pt-4">
+      <h2 className="text-lg font-semibold">Animation</h2>
+
+      <div className="grid grid-cols-[auto_1fr_auto] items-center gap-2 text-sm text-gray-400">
+        Start
+        <input
+          type="number"
+          min={0}
+          step={0.1}
+          aria-label="Start in seconds"
+          value={startSecs}
+          onChange={(e) => setStartSecs(toSeconds(e.target.value))}
+          className="rounded bg-gray-800 px-2 py-1 text-white"
+        />
+        <button
+          type="button"
+          onClick={() => setStartSecs(currentTime)}
+          className="rounded bg-gray-700 px-2 py-0.5 text-xs text-gray-300 transition-colors hover:bg-gray-600"
+        >
+          Now
+        </button>
+        End
+        <input
+          type="number"
+          min={0}
+          step={0.1}
+          aria-label="End in seconds"
+          value={endSecs}
+          onChange={(e) => setEndSecs(toSeconds(e.target.value))}
+          className="rounded bg-gray-800 px-2 py-1 text-white"
+        />
+        <button
+          type="button"
+          onClick={() => setEndSecs(currentTime)}
+          className="rounded bg-gray-700 px-2 py-0.5 text-xs text-gray-300 transition-colors hover:bg-gray-600"
+        >
+          Now
+        </button>
+      </div>
+      <p className="text-xs text-gray-500">
+        {formatDuration(startSecs)} – {formatDuration(endSecs)} (
+        {Math.max(0, endSecs - startSecs).toFixed(1)} s, at most 30 s)
+      </p>
+
+      <div className="flex gap-2">
+        <select
+          aria-label="Animation format"
+          value={format}
+          onChange={(e) => setFormat(e.target.value as AnimationFormat)}
+          className="flex-1 rounded bg-gray-800 px-2 py-1 text-white"
+        >
+          <option value="gif">GIF</option>
+          <option value="webp">WebP</option>
+        </select>
+        <input
+          type="number"
+          min={1}
+          max={50}
+          aria-label="Frames per second"
+          value={fps}
+          onChange={(e) => setFps(Math.max(1, Math.round(Number(e.target.value))))}
+          className="w-16 rounded bg-gray-800 px-2 py-1 text-white"
+        />
+        <input
+          type="number"
+          min={120}
+          max={1920}
+          step={10}
+          aria-label="Width in pixels"
+          value={width}
+          onChange={(e) => setWidth(Math.max(1, Math.round(Number(e.target.value))))}
+          className="w-20 rounded bg-gray-800 px-2 py-1 text-white"
+        />
+      </div>
+
+      <label className="flex items-center gap-2 text-sm text-gray-400">
+        Max size
+        <input
+          type="number"
+          min={0}
+          step={0.5}
+          placeholder="none"
+          value={maxSizeMb}
+          onChange={(e) => setMaxSizeMb(e.target.value)}
+          className="w-20 rounded bg-gray-800 px-2 py-1 text-white"
+        />
+        MB
+      </label>
+
+      <button
+        type="button"
+        onClick={handleExport}
+        disabled={busy || !videoFile || endSecs <= startSecs}
+        className="w-full rounded bg-gray-600 px-4 py-2 font-medium text-white transition-colors hover:bg-gray-500 disabled:opacity-50"
+      >
+        {busy ? 'Exporting…' : 'Export animation'}
+      </button>
+    </div>
+  );
+};
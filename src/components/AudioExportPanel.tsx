@@ -0,0 +1,162 @@
+import { open } from '@tauri-apps/api/dialog';
+import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/tauri';
+import { useState } from 'react';
+import { usePartitionStore } from '../stores/partitionStore';
+import { useToastStore } from '../stores/toastStore';
+import { useVideoStore } from '../stores/videoStore';
+import type { AudioExportRequest, AudioFormat, AudioMode } from '../types/audio';
+import type { SplitProgress } from '../types/partition';
+import { describeError, isAppError } from '../utils/errors';
+import { gbToBytes } from '../utils/formatters';
+
+const LOSSY_FORMATS: AudioFormat[] = ['m4a', 'mp3'];
+
+export const AudioExportPanel = () => {
+  const videoFile = useVideoStore((state) => state.videoFile);
+  const exclusions = usePartitionStore((state) => state.exclusions);
+  const strategy = usePartitionStore((state) => state.strategy);
+  const targetSizeGb = usePartitionStore((state) => state.targetSizeGb);
+  const targetDurationMins = usePartitionStore((state) => state.targetDurationMins);
+  const namingTemplate = usePartitionStore((state) => state.namingTemplate);
+  const addToast = useToastStore((state) => state.addToast);
+  const [format, setFormat] = useState<AudioFormat>('m4a');
+  const [mode, setMode] = useState<AudioMode>('encode');
+  const [bitrateKbps, setBitrateKbps] = useState(192);
+  const [skipExclusions, setSkipExclusions] = useState(true);
+  const [splitLikeVideo, setSplitLikeVideo] = useState(false);
+  const [progress, setProgress] = useState<SplitProgress | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const handleExport = async () => {
+    if (!videoFile) return;
+    const outputDir = await open({ title: 'Select folder for audio', directory: true });
+    if (typeof outputDir !== 'string') return;
+
+    const request: AudioExportRequest = {
+      inputPath: videoFile,
+      outputDir,
+      format,
+      mode,
+      bitrateKbps: mode === 'encode' && LOSSY_FORMATS.includes(format) ? bitrateKbps : null,
+      track: null,
+      exclusions: skipExclusions ? exclusions : [],
+      targetSizeBytes: splitLikeVideo ? gbToBytes(targetSizeGb) : null,
+      targetDurationSecs:
+        splitLikeVideo && strategy === 'duration' ? targetDurationMins * 60 : null,
+      namingTemplate: splitLikeVideo ? namingTemplate : null,
+      scratchDir: null,
+    };
+
+    setBusy(true);
+    const unlisten = await listen<SplitProgress>('audio-export-progress', (event) =>
+      setProgress(event.payload)
+    );
+    try {
+      addToast(await invoke<string>('execute_audio_export', { request }), 'success');
+    } catch (err) {
+      if (!(isAppError(err) && err.code === 'cancelled')) {
+        addToast(`Audio export failed: ${describeError(err)}`, 'error');
+      }
+    } finally {
+      unlisten();
+      setProgress(null);
+      setBusy(false);
+    }
+  };
+
+  const handleCancel = async () => {
+    try {
+      await invoke('cancel_audio_export');
+    } catch (err) {
+      console.error('Failed to cancel audio export:', err);
+    }
+  };
+
+  return (
+    <div className="mt-6 space-y-3 border-t border-gray-700 pt-4">
+      <h2 className="text-lg font-semibold">Audio</h2>
+
+      <div className="flex gap-2">
+        <select
+          aria-label="Audio format"
+          value={format}
+          onChange={(e) => setFormat(e.target.value as AudioFormat)}
+          className="flex-1 rounded bg-gray-800 px-2 py-1 text-white"
+        >
+          <option value="m4a">M4A (AAC)</option>
+          <option value="mp3">MP3</option>
+          <option value="flac">FLAC</option>
+          <option value="wav">WAV</option>
+        </select>
+        <select
+          aria-label="Copy or encode"
+          value={mode}
+          onChange={(e) => setMode(e.target.value as AudioMode)}
+          className="flex-1 rounded bg-gray-800 px-2 py-1 text-white"
+        >
+          <option value="encode">Encode</option>
+          <option value="copy">Copy</option>
+        </select>
+      </div>
+
+      {mode === 'encode' && LOSSY_FORMATS.includes(format) && (
+        <label className="flex items-center gap-2 text-sm text-gray-400">
+          Bitrate
+          <select
+            value={bitrateKbps}
+            onChange={(e) => setBitrateKbps(Number(e.target.value))}
+            className="rounded bg-gray-800 px-2 py-1 text-white"
+          >
+            {[96, 128, 192, 256, 320].map((kbps) => (
+              <option key={kbps} value={kbps}>
+                {kbps} kbit/s
+              </option>
+            ))}
+          </select>
+        </label>
+      )}
+
+      <label className="flex items-center gap-2 text-sm text-gray-400">
+        <input
+          type="checkbox"
+          checked={skipExclusions}
+          onChange={(e) => setSkipExclusions(e.target.checked)}
+        />
+        Leave out excluded intervals
+      </label>
+      <label className="flex items-center gap-2 text-sm text-gray-400">
+        <input
+          type="checkbox"
+          checked={splitLikeVideo}
+          onChange={(e) => setSplitLikeVideo(e.target.checked)}
+        />
+        Split into the same parts as the video
+      </label>
+
+      {progress && (
+        <p className="text-xs text-gray-400">
+          {progress.completedPartitions} / {progress.totalPartitions} parts
+        </p>
+      )}
+
+      <button
+        type="button"
+        onClick={handleExport}
+        disabled={busy || !videoFile}
+        className="w-full rounded bg-gray-600 px-4 py-2 font-medium text-white transition-colors hover:bg-gray-500 disabled:opacity-50"
+      >
+        {busy ? 'Exporting…' : 'Export audio'}
+      </button>
+      {busy && (
+        <button
+          type="button"
+          onClick={handleCancel}
+          className="w-full rounded bg-red-700 px-4 py-2 font-medium text-white transition-colors hover:bg-red-600"
+        >
+          Cancel
+        </button>
+      )}
+    </div>
+  );
+};
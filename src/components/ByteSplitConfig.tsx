@@ -0,0 +1,118 @@
+import { open } from '@tauri-apps/api/dialog';
+import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/tauri';
+import { useState } from 'react';
+import { useDirectoryStore } from '../stores/directoryStore';
+import { usePartitionStore } from '../stores/partitionStore';
+import type { ByteSplitRequest, SplitProgress } from '../types/partition';
+import { gbToBytes } from '../utils/formatters';
+
+export const ByteSplitConfig = () => {
+  const files = useDirectoryStore((state) => state.files);
+  const currentIndex = useDirectoryStore((state) => state.currentIndex);
+  const targetSizeGb = usePartitionStore((state) => state.targetSizeGb);
+  const setTargetSizeGb = usePartitionStore((state) => state.setTargetSizeGb);
+  const status = usePartitionStore((state) => state.status);
+  const setStatus = usePartitionStore((state) => state.setStatus);
+  const setProgress = usePartitionStore((state) => state.setProgress);
+  const setError = usePartitionStore((state) => state.setError);
+
+  const [writeManifest, setWriteManifest] = useState(true);
+
+  const currentFile = files[currentIndex];
+
+  const handleCancel = async () => {
+    try {
+      await invoke('cancel_split');
+    } catch (err) {
+      console.error('Failed to cancel split:', err);
+    }
+  };
+
+  const handleSplit = async () => {
+    if (!currentFile) return;
+
+    const selectedDir = await open({
+      title: 'Select output folder',
+      directory: true,
+    });
+
+    if (typeof selectedDir !== 'string') return;
+
+    await runByteSplit({
+      inputPath: currentFile.path,
+      outputDir: selectedDir,
+      targetSizeBytes: gbToBytes(targetSizeGb),
+      checksums: writeManifest ? ['sha256', 'blake3'] : [],
+    });
+  };
+
+  const runByteSplit = async (request: ByteSplitRequest) => {
+    setStatus('processing');
+    setProgress(0);
+
+    const unlisten = await listen<SplitProgress>('split-progress', (event) => {
+      setProgress(event.payload.percent);
+    });
+
+    try {
+      await invoke('execute_byte_split', { request });
+      setStatus('complete');
+      setProgress(100);
+    } catch (err) {
+      setError(err, () => void runByteSplit(request));
+    } finally {
+      unlisten();
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-lg font-semibold">Chunk File</h2>
+
+      <div>
+        <label htmlFor="chunkSize" className="block text-sm font-medium">
+          Target chunk size (GB)
+        </label>
+        <input
+          id="chunkSize"
+          type="number"
+          min={0.1}
+          step={0.1}
+          value={targetSizeGb}
+          onChange={(e) => setTargetSizeGb(Number(e.target.value))}
+          className="mt-1 w-full rounded bg-gray-800 px-3 py-2 text-white"
+        />
+      </div>
+
+      <label className="flex items-center gap-2 text-sm text-gray-400">
+        <input
+          type="checkbox"
+          checked={writeManifest}
+          onChange={(e) => setWriteManifest(e.target.checked)}
+          className="rounded"
+        />
+        Write checksum manifest
+      </label>
+
+      {status === 'processing' && (
+        <button
+          type="button"
+          onClick={handleCancel}
+          className="w-full rounded bg-red-700 px-4 py-2 font-medium text-white transition-colors hover:bg-red-600"
+        >
+          Cancel
+        </button>
+      )}
+
+      <button
+        type="button"
+        disabled={!currentFile || status === 'processing'}
+        onClick={handleSplit}
+        className="w-full rounded bg-blue-600 px-4 py-2 font-medium text-white transition-colors hover:bg-blue-700 disabled:opacity-50"
+      >
+        {status === 'processing' ? 'Processing...' : 'Split into Chunks'}
+      </button>
+    </div>
+  );
+};
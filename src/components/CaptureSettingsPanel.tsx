@@ -0,0 +1,205 @@
+import { open } from '@tauri-apps/api/dialog';
+import { invoke } from '@tauri-apps/api/tauri';
+import { useEffect, useState } from 'react';
+import { useFrameCapture } from '../hooks/useFrameCapture';
+import type { Burst, CaptureFormat, CaptureSettings } from '../types/capture';
+import { describeError } from '../utils/errors';
+
+type BurstMode = Burst['mode'];
+
+const parseSize = (value: string) => (value === '' ? null : Math.max(1, Math.round(Number(value))));
+
+export const CaptureSettingsPanel = () => {
+  const captureFrame = useFrameCapture();
+  const [settings, setSettings] = useState<CaptureSettings | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [burstMode, setBurstMode] = useState<BurstMode>('interval');
+  const [burstCount, setBurstCount] = useState(10);
+  const [burstInterval, setBurstInterval] = useState(1);
+  const [burstDuration, setBurstDuration] = useState(1);
+
+  useEffect(() => {
+    invoke<CaptureSettings>('get_capture_settings')
+      .then(setSettings)
+      .catch((err) => setError(describeError(err)));
+  }, []);
+
+  if (!settings) {
+    return error ? <p className="mt-6 text-sm text-red-400">{error}</p> : null;
+  }
+
+  // Saves every change right away; the S shortcut uses the saved settings
+  const update = async (changes: Partial<CaptureSettings>) => {
+    const next = { ...settings, ...changes };
+    if (next.format === 'jpeg') next.lossless = false;
+    try {
+      await invoke('save_capture_settings', { settings: next });
+      setSettings(next);
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  const handleSelectFolder = async () => {
+    const selected = await open({
+      title: 'Select screenshots folder',
+      directory: true,
+    });
+    if (typeof selected === 'string') update({ outputDir: selected });
+  };
+
+  const handleBurst = () => {
+    const burst: Burst =
+      burstMode === 'interval'
+        ? { mode: 'interval', count: burstCount, intervalSecs: burstInterval }
+        : { mode: 'range', durationSecs: burstDuration };
+    captureFrame(burst);
+  };
+
+  return (
+    <div className="mt-6 space-y-3 border-t border-gray-700 pt-4">
+      <h2 className="text-lg font-semibold">Screenshots</h2>
+
+      <div className="flex gap-2">
+        <select
+          aria-label="Image format"
+          value={settings.format}
+          onChange={(e) => update({ format: e.target.value as CaptureFormat })}
+          className="flex-1 rounded bg-gray-800 px-3 py-2 text-white"
+        >
+          <option value="jpeg">JPEG</option>
+          <option value="png">PNG</option>
+          <option value="webp">WebP</option>
+        </select>
+        <label className="flex items-center gap-2 text-sm text-gray-400">
+          <input
+            type="checkbox"
+            checked={settings.lossless || settings.format === 'png'}
+            disabled={settings.format !== 'webp'}
+            onChange={(e) => update({ lossless: e.target.checked })}
+            className="rounded"
+          />
+          Lossless
+        </label>
+      </div>
+
+      <div className="flex items-center gap-2 text-sm text-gray-400">
+        <input
+          type="number"
+          min={1}
+          placeholder="Width"
+          aria-label="Width"
+          value={settings.width ?? ''}
+          onChange={(e) => update({ width: parseSize(e.target.value) })}
+          className="w-full rounded bg-gray-800 px-3 py-2 text-white"
+        />
+        ×
+        <input
+          type="number"
+          min={1}
+          placeholder="Height"
+          aria-label="Height"
+          value={settings.height ?? ''}
+          onChange={(e) => update({ height: parseSize(e.target.value) })}
+          className="w-full rounded bg-gray-800 px-3 py-2 text-white"
+        />
+      </div>
+      <p className="text-xs text-gray-500">Leave a side empty to keep the aspect ratio, both for the original size.</p>
+
+      <div className="text-sm text-gray-400">
+        <p className="truncate" title={settings.outputDir ?? undefined}>
+          Folder: {settings.outputDir ?? 'next to the video'}
+        </p>
+        <div className="mt-1 flex gap-2">
+          <button
+            type="button"
+            onClick={handleSelectFolder}
+            className="rounded bg-gray-700 px-2 py-0.5 text-xs text-gray-300 transition-colors hover:bg-gray-600"
+          >
+            Change
+          </button>
+          {settings.outputDir && (
+            <button
+              type="button"
+              onClick={() => update({ outputDir: null })}
+              className="rounded bg-gray-700 px-2 py-0.5 text-xs text-gray-300 transition-colors hover:bg-gray-600"
+            >
+              Reset
+            </button>
+          )}
+        </div>
+      </div>
+
+      <label className="flex items-center gap-2 text-sm text-gray-400">
+        <input
+          type="checkbox"
+          checked={settings.embedMetadata}
+          onChange={(e) => update({ embedMetadata: e.target.checked })}
+          className="rounded"
+        />
+        Embed source file and timecode
+      </label>
+
+      <div className="space-y-2 rounded bg-gray-800 p-3 text-sm text-gray-300">
+        <select
+          aria-label="Burst mode"
+          value={burstMode}
+          onChange={(e) => setBurstMode(e.target.value as BurstMode)}
+          className="w-full rounded bg-gray-700 px-2 py-1 text-white"
+        >
+          <option value="interval">Frames at an interval</option>
+          <option value="range">Every frame in a range</option>
+        </select>
+        {burstMode === 'interval' ? (
+          <div className="flex items-center gap-2">
+            <input
+              type="number"
+              min={1}
+              max={300}
+              aria-label="Frame count"
+              value={burstCount}
+              onChange={(e) => setBurstCount(Math.max(1, Math.round(Number(e.target.value))))}
+              className="w-16 rounded bg-gray-700 px-2 py-1 text-white"
+            />
+            frames every
+            <input
+              type="number"
+              min={0.04}
+              step={0.1}
+              aria-label="Interval in seconds"
+              value={burstInterval}
+              onChange={(e) => setBurstInterval(Number(e.target.value))}
+              className="w-16 rounded bg-gray-700 px-2 py-1 text-white"
+            />
+            s
+          </div>
+        ) : (
+          <div className="flex items-center gap-2">
+            Next
+            <input
+              type="number"
+              min={0.1}
+              max={60}
+              step={0.1}
+              aria-label="Range in seconds"
+              value={burstDuration}
+              onChange={(e) => setBurstDuration(Number(e.target.value))}
+              className="w-16 rounded bg-gray-700 px-2 py-1 text-white"
+            />
+            s (up to 300 frames)
+          </div>
+        )}
+        <button
+          type="button"
+          onClick={handleBurst}
+          className="w-full rounded bg-gray-600 px-4 py-2 font-medium text-white transition-colors hover:bg-gray-500"
+        >
+          Capture burst
+        </button>
+      </div>
+
+      {error && <p className="text-sm text-red-400">{error}</p>}
+    </div>
+  );
+};
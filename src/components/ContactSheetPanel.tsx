@@ -0,0 +1,177 @@
+import { open } from '@tauri-apps/api/dialog';
+import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/tauri';
+import { useState } from 'react';
+import { useDirectoryStore } from '../stores/directoryStore';
+import { useToastStore } from '../stores/toastStore';
+import { useVideoStore } from '../stores/videoStore';
+import type { CaptureFormat } from '../types/capture';
+import type {
+  ContactSheet,
+  ContactSheetBatch,
+  ContactSheetOptions,
+  ContactSheetProgress,
+} from '../types/contactSheet';
+import { describeError } from '../utils/errors';
+
+const DEFAULT_OPTIONS: ContactSheetOptions = {
+  columns: 4,
+  rows: 6,
+  width: 1600,
+  format: 'jpeg',
+  outputDir: null,
+};
+
+const toCount = (value: string) => Math.max(1, Math.round(Number(value)));
+
+export const ContactSheetPanel = () => {
+  const videoFile = useVideoStore((state) => state.videoFile);
+  const directoryPath = useDirectoryStore((state) => state.directoryPath);
+  const addToast = useToastStore((state) => state.addToast);
+  const [options, setOptions] = useState<ContactSheetOptions>(DEFAULT_OPTIONS);
+  const [busy, setBusy] = useState(false);
+  const [progress, setProgress] = useState<ContactSheetProgress | null>(null);
+
+  const update = (changes: Partial<ContactSheetOptions>) => setOptions({ ...options, ...changes });
+
+  const handleSelectFolder = async () => {
+    const selected = await open({
+      title: 'Select folder for contact sheets',
+      directory: true,
+    });
+    if (typeof selected === 'string') update({ outputDir: selected });
+  };
+
+  const handleGenerate = async () => {
+    if (!videoFile) return;
+    setBusy(true);
+    try {
+      const sheet = await invoke<ContactSheet>('generate_contact_sheet', {
+        videoPath: videoFile,
+        options,
+      });
+      addToast(`Saved: ${sheet.path.split(/[\\/]/).pop()}`, 'success');
+    } catch (err) {
+      addToast(`Contact sheet failed: ${describeError(err)}`, 'error');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleGenerateAll = async () => {
+    if (!directoryPath) return;
+    setBusy(true);
+    const unlisten = await listen<ContactSheetProgress>('contact-sheet-progress', (event) =>
+      setProgress(event.payload)
+    );
+    try {
+      const batch = await invoke<ContactSheetBatch>('generate_contact_sheets', {
+        dirPath: directoryPath,
+        options,
+      });
+      if (batch.failures.length === 0) {
+        addToast(`Saved ${batch.sheets.length} contact sheets`, 'success');
+      } else {
+        const failed = batch.failures.map((f) => f.videoPath.split(/[\\/]/).pop()).join(', ');
+        addToast(`Saved ${batch.sheets.length} contact sheets; failed: ${failed}`, 'error');
+      }
+    } catch (err) {
+      addToast(`Contact sheets failed: ${describeError(err)}`, 'error');
+    } finally {
+      unlisten();
+      setProgress(null);
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="mt-6 space-y-3 border-t border-gray-700 pt-4">
+      <h2 className="text-lg font-semibold">Contact Sheet</h2>
+
+      <div className="flex items-center gap-2 text-sm text-gray-400">
+        <input
+          type="number"
+          min={1}
+          max={10}
+          aria-label="Columns"
+          value={options.columns}
+          onChange={(e) => update({ columns: toCount(e.target.value) })}
+          className="w-16 rounded bg-gray-800 px-2 py-1 text-white"
+        />
+        ×
+        <input
+          type="number"
+          min={1}
+          max={20}
+          aria-label="Rows"
+          value={options.rows}
+          onChange={(e) => update({ rows: toCount(e.target.value) })}
+          className="w-16 rounded bg-gray-800 px-2 py-1 text-white"
+        />
+        frames
+      </div>
+
+      <div className="flex gap-2">
+        <input
+          type="number"
+          min={320}
+          step={100}
+          aria-label="Sheet width in pixels"
+          value={options.width}
+          onChange={(e) => update({ width: toCount(e.target.value) })}
+          className="w-24 rounded bg-gray-800 px-2 py-1 text-white"
+        />
+        <select
+          aria-label="Contact sheet format"
+          value={options.format}
+          onChange={(e) => update({ format: e.target.value as CaptureFormat })}
+          className="flex-1 rounded bg-gray-800 px-2 py-1 text-white"
+        >
+          <option value="jpeg">JPEG</option>
+          <option value="png">PNG</option>
+          <option value="webp">WebP</option>
+        </select>
+      </div>
+
+      <div className="text-sm text-gray-400">
+        <p className="truncate" title={options.outputDir ?? undefined}>
+          Folder: {options.outputDir ?? 'next to each video'}
+        </p>
+        <button
+          type="button"
+          onClick={handleSelectFolder}
+          className="mt-1 rounded bg-gray-700 px-2 py-0.5 text-xs text-gray-300 transition-colors hover:bg-gray-600"
+        >
+          Change
+        </button>
+      </div>
+
+      {progress && (
+        <p className="text-xs text-gray-400">
+          {progress.index + 1} / {progress.total}: {progress.videoPath.split(/[\\/]/).pop()}
+        </p>
+      )}
+
+      {videoFile && (
+        <button
+          type="button"
+          onClick={handleGenerate}
+          disabled={busy}
+          className="w-full rounded bg-gray-600 px-4 py-2 font-medium text-white transition-colors hover:bg-gray-500 disabled:opacity-50"
+        >
+          Generate contact sheet
+        </button>
+      )}
+      {directoryPath && (
+        <button
+          type="button"
+          onClick={handleGenerateAll}
+          disabled={busy}
+          className="w-full rounded bg-gray-700 px-4 py-2 font-medium text-gray-300 transition-colors hover:bg-gray-600 disabled:opacity-50"
+        >
+          Generate for every video in the folder
+        </button>
+      )}
+    </div>
+  );
+};
@@ -1,12 +1,176 @@
+import { open, save } from '@tauri-apps/api/dialog';
+import { invoke } from '@tauri-apps/api/tauri';
+import { useEffect, useState } from 'react';
 import { usePartitionStore } from '../stores/partitionStore';
+import { useVideoStore } from '../stores/videoStore';
+import type {
+  ExclusionSuggestion,
+  NormalizedExclusions,
+  TimeInterval,
+} from '../types/partition';
+import { describeError } from '../utils/errors';
+
+const CUT_LIST_FILTERS = [
+  { name: 'Cut lists', extensions: ['edl', 'csv', 'ffmeta', 'ffmetadata', 'cue'] },
+];
 
 export const ExclusionEditor = () => {
   const exclusions = usePartitionStore((state) => state.exclusions);
   const removeExclusion = usePartitionStore((state) => state.removeExclusion);
+  const setExclusions = usePartitionStore((state) => state.setExclusions);
+  const setError = usePartitionStore((state) => state.setError);
+  const suggestions = usePartitionStore((state) => state.suggestions);
+  const setSuggestions = usePartitionStore((state) => state.setSuggestions);
+  const acceptSuggestion = usePartitionStore((state) => state.acceptSuggestion);
+  const rejectSuggestion = usePartitionStore((state) => state.rejectSuggestion);
+  const videoFile = useVideoStore((state) => state.videoFile);
+  const frameRate = useVideoStore((state) => state.metadata?.frameRate ?? 0);
+  const durationSecs = useVideoStore((state) => state.metadata?.durationSecs ?? 0);
+
+  const [normalized, setNormalized] = useState<NormalizedExclusions | null>(null);
+  const [invalidReason, setInvalidReason] = useState<string | null>(null);
+  const [analyzing, setAnalyzing] = useState(false);
+
+  // Ask the backend what it will adjust so problems show up before splitting
+  useEffect(() => {
+    let cancelled = false;
+
+    invoke<NormalizedExclusions>('normalize_exclusions', { exclusions, durationSecs })
+      .then((result) => {
+        if (cancelled) return;
+        setNormalized(result);
+        setInvalidReason(null);
+      })
+      .catch((err) => {
+        if (cancelled) return;
+        setNormalized(null);
+        setInvalidReason(describeError(err));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [exclusions, durationSecs]);
+
+  const handleImport = async () => {
+    const selected = await open({ title: 'Import cut list', filters: CUT_LIST_FILTERS });
+    if (typeof selected !== 'string') return;
+
+    try {
+      const intervals = await invoke<TimeInterval[]>('import_cut_list', {
+        filePath: selected,
+        frameRate,
+      });
+      setExclusions(intervals);
+    } catch (err) {
+      setError(err);
+    }
+  };
+
+  const handleAnalyze = async () => {
+    if (!videoFile) return;
+
+    setAnalyzing(true);
+    try {
+      const found = await invoke<ExclusionSuggestion[]>('suggest_exclusions', {
+        filePath: videoFile,
+      });
+      setSuggestions(found);
+    } catch (err) {
+      setError(err);
+    } finally {
+      setAnalyzing(false);
+    }
+  };
+
+  const handleExport = async () => {
+    const selected = await save({ title: 'Export cut list', filters: CUT_LIST_FILTERS });
+    if (!selected) return;
+
+    try {
+      await invoke('export_cut_list', { filePath: selected, intervals: exclusions, frameRate });
+    } catch (err) {
+      setError(err);
+    }
+  };
 
   return (
     <div className="space-y-2">
-      <h3 className="text-sm font-medium">Excluded Intervals</h3>
+      <div className="flex items-center justify-between">
+        <h3 className="text-sm font-medium">Excluded Intervals</h3>
+        <div className="flex gap-2 text-xs">
+          <button
+            type="button"
+            disabled={!videoFile || analyzing}
+            onClick={handleAnalyze}
+            className="text-gray-400 hover:text-gray-300 disabled:opacity-50"
+          >
+            {analyzing ? 'Analyzing...' : 'Detect'}
+          </button>
+          <button
+            type="button"
+            onClick={handleImport}
+            className="text-gray-400 hover:text-gray-300"
+          >
+            Import
+          </button>
+          <button
+            type="button"
+            disabled={exclusions.length === 0}
+            onClick={handleExport}
+            className="text-gray-400 hover:text-gray-300 disabled:opacity-50"
+          >
+            Export
+          </button>
+        </div>
+      </div>
+      {invalidReason && <p className="text-xs text-red-400">{invalidReason}</p>}
+      {normalized && normalized.warnings.length > 0 && (
+        <div className="space-y-1 text-xs text-yellow-400">
+          {normalized.warnings.map((warning) => (
+            <p key={warning}>{warning}</p>
+          ))}
+          <button
+            type="button"
+            onClick={() => setExclusions(normalized.intervals)}
+            className="text-yellow-300 underline hover:text-yellow-200"
+          >
+            Apply adjustments
+          </button>
+        </div>
+      )}
+      {suggestions.length > 0 && (
+        <ul className="space-y-1">
+          {suggestions.map((suggestion, index) => (
+            <li
+              key={`${suggestion.interval.startSecs}-${suggestion.interval.endSecs}`}
+              className="flex items-center justify-between gap-2 text-xs text-yellow-300"
+              title={suggestion.reason}
+            >
+              <span className="truncate">
+                {suggestion.interval.startSecs.toFixed(1)}s - {suggestion.interval.endSecs.toFixed(1)}s
+                {` (${Math.round(suggestion.confidence * 100)}%) ${suggestion.reason}`}
+              </span>
+              <span className="flex shrink-0 gap-2">
+                <button
+                  type="button"
+                  onClick={() => acceptSuggestion(index)}
+                  className="text-green-400 hover:text-green-300"
+                >
+                  Accept
+                </button>
+                <button
+                  type="button"
+                  onClick={() => rejectSuggestion(index)}
+                  className="text-red-400 hover:text-red-300"
+                >
+                  Reject
+                </button>
+              </span>
+            </li>
+          ))}
+        </ul>
+      )}
       {exclusions.length === 0 ? (
         <p className="text-xs text-gray-500">No exclusions. Select intervals on the timeline.</p>
       ) : (
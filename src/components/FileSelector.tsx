@@ -1,12 +1,31 @@
 import { open } from '@tauri-apps/api/dialog';
 import { invoke } from '@tauri-apps/api/tauri';
+import { useEffect, useState } from 'react';
 import type { DirectoryEntry } from '../stores/directoryStore';
 import { useDirectoryStore } from '../stores/directoryStore';
 import { useVideoStore } from '../stores/videoStore';
+import type { ThumbnailCacheInfo } from '../types/thumbnail';
+import { formatFileSize } from '../utils/formatters';
 
 export const FileSelector = () => {
   const setVideoFile = useVideoStore((state) => state.setVideoFile);
   const setDirectory = useDirectoryStore((state) => state.setDirectory);
+  const [cacheInfo, setCacheInfo] = useState<ThumbnailCacheInfo | null>(null);
+
+  useEffect(() => {
+    invoke<ThumbnailCacheInfo>('get_thumbnail_cache_info')
+      .then(setCacheInfo)
+      .catch((err) => console.error('Failed to read thumbnail cache:', err));
+  }, []);
+
+  const handleClearCache = async () => {
+    try {
+      await invoke<number>('clear_thumbnail_cache');
+      setCacheInfo(await invoke<ThumbnailCacheInfo>('get_thumbnail_cache_info'));
+    } catch (err) {
+      console.error('Failed to clear thumbnail cache:', err);
+    }
+  };
 
   const handleSelectFile = async () => {
     const selected = await open({
@@ -49,24 +68,36 @@ export const FileSelector = () => {
   };
 
   return (
-    <div className="flex flex-1 items-center justify-center gap-6">
-      <button
-        type="button"
-        onClick={handleSelectFile}
-        className="rounded-lg border-2 border-dashed border-gray-600 px-12 py-8 text-gray-400 transition-colors hover:border-blue-500 hover:text-blue-400"
-      >
-        <p className="text-lg font-medium">Select a video file</p>
-        <p className="mt-2 text-sm">Open a single video</p>
-      </button>
+    <div className="flex flex-1 flex-col items-center justify-center gap-4">
+      <div className="flex items-center justify-center gap-6">
+        <button
+          type="button"
+          onClick={handleSelectFile}
+          className="rounded-lg border-2 border-dashed border-gray-600 px-12 py-8 text-gray-400 transition-colors hover:border-blue-500 hover:text-blue-400"
+        >
+          <p className="text-lg font-medium">Select a video file</p>
+          <p className="mt-2 text-sm">Open a single video</p>
+        </button>
+
+        <button
+          type="button"
+          onClick={handleSelectDirectory}
+          className="rounded-lg border-2 border-dashed border-gray-600 px-12 py-8 text-gray-400 transition-colors hover:border-green-500 hover:text-green-400"
+        >
+          <p className="text-lg font-medium">Open Directory</p>
+          <p className="mt-2 text-sm">Browse videos and photos</p>
+        </button>
+      </div>
 
-      <button
-        type="button"
-        onClick={handleSelectDirectory}
-        className="rounded-lg border-2 border-dashed border-gray-600 px-12 py-8 text-gray-400 transition-colors hover:border-green-500 hover:text-green-400"
-      >
-        <p className="text-lg font-medium">Open Directory</p>
-        <p className="mt-2 text-sm">Browse videos and photos</p>
-      </button>
+      {cacheInfo && cacheInfo.entries.length > 0 && (
+        <p className="text-xs text-gray-500" title={cacheInfo.directory}>
+          Thumbnail cache: {formatFileSize(cacheInfo.totalBytes)} of{' '}
+          {formatFileSize(cacheInfo.limitBytes)} ({cacheInfo.entries.length} entries){' '}
+          <button type="button" onClick={handleClearCache} className="underline hover:text-gray-300">
+            Clear
+          </button>
+        </p>
+      )}
     </div>
   );
 };
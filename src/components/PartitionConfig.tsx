@@ -1,17 +1,28 @@
 import { open } from '@tauri-apps/api/dialog';
+import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/tauri';
 import { useEffect, useState } from 'react';
 import { usePartitionCalculator } from '../hooks/usePartitionCalculator';
 import { usePartitionStore } from '../stores/partitionStore';
 import { useVideoStore } from '../stores/videoStore';
-import { formatDuration, formatFileSize, gbToBytes } from '../utils/formatters';
+import type { PreflightReport, SplitPlan, SplitProgress, SplitRequest } from '../types/partition';
+import type { EncodeProfile, SplitPreset, SplitStrategy } from '../types/preset';
+import { bytesToGb, formatDuration, formatFileSize, gbToBytes } from '../utils/formatters';
 import { ExclusionEditor } from './ExclusionEditor';
 
 export const PartitionConfig = () => {
   const metadata = useVideoStore((state) => state.metadata);
   const videoFile = useVideoStore((state) => state.videoFile);
+  const strategy = usePartitionStore((state) => state.strategy);
+  const setStrategy = usePartitionStore((state) => state.setStrategy);
   const targetSizeGb = usePartitionStore((state) => state.targetSizeGb);
   const setTargetSizeGb = usePartitionStore((state) => state.setTargetSizeGb);
+  const targetDurationMins = usePartitionStore((state) => state.targetDurationMins);
+  const setTargetDurationMins = usePartitionStore((state) => state.setTargetDurationMins);
+  const encodeProfile = usePartitionStore((state) => state.encodeProfile);
+  const setEncodeProfile = usePartitionStore((state) => state.setEncodeProfile);
+  const namingTemplate = usePartitionStore((state) => state.namingTemplate);
+  const setNamingTemplate = usePartitionStore((state) => state.setNamingTemplate);
   const status = usePartitionStore((state) => state.status);
   const partitionPoints = usePartitionStore((state) => state.partitionPoints);
   const exclusions = usePartitionStore((state) => state.exclusions);
@@ -19,8 +30,15 @@ export const PartitionConfig = () => {
   const setProgress = usePartitionStore((state) => state.setProgress);
   const setError = usePartitionStore((state) => state.setError);
   const setOutputDir = usePartitionStore((state) => state.setOutputDir);
+  const presetName = usePartitionStore((state) => state.presetName);
+  const setPresetName = usePartitionStore((state) => state.setPresetName);
 
   const [deleteOriginal, setDeleteOriginal] = useState(true);
+  const [writeManifest, setWriteManifest] = useState(false);
+  const [scratchDir, setScratchDir] = useState<string | null>(null);
+  const [workerCount, setWorkerCount] = useState(1);
+  const [presets, setPresets] = useState<SplitPreset[]>([]);
+  const [plan, setPlan] = useState<SplitPlan | null>(null);
 
   const { calculate } = usePartitionCalculator();
 
@@ -29,34 +47,141 @@ export const PartitionConfig = () => {
     calculate();
   }, [calculate]);
 
+  useEffect(() => {
+    invoke<SplitPreset[]>('list_presets')
+      .then(setPresets)
+      .catch((err) => console.error('Failed to load presets:', err));
+  }, []);
+
+  const activePreset = presets.find((p) => p.name === presetName) ?? null;
+
+  // Apply the chosen preset, including one restored from the file's saved state
+  useEffect(() => {
+    if (!activePreset) return;
+    setStrategy(activePreset.strategy);
+    if (activePreset.strategy === 'size') {
+      setTargetSizeGb(Number(bytesToGb(activePreset.target).toFixed(2)));
+    } else {
+      setTargetDurationMins(Number((activePreset.target / 60).toFixed(2)));
+    }
+    setEncodeProfile(activePreset.encodeProfile);
+    setNamingTemplate(activePreset.namingTemplate);
+  }, [
+    activePreset,
+    setStrategy,
+    setTargetSizeGb,
+    setTargetDurationMins,
+    setEncodeProfile,
+    setNamingTemplate,
+  ]);
+
+  const handleSavePreset = async () => {
+    const name = window.prompt('Preset name');
+    if (!name) return;
+
+    const preset: SplitPreset = {
+      name,
+      strategy,
+      target: strategy === 'size' ? gbToBytes(targetSizeGb) : targetDurationMins * 60,
+      encodeProfile,
+      namingTemplate,
+      outputDir: activePreset?.outputDir ?? null,
+    };
+
+    try {
+      setPresets(await invoke<SplitPreset[]>('create_preset', { preset }));
+      setPresetName(name);
+    } catch (err) {
+      setError(err);
+    }
+  };
+
   const handleReset = () => {
     setStatus('idle');
     setProgress(0);
   };
 
-  const handleSplit = async () => {
-    if (!metadata || !videoFile) return;
-
-    const selectedDir = await open({
-      title: 'Select output folder',
+  const handleSelectScratchDir = async () => {
+    const selected = await open({
+      title: 'Select folder for temporary files',
       directory: true,
     });
+    setScratchDir(typeof selected === 'string' ? selected : null);
+  };
+
+  const handleCancel = async () => {
+    try {
+      await invoke('cancel_split');
+    } catch (err) {
+      console.error('Failed to cancel split:', err);
+    }
+  };
+
+  // Asks for the output folder unless the preset sets one, and builds the request for it
+  const prepareRequest = async (): Promise<SplitRequest | null> => {
+    if (!metadata || !videoFile) return null;
 
-    if (typeof selectedDir !== 'string') return;
+    const selectedDir =
+      activePreset?.outputDir ??
+      (await open({
+        title: 'Select output folder',
+        directory: true,
+      }));
+
+    if (typeof selectedDir !== 'string') return null;
 
     setOutputDir(selectedDir);
+
+    return {
+      inputPath: videoFile,
+      outputDir: selectedDir,
+      targetSizeBytes: gbToBytes(targetSizeGb),
+      targetDurationSecs: strategy === 'duration' ? targetDurationMins * 60 : null,
+      exclusions,
+      encodeProfile,
+      namingTemplate,
+      checksums: writeManifest ? ['sha256', 'blake3'] : [],
+      scratchDir,
+      workerCount,
+    };
+  };
+
+  const handlePreviewPlan = async () => {
+    const request = await prepareRequest();
+    if (!request) return;
+
+    try {
+      setPlan(await invoke<SplitPlan>('plan_split', { request }));
+    } catch (err) {
+      setError(err);
+    }
+  };
+
+  const runSplit = async (request: SplitRequest) => {
+    const retry = () => void runSplit(request);
+
+    try {
+      const space = await invoke<PreflightReport>('check_split_space', { request });
+      if (!space.sufficient) {
+        setError(
+          `Not enough disk space: ${formatFileSize(space.requiredBytes)} required, ${formatFileSize(space.availableBytes)} available`
+        );
+        return;
+      }
+    } catch (err) {
+      setError(err, retry);
+      return;
+    }
+
     setStatus('processing');
     setProgress(0);
 
+    const unlisten = await listen<SplitProgress>('split-progress', (event) => {
+      setProgress(event.payload.percent);
+    });
+
     try {
-      await invoke('execute_split', {
-        request: {
-          inputPath: videoFile,
-          outputDir: selectedDir,
-          targetSizeBytes: gbToBytes(targetSizeGb),
-          exclusions,
-        },
-      });
+      await invoke('execute_split', { request });
       setStatus('complete');
       setProgress(100);
 
@@ -68,11 +193,17 @@ export const PartitionConfig = () => {
         }
       }
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
-      setError(message);
+      setError(err, retry);
+    } finally {
+      unlisten();
     }
   };
 
+  const handleSplit = async () => {
+    const request = await prepareRequest();
+    if (request) await runSplit(request);
+  };
+
   return (
     <div className="space-y-4">
       <h2 className="text-lg font-semibold">Partition Settings</h2>
@@ -90,16 +221,106 @@ export const PartitionConfig = () => {
       )}
 
       <div>
-        <label htmlFor="partitionSize" className="block text-sm font-medium">
-          Target partition size (GB)
+        <label htmlFor="preset" className="block text-sm font-medium">
+          Preset
+        </label>
+        <div className="mt-1 flex gap-2">
+          <select
+            id="preset"
+            value={presetName ?? ''}
+            onChange={(e) => setPresetName(e.target.value || null)}
+            className="w-full rounded bg-gray-800 px-3 py-2 text-white"
+          >
+            <option value="">Choose a preset...</option>
+            {presets.map((preset) => (
+              <option key={preset.name} value={preset.name}>
+                {preset.name}
+              </option>
+            ))}
+          </select>
+          <button
+            type="button"
+            onClick={handleSavePreset}
+            className="rounded bg-gray-700 px-3 py-2 text-sm text-gray-300 transition-colors hover:bg-gray-600"
+          >
+            Save
+          </button>
+        </div>
+      </div>
+
+      <div>
+        <label htmlFor="strategy" className="block text-sm font-medium">
+          Split by
+        </label>
+        <select
+          id="strategy"
+          value={strategy}
+          onChange={(e) => setStrategy(e.target.value as SplitStrategy)}
+          className="mt-1 w-full rounded bg-gray-800 px-3 py-2 text-white"
+        >
+          <option value="size">Size</option>
+          <option value="duration">Duration</option>
+        </select>
+      </div>
+
+      {strategy === 'size' ? (
+        <div>
+          <label htmlFor="partitionSize" className="block text-sm font-medium">
+            Target partition size (GB)
+          </label>
+          <input
+            id="partitionSize"
+            type="number"
+            min={0.1}
+            step={0.1}
+            value={targetSizeGb}
+            onChange={(e) => setTargetSizeGb(Number(e.target.value))}
+            className="mt-1 w-full rounded bg-gray-800 px-3 py-2 text-white"
+          />
+        </div>
+      ) : (
+        <div>
+          <label htmlFor="partitionDuration" className="block text-sm font-medium">
+            Partition length (minutes)
+          </label>
+          <input
+            id="partitionDuration"
+            type="number"
+            min={0.1}
+            step={1}
+            value={targetDurationMins}
+            onChange={(e) => setTargetDurationMins(Number(e.target.value))}
+            className="mt-1 w-full rounded bg-gray-800 px-3 py-2 text-white"
+          />
+        </div>
+      )}
+
+      <div>
+        <label htmlFor="encodeProfile" className="block text-sm font-medium">
+          Encoding
+        </label>
+        <select
+          id="encodeProfile"
+          value={encodeProfile}
+          onChange={(e) => setEncodeProfile(e.target.value as EncodeProfile)}
+          className="mt-1 w-full rounded bg-gray-800 px-3 py-2 text-white"
+        >
+          <option value="copy">Stream copy (fast, lossless)</option>
+          <option value="h264">H.264 MP4</option>
+          <option value="hevc">HEVC MP4</option>
+        </select>
+      </div>
+
+      <div>
+        <label htmlFor="namingTemplate" className="block text-sm font-medium">
+          File names
         </label>
         <input
-          id="partitionSize"
-          type="number"
-          min={0.1}
-          step={0.1}
-          value={targetSizeGb}
-          onChange={(e) => setTargetSizeGb(Number(e.target.value))}
+          id="namingTemplate"
+          type="text"
+          value={namingTemplate}
+          onChange={(e) => setNamingTemplate(e.target.value)}
+          title="{stem}, {n} and {ext} are replaced by the source name, part number and extension"
           className="mt-1 w-full rounded bg-gray-800 px-3 py-2 text-white"
         />
       </div>
@@ -110,6 +331,22 @@ export const PartitionConfig = () => {
 
       <ExclusionEditor />
 
+      <div>
+        <label htmlFor="workerCount" className="block text-sm font-medium">
+          Parallel partitions
+        </label>
+        <input
+          id="workerCount"
+          type="number"
+          min={1}
+          max={16}
+          step={1}
+          value={workerCount}
+          onChange={(e) => setWorkerCount(Math.max(1, Math.round(Number(e.target.value))))}
+          className="mt-1 w-full rounded bg-gray-800 px-3 py-2 text-white"
+        />
+      </div>
+
       <label className="flex items-center gap-2 text-sm text-gray-400">
         <input
           type="checkbox"
@@ -120,6 +357,89 @@ export const PartitionConfig = () => {
         Delete original after split
       </label>
 
+      <label className="flex items-center gap-2 text-sm text-gray-400">
+        <input
+          type="checkbox"
+          checked={writeManifest}
+          onChange={(e) => setWriteManifest(e.target.checked)}
+          className="rounded"
+        />
+        Write checksum manifest
+      </label>
+
+      <div className="text-sm text-gray-400">
+        <p className="truncate" title={scratchDir ?? undefined}>
+          Temporary files: {scratchDir ?? 'system temp folder'}
+        </p>
+        <button
+          type="button"
+          onClick={handleSelectScratchDir}
+          className="mt-1 rounded bg-gray-700 px-2 py-0.5 text-xs text-gray-300 transition-colors hover:bg-gray-600"
+        >
+          Change
+        </button>
+      </div>
+
+      {plan && (
+        <div className="space-y-2 rounded bg-gray-800 p-3 text-xs text-gray-300">
+          <div className="flex items-center justify-between">
+            <p className="font-medium">
+              Plan: {plan.partitions.length} partition(s),{' '}
+              {formatFileSize(plan.space.requiredBytes)} needed,{' '}
+              {formatFileSize(plan.space.availableBytes)} free
+            </p>
+            <button
+              type="button"
+              onClick={() => setPlan(null)}
+              className="text-gray-500 hover:text-gray-300"
+            >
+              Close
+            </button>
+          </div>
+          {plan.warnings.map((warning) => (
+            <p key={warning} className="text-yellow-400">
+              {warning}
+            </p>
+          ))}
+          {plan.partitions.map((partition) => (
+            <details key={partition.index}>
+              <summary className="cursor-pointer truncate" title={partition.outputPath}>
+                {partition.outputPath} (~{formatFileSize(partition.estimatedSizeBytes)})
+              </summary>
+              <ul className="mt-1 space-y-0.5 pl-4 text-gray-400">
+                {partition.segments.map((segment) => (
+                  <li key={segment.interval.startSecs}>
+                    {formatDuration(segment.interval.startSecs)} –{' '}
+                    {formatDuration(segment.interval.endSecs)}
+                    {segment.keyframeStartSecs !== null &&
+                      segment.keyframeStartSecs < segment.interval.startSecs &&
+                      ` (starts at keyframe ${formatDuration(segment.keyframeStartSecs)})`}
+                  </li>
+                ))}
+              </ul>
+              <pre className="mt-1 whitespace-pre-wrap break-all text-gray-500">
+                ffmpeg {partition.ffmpegArgs.join(' ')}
+              </pre>
+              {partition.concatList && (
+                <pre className="mt-1 whitespace-pre-wrap break-all text-gray-500">
+                  {partition.concatList}
+                </pre>
+              )}
+            </details>
+          ))}
+        </div>
+      )}
+
+      {status === 'processing' && (
+        <button
+          type="button"
+          onClick={handleCancel}
+          className="w-full rounded bg-red-700 px-4 py-2 font-medium text-white transition-colors hover:bg-red-600"
+        >
+          Cancel
+        </button>
+      )}
+
       {status === 'complete' ? (
         <button
           type="button"
@@ -129,14 +449,24 @@ export const PartitionConfig = () => {
           Reset
         </button>
       ) : (
-        <button
-          type="button"
-          disabled={!metadata || status === 'processing'}
-          onClick={handleSplit}
-          className="w-full rounded bg-blue-600 px-4 py-2 font-medium text-white transition-colors hover:bg-blue-700 disabled:opacity-50"
-        >
-          {status === 'processing' ? 'Processing...' : 'Split Video'}
-        </button>
+        <>
+          <button
+            type="button"
+            disabled={!metadata || status === 'processing'}
+            onClick={handlePreviewPlan}
+            className="w-full rounded bg-gray-700 px-4 py-2 font-medium text-gray-300 transition-colors hover:bg-gray-600 disabled:opacity-50"
+          >
+            Preview Plan
+          </button>
+          <button
+            type="button"
+            disabled={!metadata || status === 'processing'}
+            onClick={handleSplit}
+            className="w-full rounded bg-blue-600 px-4 py-2 font-medium text-white transition-colors hover:bg-blue-700 disabled:opacity-50"
+          >
+            {status === 'processing' ? 'Processing...' : 'Split Video'}
+          </button>
+        </>
       )}
     </div>
   );
@@ -1,4 +1,4 @@
-import { convertFileSrc } from '@tauri-apps/api/tauri';
+import { usePreviewSource } from '../hooks/usePreviewSource';
 import { useDirectoryStore } from '../stores/directoryStore';
 
 export const PhotoViewer = () => {
@@ -6,6 +6,7 @@ export const PhotoViewer = () => {
   const currentIndex = useDirectoryStore((state) => state.currentIndex);
 
   const currentFile = files[currentIndex];
+  const preview = usePreviewSource(currentFile?.path ?? null);
 
   if (!currentFile) {
     return (
@@ -15,13 +16,16 @@ export const PhotoViewer = () => {
     );
   }
 
-  const imgSrc = convertFileSrc(currentFile.path);
-
   return (
     <div className="relative flex flex-1 items-center justify-center bg-black">
-      <img src={imgSrc} alt={currentFile.name} className="max-h-full max-w-full object-contain" />
+      {preview.src ? (
+        <img src={preview.src} alt={currentFile.name} className="max-h-full max-w-full object-contain" />
+      ) : (
+        <span className="text-sm text-gray-500">{preview.error ?? 'Preparing preview…'}</span>
+      )}
       <div className="absolute bottom-0 left-0 right-0 bg-black/60 px-4 py-2 text-center text-sm text-gray-300">
         {currentFile.name}
+        {preview.transcoded && <span className="ml-4 text-gray-500">Converted preview</span>}
         <span className="ml-4 text-gray-500">Press Delete to move to trash</span>
       </div>
     </div>
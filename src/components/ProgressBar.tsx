@@ -4,6 +4,8 @@ export const ProgressBar = () => {
   const status = usePartitionStore((state) => state.status);
   const progress = usePartitionStore((state) => state.progress);
   const errorMessage = usePartitionStore((state) => state.errorMessage);
+  const errorDetail = usePartitionStore((state) => state.errorDetail);
+  const retry = usePartitionStore((state) => state.retry);
   const setStatus = usePartitionStore((state) => state.setStatus);
   const setProgress = usePartitionStore((state) => state.setProgress);
 
@@ -34,7 +36,29 @@ export const ProgressBar = () => {
   if (status === 'error') {
     return (
       <div className="flex items-center gap-3">
-        <p className="text-xs text-red-400">{errorMessage}</p>
+        <div className="min-w-0 flex-1">
+          <p className="text-xs text-red-400">{errorMessage}</p>
+          {errorDetail?.stderr && (
+            <details className="text-xs text-gray-500">
+              <summary className="cursor-pointer">
+                FFmpeg output
+                {errorDetail.exitCode !== null && ` (exit code ${errorDetail.exitCode})`}
+              </summary>
+              <pre className="mt-1 max-h-32 overflow-auto whitespace-pre-wrap break-all">
+                {errorDetail.stderr}
+              </pre>
+            </details>
+          )}
+        </div>
+        {retry && errorDetail?.retryable && (
+          <button
+            type="button"
+            onClick={retry}
+            className="rounded bg-blue-600 px-2 py-0.5 text-xs text-white transition-colors hover:bg-blue-700"
+          >
+            Retry
+          </button>
+        )}
         <button
           type="button"
           onClick={handleReset}
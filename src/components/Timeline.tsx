@@ -1,28 +1,41 @@
+import { convertFileSrc } from '@tauri-apps/api/tauri';
 import { useCallback, useRef, useState } from 'react';
+import { usePreviewFrame } from '../hooks/usePreviewFrame';
 import { useThumbnails } from '../hooks/useThumbnails';
 import { usePartitionStore } from '../stores/partitionStore';
 import { useVideoStore } from '../stores/videoStore';
 import { formatDuration } from '../utils/formatters';
 
+/** Height of the hover preview in pixels */
+const PREVIEW_HEIGHT = 56;
+
 type DragMode =
   | { type: 'create'; startTime: number; endTime: number }
   | { type: 'resize-start'; index: number; time: number }
   | { type: 'resize-end'; index: number; time: number };
 
 export const Timeline = () => {
+  const videoFile = useVideoStore((state) => state.videoFile);
   const metadata = useVideoStore((state) => state.metadata);
   const currentTime = useVideoStore((state) => state.currentTime);
   const setSeekTo = useVideoStore((state) => state.setSeekTo);
   const thumbnails = useVideoStore((state) => state.thumbnails);
+  const sprite = useVideoStore((state) => state.sprite);
   const partitionPoints = usePartitionStore((state) => state.partitionPoints);
   const exclusions = usePartitionStore((state) => state.exclusions);
   const addExclusion = usePartitionStore((state) => state.addExclusion);
   const updateExclusion = usePartitionStore((state) => state.updateExclusion);
+  const suggestions = usePartitionStore((state) => state.suggestions);
 
   useThumbnails();
 
   const barRef = useRef<HTMLDivElement>(null);
   const [drag, setDrag] = useState<DragMode | null>(null);
+  const [hoverTime, setHoverTime] = useState<number | null>(null);
+
+  // While an exclusion edge is being placed, show the exact frame at the edge
+  const edgeTime = drag ? (drag.type === 'create' ? drag.endTime : drag.time) : null;
+  const edgeFrame = usePreviewFrame(videoFile, edgeTime);
 
   const pixelToTime = useCallback(
     (clientX: number): number => {
@@ -65,8 +78,9 @@ export const Timeline = () => {
 
   const handleMouseMove = useCallback(
     (e: React.MouseEvent) => {
-      if (!drag) return;
       const time = pixelToTime(e.clientX);
+      setHoverTime(time);
+      if (!drag) return;
 
       if (drag.type === 'create') {
         setDrag({ ...drag, endTime: time });
@@ -119,6 +133,7 @@ export const Timeline = () => {
   }, [commitDrag]);
 
   const handleMouseLeave = useCallback(() => {
+    setHoverTime(null);
     commitDrag();
   }, [commitDrag]);
 
@@ -142,6 +157,13 @@ export const Timeline = () => {
       : -1;
   const nearestThumbUrl = nearestThumbIndex >= 0 ? thumbnails[nearestThumbIndex] : null;
 
+  // Sprite tile under the mouse, for scrubbing previews at any position
+  const hoverTile =
+    sprite && hoverTime !== null
+      ? Math.min(sprite.count - 1, Math.floor(hoverTime / sprite.intervalSecs))
+      : null;
+  const showHoverPreview = hoverTime !== null && (edgeFrame !== null || hoverTile !== null);
+
   // Compute preview positions for active drag
   const getExclusionStyle = (index: number) => {
     const excl = exclusions[index];
@@ -173,8 +195,48 @@ export const Timeline = () => {
 
   return (
     <div className="border-t border-gray-700 bg-gray-800 px-4 pb-3 pt-2">
+      {/* Hover preview: the exact frame at a dragged edge, otherwise the sprite tile */}
+      {showHoverPreview && hoverTime !== null && (
+        <div className="relative mb-1 h-16">
+          <div
+            className="pointer-events-none absolute top-0"
+            style={{
+              left: `clamp(40px, ${(hoverTime / metadata.durationSecs) * 100}%, calc(100% - 40px))`,
+              transform: 'translateX(-50%)',
+            }}
+          >
+            {edgeFrame ? (
+              <img
+                src={edgeFrame}
+                alt="Frame at edge"
+                className="rounded border-2 border-red-400 shadow-lg"
+                style={{ height: PREVIEW_HEIGHT }}
+                draggable={false}
+              />
+            ) : (
+              sprite &&
+              hoverTile !== null && (
+                <div
+                  className="rounded border-2 border-gray-300 shadow-lg"
+                  style={{
+                    width: (sprite.tileWidth * PREVIEW_HEIGHT) / sprite.tileHeight,
+                    height: PREVIEW_HEIGHT,
+                    backgroundImage: `url("${convertFileSrc(sprite.imagePath)}")`,
+                    backgroundSize: `${(sprite.columns * sprite.tileWidth * PREVIEW_HEIGHT) / sprite.tileHeight}px auto`,
+                    backgroundPosition: `-${((hoverTile % sprite.columns) * sprite.tileWidth * PREVIEW_HEIGHT) / sprite.tileHeight}px -${Math.floor(hoverTile / sprite.columns) * PREVIEW_HEIGHT}px`,
+                  }}
+                />
+              )
+            )}
+            <span className="absolute bottom-1 right-2 text-xs text-white drop-shadow">
+              {formatDuration(hoverTime)}
+            </span>
+          </div>
+        </div>
+      )}
+
       {/* Current-position thumbnail preview */}
-      {nearestThumbUrl && (
+      {nearestThumbUrl && !showHoverPreview && (
         <div className="relative mb-1 h-16">
           <div
             className="pointer-events-none absolute top-0"
@@ -210,16 +272,21 @@ export const Timeline = () => {
         {/* Thumbnail strip */}
         {thumbnails.length > 0 && (
           <div className="absolute inset-0 z-0 flex">
-            {thumbnails.map((url, i) => (
-              <img
-                key={url}
-                src={url}
-                alt={`Thumbnail ${i + 1}`}
-                className="h-full object-cover"
-                style={{ width: `${100 / thumbnails.length}%` }}
-                draggable={false}
-              />
-            ))}
+            {thumbnails.map((url, i) =>
+              url ? (
+                <img
+                  key={url}
+                  src={url}
+                  alt={`Thumbnail ${i + 1}`}
+                  className="h-full object-cover"
+                  style={{ width: `${100 / thumbnails.length}%` }}
+                  draggable={false}
+                />
+              ) : (
+                // Still being generated
+                <div key={i} style={{ width: `${100 / thumbnails.length}%` }} />
+              )
+            )}
           </div>
         )}
 
@@ -250,6 +317,22 @@ export const Timeline = () => {
           );
         })}
 
+        {/* Suggested exclusions awaiting accept or reject */}
+        {suggestions.map((suggestion) => {
+          const { startSecs, endSecs } = suggestion.interval;
+          return (
+            <div
+              key={`suggestion-${startSecs}-${endSecs}`}
+              title={suggestion.reason}
+              className="pointer-events-none absolute top-0 z-10 h-full border border-dashed border-yellow-400/70 bg-yellow-500/20"
+              style={{
+                left: `${(startSecs / metadata.durationSecs) * 100}%`,
+                width: `${((endSecs - startSecs) / metadata.durationSecs) * 100}%`,
+              }}
+            />
+          );
+        })}
+
         {/* Active drag selection */}
         {drag?.type === 'create' && (
           <div
@@ -1,5 +1,5 @@
-import { convertFileSrc } from '@tauri-apps/api/tauri';
 import { useEffect, useRef } from 'react';
+import { usePreviewSource } from '../hooks/usePreviewSource';
 import { useVideoMetadata } from '../hooks/useVideoMetadata';
 import { useVideoStore } from '../stores/videoStore';
 
@@ -14,6 +14,7 @@ export const VideoPlayer = () => {
   const togglePlaySignal = useVideoStore((state) => state.togglePlaySignal);
   const clearTogglePlay = useVideoStore((state) => state.clearTogglePlay);
   const { fetchMetadata } = useVideoMetadata();
+  const preview = usePreviewSource(videoFile);
 
   useEffect(() => {
     if (seekTo !== null && videoRef.current) {
@@ -37,7 +38,13 @@ export const VideoPlayer = () => {
     return null;
   }
 
-  const videoSrc = convertFileSrc(videoFile);
+  if (!preview.src) {
+    return (
+      <div className="flex flex-1 items-center justify-center bg-black text-sm text-gray-500">
+        {preview.error ?? 'Preparing a preview this player can show…'}
+      </div>
+    );
+  }
 
   const handleTimeUpdate = () => {
     if (videoRef.current) {
@@ -51,18 +58,20 @@ export const VideoPlayer = () => {
 
     const fileName = videoFile.split('\\').pop() ?? videoFile.split('/').pop() ?? videoFile;
 
-    // Set initial metadata from the HTML5 video element
+    // Set initial metadata from the HTML5 video element; a proxy's resolution is not the
+    // source's, so its dimensions are left to the backend
     setMetadata({
       filePath: videoFile,
       fileName,
       fileSize: 0,
       durationSecs: video.duration,
-      width: video.videoWidth,
-      height: video.videoHeight,
+      width: preview.transcoded ? 0 : video.videoWidth,
+      height: preview.transcoded ? 0 : video.videoHeight,
       videoCodec: 'unknown',
       audioCodec: null,
       bitrate: 0,
       format: fileName.split('.').pop() ?? 'unknown',
+      frameRate: 0,
     });
 
     // Fetch full metadata from the backend (file size, codecs, bitrate)
@@ -70,10 +79,10 @@ export const VideoPlayer = () => {
   };
 
   return (
-    <div className="flex-1 bg-black">
+    <div className="relative flex-1 bg-black">
       <video
         ref={videoRef}
-        src={videoSrc}
+        src={preview.src}
         controls
         onTimeUpdate={handleTimeUpdate}
         onLoadedMetadata={handleLoadedMetadata}
@@ -81,6 +90,11 @@ export const VideoPlayer = () => {
       >
         <track kind="captions" />
       </video>
+      {preview.transcoded && (
+        <span className="absolute right-2 top-2 rounded bg-black/60 px-2 py-1 text-xs text-gray-300">
+          Low-resolution preview — output uses the original file
+        </span>
+      )}
     </div>
   );
 };
@@ -33,8 +33,7 @@ export const useFfmpegProcess = () => {
       setStatus('complete');
       setProgress(100);
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
-      setError(message);
+      setError(err);
     }
   }, [videoFile, outputDir, targetSizeGb, exclusions, setStatus, setProgress, setError]);
 
@@ -0,0 +1,42 @@
+import { invoke } from '@tauri-apps/api/tauri';
+import { useCallback } from 'react';
+import { useToastStore } from '../stores/toastStore';
+import { useVideoStore } from '../stores/videoStore';
+import type { Burst, CapturedFrame } from '../types/capture';
+import { describeError } from '../utils/errors';
+
+const fileNameOf = (path: string) => path.split(/[\\/]/).pop() ?? path;
+
+/**
+ * Captures the frame the player shows, or a burst starting there, with the saved settings.
+ *
+ * The frame rate lets the backend snap the player time to the displayed frame.
+ */
+export const useFrameCapture = () => {
+  const videoFile = useVideoStore((state) => state.videoFile);
+  const currentTime = useVideoStore((state) => state.currentTime);
+  const frameRate = useVideoStore((state) => state.metadata?.frameRate ?? 0);
+  const addToast = useToastStore((state) => state.addToast);
+
+  return useCallback(
+    async (burst?: Burst) => {
+      if (!videoFile) return;
+      try {
+        const frames = await invoke<CapturedFrame[]>('capture_frame', {
+          videoPath: videoFile,
+          timestampSecs: currentTime,
+          frameRate: frameRate > 0 ? frameRate : null,
+          burst: burst ?? null,
+        });
+        const message =
+          frames.length === 1
+            ? `Saved: ${fileNameOf(frames[0].path)} (frame at ${frames[0].timestampSecs.toFixed(3)}s)`
+            : `Saved ${frames.length} frames`;
+        addToast(message, 'success');
+      } catch (err) {
+        addToast(`Screenshot failed: ${describeError(err)}`, 'error');
+      }
+    },
+    [videoFile, currentTime, frameRate, addToast]
+  );
+};
@@ -0,0 +1,40 @@
+import { invoke } from '@tauri-apps/api/tauri';
+import { useEffect, useRef, useState } from 'react';
+import { isAppError } from '../utils/errors';
+
+const PREVIEW_HEIGHT = 90;
+
+/**
+ * Fetches the exact frame at `timeSecs` while the mouse moves over the timeline.
+ *
+ * The backend cancels requests superseded by newer ones, so only the latest position
+ * is decoded; the last frame stays visible until the next one arrives.
+ */
+export const usePreviewFrame = (videoFile: string | null, timeSecs: number | null) => {
+  const [frame, setFrame] = useState<string | null>(null);
+  const latestRequest = useRef(0);
+
+  useEffect(() => {
+    if (!videoFile || timeSecs === null) {
+      setFrame(null);
+      return;
+    }
+
+    const request = ++latestRequest.current;
+    invoke<string>('preview_frame', {
+      videoPath: videoFile,
+      timestampSecs: timeSecs,
+      height: PREVIEW_HEIGHT,
+    })
+      .then((dataUrl) => {
+        if (request === latestRequest.current) setFrame(dataUrl);
+      })
+      .catch((err) => {
+        if (!isAppError(err) || err.code !== 'cancelled') {
+          console.error('Failed to load preview frame:', err);
+        }
+      });
+  }, [videoFile, timeSecs]);
+
+  return frame;
+};
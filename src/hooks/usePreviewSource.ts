@@ -0,0 +1,44 @@
+import { convertFileSrc, invoke } from '@tauri-apps/api/tauri';
+import { useEffect, useState } from 'react';
+import type { PreviewSource } from '../types/video';
+import { describeError, isAppError } from '../utils/errors';
+
+interface PreviewState {
+  src: string | null;
+  transcoded: boolean;
+  error: string | null;
+}
+
+const EMPTY: PreviewState = { src: null, transcoded: false, error: null };
+
+/**
+ * Resolves a URL the webview can display for `filePath`.
+ *
+ * Formats it can't decode are converted by the backend first; `src` stays null
+ * until the converted copy is ready. Switching files cancels a running conversion.
+ */
+export const usePreviewSource = (filePath: string | null) => {
+  const [state, setState] = useState<PreviewState>(EMPTY);
+
+  useEffect(() => {
+    setState(EMPTY);
+    if (!filePath) return;
+
+    let cancelled = false;
+    invoke<PreviewSource>('prepare_preview', { filePath })
+      .then((source) => {
+        if (cancelled) return;
+        setState({ src: convertFileSrc(source.path), transcoded: source.transcoded, error: null });
+      })
+      .catch((err) => {
+        if (cancelled || (isAppError(err) && err.code === 'cancelled')) return;
+        setState({ ...EMPTY, error: describeError(err) });
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [filePath]);
+
+  return state;
+};
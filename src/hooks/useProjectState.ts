@@ -0,0 +1,56 @@
+import { invoke } from '@tauri-apps/api/tauri';
+import { useEffect, useState } from 'react';
+import { usePartitionStore } from '../stores/partitionStore';
+import { useVideoStore } from '../stores/videoStore';
+import type { ProjectState } from '../types/project';
+
+const SAVE_DELAY_MS = 500;
+
+/** Restores the saved editing state when a video opens and saves it as it changes. */
+export const useProjectState = () => {
+  const videoFile = useVideoStore((state) => state.videoFile);
+  const restoreProject = usePartitionStore((state) => state.restoreProject);
+  const exclusions = usePartitionStore((state) => state.exclusions);
+  const splitPoints = usePartitionStore((state) => state.splitPoints);
+  const presetName = usePartitionStore((state) => state.presetName);
+  const bookmarks = usePartitionStore((state) => state.bookmarks);
+
+  // The file whose state has been loaded; saving waits for it so a restore
+  // isn't overwritten by the empty state of a freshly reset store
+  const [loadedFile, setLoadedFile] = useState<string | null>(null);
+
+  useEffect(() => {
+    setLoadedFile(null);
+    if (!videoFile) return;
+
+    let cancelled = false;
+
+    invoke<ProjectState | null>('load_project_state', { filePath: videoFile })
+      .then((state) => {
+        if (cancelled) return;
+        if (state) restoreProject(state);
+        setLoadedFile(videoFile);
+      })
+      .catch((err) => {
+        console.error('Failed to load project state:', err);
+        if (!cancelled) setLoadedFile(videoFile);
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [videoFile, restoreProject]);
+
+  useEffect(() => {
+    if (!loadedFile || loadedFile !== videoFile) return;
+
+    const state: ProjectState = { exclusions, splitPoints, preset: presetName, bookmarks };
+    const timer = setTimeout(() => {
+      invoke('save_project_state', { filePath: loadedFile, state }).catch((err) =>
+        console.error('Failed to save project state:', err)
+      );
+    }, SAVE_DELAY_MS);
+
+    return () => clearTimeout(timer);
+  }, [loadedFile, videoFile, exclusions, splitPoints, presetName, bookmarks]);
+};
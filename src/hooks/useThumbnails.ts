@@ -1,39 +1,64 @@
+import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/tauri';
 import { convertFileSrc } from '@tauri-apps/api/tauri';
 import { useEffect } from 'react';
 import { useVideoStore } from '../stores/videoStore';
+import type { ThumbnailReady, ThumbnailStrip } from '../types/thumbnail';
 
 const THUMBNAIL_COUNT = 20;
 const THUMBNAIL_HEIGHT = 60;
+// Hover previews use a denser sprite sheet rendered in one pass
+const SPRITE_TILE_COUNT = 100;
+const SPRITE_TILE_HEIGHT = 90;
 
 export const useThumbnails = () => {
   const videoFile = useVideoStore((state) => state.videoFile);
   const setThumbnails = useVideoStore((state) => state.setThumbnails);
+  const setThumbnail = useVideoStore((state) => state.setThumbnail);
+  const setSprite = useVideoStore((state) => state.setSprite);
 
   useEffect(() => {
     if (!videoFile) {
       setThumbnails([]);
+      setSprite(null);
       return;
     }
 
     let cancelled = false;
+    setThumbnails(Array<string | null>(THUMBNAIL_COUNT).fill(null));
+
+    // Fill in each slot as soon as its thumbnail exists
+    const unlistenPromise = listen<ThumbnailReady>('thumbnail-ready', (event) => {
+      if (cancelled || event.payload.videoPath !== videoFile) return;
+      setThumbnail(event.payload.index, convertFileSrc(event.payload.path));
+    });
 
     const generate = async () => {
       try {
-        const paths = await invoke<string[]>('generate_thumbnails', {
+        const strip = await invoke<ThumbnailStrip>('generate_thumbnails', {
           videoPath: videoFile,
           count: THUMBNAIL_COUNT,
           height: THUMBNAIL_HEIGHT,
         });
 
-        if (cancelled) return;
+        if (cancelled || strip.layout !== 'files') return;
 
-        const urls = paths.map((p) => convertFileSrc(p));
+        const urls = strip.paths.map((p) => convertFileSrc(p));
         setThumbnails(urls);
       } catch {
-        if (!cancelled) {
-          setThumbnails([]);
-        }
+        // Keep whatever thumbnails arrived before the failure
+      }
+
+      try {
+        const sprite = await invoke<ThumbnailStrip>('generate_thumbnails', {
+          videoPath: videoFile,
+          count: SPRITE_TILE_COUNT,
+          height: SPRITE_TILE_HEIGHT,
+          layout: 'sprite',
+        });
+        if (!cancelled && sprite.layout === 'sprite') setSprite(sprite);
+      } catch (err) {
+        console.error('Failed to generate sprite sheet:', err);
       }
     };
 
@@ -41,6 +66,7 @@ export const useThumbnails = () => {
 
     return () => {
       cancelled = true;
+      unlistenPromise.then((unlisten) => unlisten());
     };
-  }, [videoFile, setThumbnails]);
+  }, [videoFile, setThumbnails, setThumbnail, setSprite]);
 };
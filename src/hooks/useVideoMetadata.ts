@@ -2,6 +2,7 @@ import { invoke } from '@tauri-apps/api/tauri';
 import { useCallback, useState } from 'react';
 import { useVideoStore } from '../stores/videoStore';
 import type { VideoMetadata } from '../types/video';
+import { describeError } from '../utils/errors';
 
 export const useVideoMetadata = () => {
   const [loading, setLoading] = useState(false);
@@ -36,11 +37,11 @@ export const useVideoMetadata = () => {
           audioCodec: backend.audioCodec || current?.audioCodec || null,
           bitrate: backend.bitrate || current?.bitrate || 0,
           format: backend.format || current?.format || 'unknown',
+          frameRate: backend.frameRate || current?.frameRate || 0,
         };
         setMetadata(merged);
       } catch (err) {
-        const message = err instanceof Error ? err.message : String(err);
-        setError(message);
+        setError(describeError(err));
       } finally {
         setLoading(false);
       }
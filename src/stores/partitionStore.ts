@@ -1,35 +1,81 @@
 import { create } from 'zustand';
-import type { PartitionPoint, ProcessingStatus, TimeInterval } from '../types/partition';
+import type { AppError } from '../types/error';
+import { describeError, isAppError } from '../utils/errors';
+import type {
+  ExclusionSuggestion,
+  PartitionPoint,
+  ProcessingStatus,
+  TimeInterval,
+} from '../types/partition';
+import type { EncodeProfile, SplitStrategy } from '../types/preset';
+import type { Bookmark, ProjectState } from '../types/project';
+
+export const DEFAULT_NAMING_TEMPLATE = '{stem}_part{n}.{ext}';
 
 interface PartitionState {
+  strategy: SplitStrategy;
   targetSizeGb: number;
+  targetDurationMins: number;
+  encodeProfile: EncodeProfile;
+  namingTemplate: string;
   exclusions: TimeInterval[];
+  splitPoints: number[];
+  presetName: string | null;
+  bookmarks: Bookmark[];
+  suggestions: ExclusionSuggestion[];
   partitionPoints: PartitionPoint[];
   outputDir: string | null;
   status: ProcessingStatus;
   progress: number;
   errorMessage: string | null;
+  errorDetail: AppError | null;
+  retry: (() => void) | null;
+  setStrategy: (strategy: SplitStrategy) => void;
   setTargetSizeGb: (size: number) => void;
+  setTargetDurationMins: (minutes: number) => void;
+  setEncodeProfile: (profile: EncodeProfile) => void;
+  setNamingTemplate: (template: string) => void;
   addExclusion: (interval: TimeInterval) => void;
   removeExclusion: (index: number) => void;
   updateExclusion: (index: number, interval: TimeInterval) => void;
+  setExclusions: (intervals: TimeInterval[]) => void;
+  setSuggestions: (suggestions: ExclusionSuggestion[]) => void;
+  acceptSuggestion: (index: number) => void;
+  rejectSuggestion: (index: number) => void;
+  setPresetName: (name: string | null) => void;
+  restoreProject: (state: ProjectState) => void;
   setPartitionPoints: (points: PartitionPoint[]) => void;
   setOutputDir: (dir: string | null) => void;
   setStatus: (status: ProcessingStatus) => void;
   setProgress: (progress: number) => void;
-  setError: (message: string | null) => void;
+  /** Shows a failure; `retry` is offered when the backend marks the error retryable. */
+  setError: (error: unknown, retry?: () => void) => void;
   reset: () => void;
 }
 
 export const usePartitionStore = create<PartitionState>()((set) => ({
+  strategy: 'size',
   targetSizeGb: 4,
+  targetDurationMins: 15,
+  encodeProfile: 'copy',
+  namingTemplate: DEFAULT_NAMING_TEMPLATE,
   exclusions: [],
+  splitPoints: [],
+  presetName: null,
+  bookmarks: [],
+  suggestions: [],
   partitionPoints: [],
   outputDir: null,
   status: 'idle',
   progress: 0,
   errorMessage: null,
+  errorDetail: null,
+  retry: null,
+  setStrategy: (strategy) => set({ strategy }),
   setTargetSizeGb: (size) => set({ targetSizeGb: size }),
+  setTargetDurationMins: (minutes) => set({ targetDurationMins: minutes }),
+  setEncodeProfile: (profile) => set({ encodeProfile: profile }),
+  setNamingTemplate: (template) => set({ namingTemplate: template }),
   addExclusion: (interval) => set((state) => ({ exclusions: [...state.exclusions, interval] })),
   removeExclusion: (index) =>
     set((state) => ({
@@ -39,19 +85,54 @@ export const usePartitionStore = create<PartitionState>()((set) => ({
     set((state) => ({
       exclusions: state.exclusions.map((e, i) => (i === index ? interval : e)),
     })),
+  setExclusions: (intervals) => set({ exclusions: intervals }),
+  setSuggestions: (suggestions) => set({ suggestions }),
+  acceptSuggestion: (index) =>
+    set((state) => ({
+      exclusions: [...state.exclusions, state.suggestions[index].interval],
+      suggestions: state.suggestions.filter((_, i) => i !== index),
+    })),
+  rejectSuggestion: (index) =>
+    set((state) => ({
+      suggestions: state.suggestions.filter((_, i) => i !== index),
+    })),
+  setPresetName: (name) => set({ presetName: name }),
+  restoreProject: (state) =>
+    set({
+      exclusions: state.exclusions,
+      splitPoints: state.splitPoints,
+      presetName: state.preset,
+      bookmarks: state.bookmarks,
+    }),
   setPartitionPoints: (points) => set({ partitionPoints: points }),
   setOutputDir: (dir) => set({ outputDir: dir }),
   setStatus: (status) => set({ status }),
   setProgress: (progress) => set({ progress }),
-  setError: (message) => set({ errorMessage: message, status: 'error' }),
+  setError: (error, retry) =>
+    set({
+      errorMessage: describeError(error),
+      errorDetail: isAppError(error) ? error : null,
+      retry: retry ?? null,
+      status: 'error',
+    }),
   reset: () =>
     set({
+      strategy: 'size',
       targetSizeGb: 4,
+      targetDurationMins: 15,
+      encodeProfile: 'copy',
+      namingTemplate: DEFAULT_NAMING_TEMPLATE,
       exclusions: [],
+      splitPoints: [],
+      presetName: null,
+      bookmarks: [],
+      suggestions: [],
       partitionPoints: [],
       outputDir: null,
       status: 'idle',
       progress: 0,
       errorMessage: null,
+      errorDetail: null,
+      retry: null,
     }),
 }));
@@ -1,4 +1,5 @@
 import { create } from 'zustand';
+import type { SpriteSheet } from '../types/thumbnail';
 import type { VideoMetadata } from '../types/video';
 
 interface VideoState {
@@ -6,13 +7,18 @@ interface VideoState {
   metadata: VideoMetadata | null;
   currentTime: number;
   seekTo: number | null;
-  thumbnails: string[];
+  /** Strip slots in timeline order; `null` until that thumbnail has been generated */
+  thumbnails: (string | null)[];
+  /** Dense thumbnails for hover previews, once generated */
+  sprite: SpriteSheet | null;
   togglePlaySignal: number | null;
   setVideoFile: (path: string | null) => void;
   setMetadata: (metadata: VideoMetadata | null) => void;
   setCurrentTime: (time: number) => void;
   setSeekTo: (time: number | null) => void;
-  setThumbnails: (urls: string[]) => void;
+  setThumbnails: (urls: (string | null)[]) => void;
+  setThumbnail: (index: number, url: string) => void;
+  setSprite: (sprite: SpriteSheet | null) => void;
   setTogglePlay: () => void;
   clearTogglePlay: () => void;
   reset: () => void;
@@ -24,12 +30,18 @@ export const useVideoStore = create<VideoState>()((set) => ({
   currentTime: 0,
   seekTo: null,
   thumbnails: [],
+  sprite: null,
   togglePlaySignal: null,
   setVideoFile: (path) => set({ videoFile: path }),
   setMetadata: (metadata) => set({ metadata }),
   setCurrentTime: (time) => set({ currentTime: time }),
   setSeekTo: (time) => set({ seekTo: time }),
   setThumbnails: (urls) => set({ thumbnails: urls }),
+  setThumbnail: (index, url) =>
+    set((state) => ({
+      thumbnails: state.thumbnails.map((existing, i) => (i === index ? url : existing)),
+    })),
+  setSprite: (sprite) => set({ sprite }),
   setTogglePlay: () => set((state) => ({ togglePlaySignal: (state.togglePlaySignal ?? 0) + 1 })),
   clearTogglePlay: () => set({ togglePlaySignal: null }),
   reset: () =>
@@ -39,6 +51,7 @@ export const useVideoStore = create<VideoState>()((set) => ({
       currentTime: 0,
       seekTo: null,
       thumbnails: [],
+      sprite: null,
       togglePlaySignal: null,
     }),
 }));
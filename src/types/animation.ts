@@ -0,0 +1,21 @@
+export type AnimationFormat = 'gif' | 'webp';
+
+export interface AnimationRequest {
+  videoPath: string;
+  startSecs: number;
+  endSecs: number;
+  outputPath: string;
+  format: AnimationFormat;
+  fps: number;
+  width: number;
+  maxSizeBytes: number | null;
+}
+
+export interface AnimationExport {
+  path: string;
+  sizeBytes: number;
+  fps: number;
+  width: number;
+  attempts: number;
+  withinTarget: boolean;
+}
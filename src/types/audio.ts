@@ -0,0 +1,19 @@
+import type { TimeInterval } from './partition';
+
+export type AudioFormat = 'm4a' | 'mp3' | 'flac' | 'wav';
+
+export type AudioMode = 'encode' | 'copy';
+
+export interface AudioExportRequest {
+  inputPath: string;
+  outputDir: string;
+  format: AudioFormat;
+  mode: AudioMode;
+  bitrateKbps: number | null;
+  track: number | null;
+  exclusions: TimeInterval[];
+  targetSizeBytes: number | null;
+  targetDurationSecs: number | null;
+  namingTemplate: string | null;
+  scratchDir: string | null;
+}
@@ -0,0 +1,20 @@
+export type CaptureFormat = 'jpeg' | 'png' | 'webp';
+
+export interface CaptureSettings {
+  version: number;
+  format: CaptureFormat;
+  lossless: boolean;
+  width: number | null;
+  height: number | null;
+  outputDir: string | null;
+  embedMetadata: boolean;
+}
+
+export type Burst =
+  | { mode: 'interval'; count: number; intervalSecs: number }
+  | { mode: 'range'; durationSecs: number };
+
+export interface CapturedFrame {
+  path: string;
+  timestampSecs: number;
+}
@@ -0,0 +1,26 @@
+import type { AppError } from './error';
+import type { CaptureFormat } from './capture';
+
+export interface ContactSheetOptions {
+  columns: number;
+  rows: number;
+  width: number;
+  format: CaptureFormat;
+  outputDir: string | null;
+}
+
+export interface ContactSheet {
+  videoPath: string;
+  path: string;
+}
+
+export interface ContactSheetBatch {
+  sheets: ContactSheet[];
+  failures: { videoPath: string; error: AppError }[];
+}
+
+export interface ContactSheetProgress {
+  index: number;
+  total: number;
+  videoPath: string;
+}
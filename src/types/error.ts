@@ -0,0 +1,21 @@
+export type ErrorCode =
+  | 'sidecar_missing'
+  | 'not_found'
+  | 'permission_denied'
+  | 'disk_full'
+  | 'unsupported_media'
+  | 'ffmpeg_failed'
+  | 'io'
+  | 'invalid_input'
+  | 'cancelled'
+  | 'internal';
+
+/** Error returned by every backend command. */
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  path: string | null;
+  exitCode: number | null;
+  stderr: string | null;
+  retryable: boolean;
+}
@@ -1,3 +1,5 @@
+import type { EncodeProfile } from './preset';
+
 export interface TimeInterval {
   startSecs: number;
   endSecs: number;
@@ -10,11 +12,74 @@ export interface PartitionPoint {
   estimatedSizeBytes: number;
 }
 
+export type HashAlgorithm = 'sha256' | 'blake3';
+
 export interface SplitRequest {
   inputPath: string;
   outputDir: string;
   targetSizeBytes: number;
+  /** Replaces the size target when set */
+  targetDurationSecs?: number | null;
   exclusions: TimeInterval[];
+  encodeProfile?: EncodeProfile;
+  namingTemplate?: string | null;
+  checksums?: HashAlgorithm[];
+  scratchDir?: string | null;
+  workerCount?: number;
+}
+
+export interface ByteSplitRequest {
+  inputPath: string;
+  outputDir: string;
+  targetSizeBytes: number;
+  checksums?: HashAlgorithm[];
+}
+
+export interface SplitProgress {
+  completedPartitions: number;
+  totalPartitions: number;
+  percent: number;
 }
 
 export type ProcessingStatus = 'idle' | 'calculating' | 'processing' | 'complete' | 'error';
+
+export interface PreflightReport {
+  requiredBytes: number;
+  partitionBytes: number;
+  availableBytes: number;
+  sufficient: boolean;
+}
+
+export interface NormalizedExclusions {
+  intervals: TimeInterval[];
+  warnings: string[];
+}
+
+export type DetectionKind = 'black' | 'freeze' | 'silence';
+
+export interface ExclusionSuggestion {
+  interval: TimeInterval;
+  confidence: number;
+  reason: string;
+  kinds: DetectionKind[];
+}
+
+export interface PlannedSegment {
+  interval: TimeInterval;
+  keyframeStartSecs: number | null;
+}
+
+export interface PartitionPlan {
+  index: number;
+  outputPath: string;
+  estimatedSizeBytes: number;
+  segments: PlannedSegment[];
+  ffmpegArgs: string[];
+  concatList: string | null;
+}
+
+export interface SplitPlan {
+  partitions: PartitionPlan[];
+  space: PreflightReport;
+  warnings: string[];
+}
@@ -0,0 +1,13 @@
+export type SplitStrategy = 'size' | 'duration';
+
+export type EncodeProfile = 'copy' | 'h264' | 'hevc';
+
+export interface SplitPreset {
+  name: string;
+  strategy: SplitStrategy;
+  /** Bytes for the size strategy, seconds for the duration strategy */
+  target: number;
+  encodeProfile: EncodeProfile;
+  namingTemplate: string;
+  outputDir: string | null;
+}
@@ -0,0 +1,13 @@
+import type { TimeInterval } from './partition';
+
+export interface Bookmark {
+  timeSecs: number;
+  label: string;
+}
+
+export interface ProjectState {
+  exclusions: TimeInterval[];
+  splitPoints: number[];
+  preset: string | null;
+  bookmarks: Bookmark[];
+}
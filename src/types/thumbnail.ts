@@ -0,0 +1,40 @@
+/** Payload of the `thumbnail-ready` event. */
+export interface ThumbnailReady {
+  videoPath: string;
+  index: number;
+  total: number;
+  path: string;
+}
+
+export interface CacheEntry {
+  key: string;
+  sourcePath: string;
+  variant: string;
+  sizeBytes: number;
+  lastUsedMillis: number;
+}
+
+export interface ThumbnailCacheInfo {
+  directory: string;
+  totalBytes: number;
+  limitBytes: number;
+  entries: CacheEntry[];
+}
+
+export type ThumbnailLayout = 'files' | 'sprite';
+
+/** A tiled thumbnail image; tile `i` starts at `i * intervalSecs`. */
+export interface SpriteSheet {
+  imagePath: string;
+  vttPath: string;
+  count: number;
+  columns: number;
+  rows: number;
+  tileWidth: number;
+  tileHeight: number;
+  intervalSecs: number;
+}
+
+export type ThumbnailStrip =
+  | { layout: 'files'; paths: string[] }
+  | ({ layout: 'sprite' } & SpriteSheet);
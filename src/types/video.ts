@@ -9,4 +9,10 @@ export interface VideoMetadata {
   audioCodec: string | null;
   bitrate: number;
   format: string;
+  frameRate: number;
+}
+
+export interface PreviewSource {
+  path: string;
+  transcoded: boolean;
 }
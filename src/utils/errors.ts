@@ -0,0 +1,22 @@
+import type { AppError, ErrorCode } from '../types/error';
+
+/** What the user can do about each kind of failure. */
+const HINTS: Partial<Record<ErrorCode, string>> = {
+  sidecar_missing: 'The bundled FFmpeg is missing or blocked. Reinstall the app or allow it in your antivirus.',
+  not_found: 'The file may have been moved, renamed or deleted.',
+  permission_denied: 'Close other programs using the file, or choose a folder you can write to.',
+  disk_full: 'Free up space or choose an output folder on another drive.',
+  unsupported_media: 'This file uses a codec or container FFmpeg cannot stream-copy.',
+};
+
+export const isAppError = (err: unknown): err is AppError =>
+  typeof err === 'object' && err !== null && 'code' in err && 'message' in err;
+
+/** Turns anything thrown by `invoke` into a message, with a hint for known failures. */
+export const describeError = (err: unknown): string => {
+  if (isAppError(err)) {
+    const hint = HINTS[err.code];
+    return hint ? `${err.message}. ${hint}` : err.message;
+  }
+  return err instanceof Error ? err.message : String(err);
+};